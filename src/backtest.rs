@@ -0,0 +1,270 @@
+use anyhow::{Context, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use std::fs::read_to_string;
+use crate::config::{parse_sol, validate_martingale, MartingaleConfig, OnMaxLosses};
+use crate::mining::ev;
+use crate::mining::grid::{self, SelectionContext, TOTAL_BLOCKS};
+use crate::mining::strategy::new_strategy;
+use crate::ore::state::{winning_square_from_hash, Round};
+
+const DEFAULT_STARTING_BALANCE: u64 = 10_000_000_000; // 10 SOL
+const ROUND_DURATION_MINUTES: u64 = 1; // Rounds last about a minute, used to size cooldowns
+
+/// One settled round from the history file
+#[derive(Debug, Clone)]
+pub struct HistoricalRound {
+    pub round_id: u64,
+    pub winning_square: u8,
+    pub deployed: [u64; TOTAL_BLOCKS], // Final SOL (lamports) deployed per square by other miners
+}
+
+/// A round as written in the history file: the winning square, or the slot hash it comes from
+#[derive(Deserialize)]
+struct HistoryEntry {
+    round_id: u64,
+    #[serde(default)]
+    winning_square: Option<u8>,
+    #[serde(default)]
+    slot_hash: Option<String>, // 64 hex chars, as stored in the SQLite history
+    deployed: [u64; TOTAL_BLOCKS],
+}
+
+impl HistoryEntry {
+    fn resolve(self) -> Result<HistoricalRound> {
+        let winning_square = match (self.winning_square, &self.slot_hash) {
+            (Some(square), _) => square,
+            (None, Some(hash)) => {
+                let hash = parse_slot_hash(hash)
+                    .with_context(|| format!("Round #{} has an invalid slot_hash", self.round_id))?;
+                winning_square_from_hash(&hash)
+                    .with_context(|| format!("Round #{} slot_hash has no RNG (round never settled)", self.round_id))? as u8
+            }
+            (None, None) => anyhow::bail!("Round #{} needs a winning_square or a slot_hash", self.round_id),
+        };
+        Ok(HistoricalRound { round_id: self.round_id, winning_square, deployed: self.deployed })
+    }
+}
+
+fn parse_slot_hash(hex: &str) -> Result<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        anyhow::bail!("expected 64 hex characters");
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)?;
+    }
+    Ok(hash)
+}
+
+/// Outcome of replaying the history with one strategy config
+#[derive(Debug, Clone, Default)]
+pub struct BacktestReport {
+    pub label: String,
+    pub rounds_played: u32,
+    pub rounds_skipped: u32,      // EV guard or cooldown
+    pub wins: u32,
+    pub losses: u32,
+    pub total_bet: u64,
+    pub total_returned: u64,
+    pub pnl: i64,                 // Final balance - starting balance (lamports)
+    pub max_drawdown: u64,        // Largest drop from a P&L peak (lamports)
+    pub longest_loss_streak: u32,
+    pub stopped_at_round: Option<u64>, // Round where the run ended early (max losses with "stop", or out of funds)
+}
+
+impl BacktestReport {
+    pub fn print(&self) {
+        println!("📊 {}", self.label);
+        println!("   Rounds: {} played, {} skipped ({} wins / {} losses)",
+            self.rounds_played, self.rounds_skipped, self.wins, self.losses);
+        println!("   Wagered: {:.6} SOL, returned: {:.6} SOL",
+            self.total_bet as f64 / 1e9,
+            self.total_returned as f64 / 1e9);
+        println!("   P&L: {:+.6} SOL, max drawdown: {:.6} SOL, longest loss streak: {}",
+            self.pnl as f64 / 1e9,
+            self.max_drawdown as f64 / 1e9,
+            self.longest_loss_streak);
+        if let Some(round_id) = self.stopped_at_round {
+            println!("   🛑 Stopped early at round #{}", round_id);
+        }
+    }
+}
+
+/// Read a JSON array of historical rounds, oldest first
+pub fn load_history(path: &str) -> Result<Vec<HistoricalRound>> {
+    let contents = read_to_string(path)
+        .with_context(|| format!("Failed to read history file: {}", path))?;
+    let entries: Vec<HistoryEntry> = serde_json::from_str(&contents)
+        .context("Failed to parse history JSON")?;
+    let history = entries.into_iter()
+        .map(HistoryEntry::resolve)
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(round) = history.iter().find(|r| r.winning_square as usize >= TOTAL_BLOCKS) {
+        anyhow::bail!("Round #{} has winning_square {} (must be 0-24)", round.round_id, round.winning_square);
+    }
+
+    Ok(history)
+}
+
+/// Read the martingale settings from a full bot config or a file holding just the `martingale` section
+pub fn load_strategy_config(path: &str) -> Result<MartingaleConfig> {
+    let contents = read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path))?;
+    let mut value: serde_json::Value = serde_json::from_str(&contents)
+        .context("Failed to parse config JSON")?;
+
+    if let Some(section) = value.get_mut("martingale") {
+        value = section.take();
+    }
+
+    let mut config: MartingaleConfig = serde_json::from_value(value)
+        .with_context(|| format!("Invalid martingale settings in {}", path))?;
+    validate_martingale(&mut config)?;
+    Ok(config)
+}
+
+/// Replay `history` through the configured strategy and block selection
+///
+/// Payouts follow `ev::payout_if_wins` (SOL only: protocol fees and ORE rewards are not modelled),
+/// and our bet is assumed not to change anyone else's deployment.
+pub fn simulate(label: &str, history: &[HistoricalRound], config: &MartingaleConfig, starting_balance: u64, seed: u64) -> BacktestReport {
+    let mut strategy = new_strategy(config);
+    let selector = grid::build_selector(&config.block_selection);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut report = BacktestReport { label: label.to_string(), ..Default::default() };
+    let mut balance = starting_balance;
+    let mut peak_pnl: i64 = 0;
+    let mut loss_streak: u32 = 0;
+    let mut cooldown_rounds: u64 = 0;
+
+    for historical in history {
+        if cooldown_rounds > 0 {
+            cooldown_rounds -= 1;
+            report.rounds_skipped += 1;
+            continue;
+        }
+
+        let mut round: Round = bytemuck::Zeroable::zeroed();
+        round.id = historical.round_id;
+        round.deployed = historical.deployed;
+        round.total_deployed = historical.deployed.iter().sum();
+
+        let blocks = {
+            let previous_winner = strategy.state().last_winning_square;
+            let mut ctx = SelectionContext { round: &round, previous_winner, rng: &mut rng };
+            selector.select(strategy.state().blocks_per_bet(config), &mut ctx)
+        };
+        let block_indices: Vec<u8> = blocks.iter().map(|b| b.index).collect();
+
+        strategy.state_mut().update_balance(config, balance);
+        let bet_per_block = strategy.next_bet();
+        let total_bet = bet_per_block * block_indices.len() as u64;
+
+        // Same EV guard as the live bot
+        let ratio = ev::ev_ratio(&round.deployed, &block_indices, bet_per_block);
+        let expected_sol = ev::estimate_ev(&round, &block_indices, bet_per_block, config.ore_price_sol) / 1e9;
+        let skip = matches!(config.min_ev_ratio, Some(min) if ratio < min)
+            || matches!(config.min_ev_threshold, Some(min) if expected_sol < min);
+        if skip {
+            report.rounds_skipped += 1;
+            continue;
+        }
+
+        if total_bet > balance {
+            report.stopped_at_round = Some(historical.round_id);
+            break;
+        }
+
+        strategy.record_bet(historical.round_id, total_bet);
+        balance -= total_bet;
+        report.total_bet += total_bet;
+        report.rounds_played += 1;
+
+        let mut stop = false;
+        if block_indices.contains(&historical.winning_square) {
+            let payout = ev::payout_if_wins(&round.deployed, &block_indices, historical.winning_square, bet_per_block) as u64;
+            balance += payout;
+            report.total_returned += payout;
+            report.wins += 1;
+            loss_streak = 0;
+            strategy.update_earnings(0, payout);
+            strategy.on_win(config);
+        } else {
+            report.losses += 1;
+            loss_streak += 1;
+            report.longest_loss_streak = report.longest_loss_streak.max(loss_streak);
+            let (should_continue, _) = strategy.on_loss(config);
+            if !should_continue {
+                match config.on_max_losses {
+                    OnMaxLosses::Stop => stop = true,
+                    OnMaxLosses::Cooldown => cooldown_rounds = config.cooldown_rounds
+                        .unwrap_or(config.cooldown_minutes / ROUND_DURATION_MINUTES),
+                }
+            }
+        }
+        strategy.state_mut().last_winning_square = Some(historical.winning_square);
+
+        let pnl = balance as i64 - starting_balance as i64;
+        peak_pnl = peak_pnl.max(pnl);
+        report.max_drawdown = report.max_drawdown.max((peak_pnl - pnl) as u64);
+
+        if stop {
+            report.stopped_at_round = Some(historical.round_id);
+            break;
+        }
+    }
+
+    report.pnl = balance as i64 - starting_balance as i64;
+    report
+}
+
+/// `backtest <history.json> [config.json ...] [--balance SOL] [--seed N]`
+///
+/// Every config is replayed against the same history with the same seed so the reports compare
+/// like for like. Without a config, `default_config` is used.
+pub fn run(args: &[String], default_config: &str) -> Result<()> {
+    let mut positional = Vec::new();
+    let mut starting_balance = DEFAULT_STARTING_BALANCE;
+    let mut seed = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--balance" => {
+                let value = iter.next().context("--balance needs a value in SOL")?;
+                starting_balance = parse_sol(value)
+                    .with_context(|| format!("Invalid --balance: {}", value))?;
+            }
+            "--seed" => {
+                let value = iter.next().context("--seed needs a value")?;
+                seed = Some(value.parse::<u64>()
+                    .with_context(|| format!("Invalid --seed: {}", value))?);
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let (history_path, config_paths) = positional.split_first()
+        .context("Usage: backtest <history.json> [config.json ...] [--balance SOL] [--seed N]")?;
+    let config_paths = if config_paths.is_empty() { vec![default_config] } else { config_paths.to_vec() };
+
+    if starting_balance == 0 {
+        anyhow::bail!("--balance must be > 0");
+    }
+    let seed = seed.unwrap_or_else(|| rand::rng().random());
+
+    let history = load_history(history_path)?;
+    println!("🔁 Backtesting {} rounds from {} (starting balance {:.4} SOL, seed {})",
+        history.len(), history_path, starting_balance as f64 / 1e9, seed);
+
+    for path in config_paths {
+        let config = load_strategy_config(path)?;
+        simulate(path, &history, &config, starting_balance, seed).print();
+    }
+
+    Ok(())
+}
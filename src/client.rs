@@ -1,7 +1,9 @@
+use solana_client::client_error::{ClientError, ClientErrorKind};
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
 use anyhow::Result;
+use reqwest::StatusCode;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -10,14 +12,15 @@ pub struct SolanaClient {
 }
 
 impl SolanaClient {
-    pub async fn new(rpc_url: &str) -> Result<Self> {
-        let rpc = RpcClient::new_with_commitment(
-            rpc_url.to_string(),
-            CommitmentConfig { commitment: CommitmentLevel::Confirmed },
-        );
+    pub async fn new(rpc_url: &str, commitment: CommitmentConfig) -> Result<Self> {
+        let rpc = RpcClient::new_with_commitment(rpc_url.to_string(), commitment);
 
         // Test connection
-        let block_height = rpc.get_block_height().await?;
+        let block_height = rpc.get_block_height().await
+            .map_err(|e| {
+                let reason = connect_error_reason(&e);
+                anyhow::Error::new(e).context(format!("RPC endpoint {} {}", endpoint_host(rpc_url), reason))
+            })?;
         log::info!("Connected to Solana cluster. Block height: {}", block_height);
 
         Ok(Self { rpc: Arc::new(rpc) })
@@ -27,4 +30,47 @@ impl SolanaClient {
         let balance = self.rpc.get_balance(pubkey).await?;
         Ok(balance)
     }
+
+    /// Estimate a compute unit price (micro-lamports) as the given percentile of recent
+    /// prioritization fees paid by transactions that write-lock `accounts`
+    pub async fn get_priority_fee_estimate(&self, accounts: &[Pubkey], percentile: u8) -> Result<u64> {
+        let fees = self.rpc.get_recent_prioritization_fees(accounts).await?;
+        let mut values: Vec<u64> = fees.iter().map(|f| f.prioritization_fee).collect();
+        Ok(percentile_of(&mut values, percentile))
+    }
+}
+
+/// Actionable reason for a failed startup connection check
+fn connect_error_reason(e: &ClientError) -> &'static str {
+    match e.kind() {
+        ClientErrorKind::Reqwest(err) => match err.status() {
+            Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) =>
+                "rejected the request as unauthorized: check the API key in rpc_url",
+            Some(StatusCode::NOT_FOUND) => "returned 404: check the path in rpc_url",
+            Some(_) => "returned an HTTP error",
+            None if err.is_timeout() => "timed out: check rpc_url and your network",
+            None if err.is_connect() => "is unreachable: check rpc_url and your network",
+            None => "failed the connection check",
+        },
+        ClientErrorKind::SerdeJson(_) => "did not answer with JSON-RPC: is rpc_url a Solana RPC endpoint?",
+        _ => "failed the connection check",
+    }
+}
+
+/// Host part of the RPC URL, so API keys in the path or query don't end up in error messages
+fn endpoint_host(rpc_url: &str) -> String {
+    reqwest::Url::parse(rpc_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| rpc_url.to_string())
+}
+
+/// Nearest-rank percentile (0-100) of a set of values; 0 for an empty set
+pub fn percentile_of(values: &mut [u64], percentile: u8) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let rank = (percentile.min(100) as f64 / 100.0 * values.len() as f64).ceil() as usize;
+    values[rank.saturating_sub(1).min(values.len() - 1)]
 }
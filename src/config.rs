@@ -1,54 +1,506 @@
 use serde::{Deserialize, Serialize};
 use anyhow::{Context, Result};
 use std::fs::read_to_string;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use crate::mining::grid::{perimeter_squares, TOTAL_BLOCKS};
+use crate::notifier::Mention;
+use crate::schedule::Schedule;
+use zeroize::Zeroizing;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct BotConfig {
     pub rpc_url: String,
-    pub private_key: String,
+    #[serde(default)]
+    pub ws_url: Option<String>,       // WebSocket endpoint (default: derived from rpc_url)
+    #[serde(default = "default_board_stale_secs")]
+    pub board_stale_secs: u64,        // Board stream older than this falls back to RPC (default: 90, about one round)
+    #[serde(default, deserialize_with = "deserialize_private_key")]
+    pub private_key: Zeroizing<String>, // Base58 private key, keypair file path, 64-byte JSON array, seed phrase, or "env:VAR_NAME"
+    #[serde(default)]
+    pub encrypted_key_path: Option<String>, // Key file written by `keygen encrypt`; replaces private_key (default: off)
+    #[serde(default)]
+    pub wallets: Vec<WalletConfig>,   // Several wallets betting side by side from one process (default: just private_key)
+    #[serde(skip)]
+    pub wallet_name: Option<String>,  // Set on the per-wallet configs built from `wallets`
+    #[serde(default)]
+    pub key_source: KeySource,        // How to read private_key: "auto" (default), "base58", "file" or "mnemonic"
+    #[serde(default)]
+    pub derivation_account_index: Option<u32>, // Mnemonic account: m/44'/501'/N'/0' (default: 0)
     pub martingale: MartingaleConfig,
     pub monitoring: MonitoringConfig,
-    pub discord: DiscordConfig,
+    #[serde(default = "default_discord_config")]
+    pub discord: DiscordConfig,       // Discord webhooks (section optional: missing = disabled)
+    #[serde(default)]
+    pub telegram: Option<TelegramConfig>, // Telegram notifications (default: off)
+    #[serde(default)]
+    pub notifier: NotifierKind,       // "auto" (default) or "stdout" to log notifications instead of sending them
+    #[serde(default)]
+    pub transaction: TransactionConfig,
+    #[serde(default = "default_state_file")]
+    pub state_file: String,           // Where betting state is persisted (default: state.json)
+    #[serde(default)]
+    pub ledger_file: Option<String>,  // Append one CSV row per completed round here (default: off)
+    #[serde(default)]
+    pub history: Option<HistoryConfig>, // SQLite history of rounds, bets and outcomes (default: off)
+    #[serde(default)]
+    pub status: Option<StatusConfig>, // Local HTTP status/pause endpoint (default: off)
+    #[serde(default)]
+    pub pause_file: Option<String>,   // No new bets while this file exists (default: off)
+    #[serde(default)]
+    pub schedule: ScheduleConfig,     // Only bet within these UTC hours (default: always)
+    #[serde(default)]
+    pub dry_run: bool,                // Paper-trade: skip transaction submission (default: false)
+    #[serde(default)]
+    pub log_format: LogFormat,        // "text" (default) or "json" (one object per line)
+}
+
+/// Log output format
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Human-readable env_logger output
+    #[default]
+    Text,
+    /// One JSON object per line for log aggregators
+    Json,
+}
+
+/// `private_key` as a string, or as the Solana CLI byte array written inline (kept as its JSON text)
+/// Wiped from memory when the config is dropped
+fn deserialize_private_key<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Zeroizing<String>, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(key) => Ok(Zeroizing::new(key)),
+        array @ serde_json::Value::Array(_) => Ok(Zeroizing::new(array.to_string())),
+        _ => Err(serde::de::Error::custom("private_key must be a string or a JSON array of 64 bytes")),
+    }
+}
+
+fn default_board_stale_secs() -> u64 {
+    90
+}
+
+fn default_state_file() -> String {
+    "state.json".to_string()
+}
+
+impl BotConfig {
+    /// WebSocket endpoint: `ws_url` if set, otherwise rpc_url with http(s) swapped for ws(s)
+    pub fn ws_url(&self) -> String {
+        match &self.ws_url {
+            Some(url) => url.clone(),
+            None => self.rpc_url
+                .replace("https://", "wss://")
+                .replace("http://", "ws://"),
+        }
+    }
+
+    /// State file actually used: dry runs get their own file (state.dry-run.json)
+    /// so paper trades never move the live martingale cycle
+    pub fn effective_state_file(&self) -> PathBuf {
+        self.dry_run_aware(&self.state_file)
+    }
+
+    /// Ledger file actually used, if enabled: dry runs write to ledger.dry-run.csv and the like
+    pub fn effective_ledger_file(&self) -> Option<PathBuf> {
+        self.ledger_file.as_deref().map(|path| self.dry_run_aware(path))
+    }
+
+    /// History database actually used, if enabled: dry runs write to history.dry-run.sqlite and the like
+    pub fn effective_history_db(&self) -> Option<PathBuf> {
+        self.history.as_ref().map(|history| self.dry_run_aware(&history.sqlite_path))
+    }
+
+    /// `path` as is, or with ".dry-run" inserted before the extension in dry-run mode
+    fn dry_run_aware(&self, path: &str) -> PathBuf {
+        if self.dry_run { tagged_path(path, "dry-run") } else { PathBuf::from(path) }
+    }
+
+    /// The config `wallet` runs with: its key and (if given) martingale section on top of
+    /// everything else, and its own state, ledger and history files (state.<name>.json and the like)
+    pub fn for_wallet(&self, wallet: &WalletConfig) -> BotConfig {
+        let tag = |path: &str| tagged_path(path, &wallet.name).to_string_lossy().into_owned();
+        let mut config = self.clone();
+        config.private_key = wallet.private_key.clone();
+        config.key_source = wallet.key_source;
+        config.derivation_account_index = wallet.derivation_account_index;
+        config.encrypted_key_path = None;
+        if let Some(martingale) = &wallet.martingale {
+            config.martingale = martingale.clone();
+        }
+        config.state_file = tag(&self.state_file);
+        config.ledger_file = self.ledger_file.as_deref().map(tag);
+        if let Some(history) = &mut config.history {
+            history.sqlite_path = tag(&history.sqlite_path);
+        }
+        config.wallet_name = Some(wallet.name.clone());
+        config
+    }
+
+    /// One config per entry of `wallets`, or just this one without them
+    pub fn wallet_configs(&self) -> Vec<BotConfig> {
+        if self.wallets.is_empty() {
+            return vec![self.clone()];
+        }
+        self.wallets.iter().map(|wallet| self.for_wallet(wallet)).collect()
+    }
+}
+
+/// `path` with ".<tag>" inserted before the extension (state.json → state.<tag>.json)
+fn tagged_path(path: &str, tag: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    let stem = path.file_stem().map_or("state".into(), |s| s.to_string_lossy().into_owned());
+    match path.extension() {
+        Some(ext) => path.with_file_name(format!("{}.{}.{}", stem, tag, ext.to_string_lossy())),
+        None => path.with_file_name(format!("{}.{}", stem, tag)),
+    }
+}
+
+/// One wallet of a multi-wallet setup; anything not set here comes from the top level
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WalletConfig {
+    pub name: String,                 // Shown on its notifications and used in its file names
+    #[serde(default, deserialize_with = "deserialize_private_key")]
+    pub private_key: Zeroizing<String>, // Same forms as the top-level private_key
+    #[serde(default)]
+    pub key_source: KeySource,
+    #[serde(default)]
+    pub derivation_account_index: Option<u32>,
+    #[serde(default)]
+    pub martingale: Option<MartingaleConfig>, // Own strategy (default: the top-level martingale section)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HistoryConfig {
+    pub sqlite_path: String,          // Database file, created with its tables on first start
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct MartingaleConfig {
-    pub base_bet_amount: f64,         // Initial bet in SOL (e.g., 0.01)
+    pub base_bet_amount: f64,         // Initial bet in SOL (e.g., 0.01); fixed mode, or fallback before the balance is known
+    #[serde(default, alias = "bet_mode")]
+    pub base_bet_mode: BaseBetMode,   // "fixed" (default) or "percent" of wallet balance
+    #[serde(default)]
+    pub base_bet_percent: f64,        // Percent mode: base bet per block as % of balance (e.g., 0.5)
     pub max_consecutive_losses: u8,   // Max losses before reset (bet doubles each loss)
     pub warn_consecutive_losses: u8,  // Send Discord warning at this loss count
     pub blocks_per_bet: u8,           // Number of grid blocks to bet on (1-25)
+    #[serde(default)]
+    pub blocks_per_loss_level: Vec<u8>, // Blocks to bet after 0, 1, 2... losses in a row (default: blocks_per_bet throughout)
     #[serde(default = "default_multiplier")]
     pub multiplier: f64,              // Bet multiplier on loss (default: 2.0)
+    #[serde(default)]
+    pub strategy: StrategyKind,       // Betting progression (default: martingale)
+    #[serde(default = "default_win_streak_cap")]
+    pub win_streak_cap: u8,           // Paroli: wins in a row before going back to base (default: 3)
+    #[serde(default)]
+    pub stop_on_max_losses: bool,     // Flat: apply on_max_losses at max_consecutive_losses instead of only notifying (default: false)
+    #[serde(default)]
+    pub custom_sequence: Vec<f64>,    // Custom: base bet multipliers after 1, 2, 3... losses (e.g. [2, 3, 5, 8])
+    #[serde(default)]
+    pub block_selection: BlockSelection, // How squares are picked each round (default: random)
+    #[serde(default, skip_serializing)]
+    pub fixed_blocks: Option<Vec<u8>>, // Shorthand for block_selection {"fixed": [...]}
+    #[serde(default)]
+    pub max_bet_per_block_sol: Option<f64>, // Absolute cap on the bet per block in SOL (default: no cap)
+    #[serde(default)]
+    pub min_bet_per_block_sol: Option<f64>, // Floor on the bet per block in SOL (default: 0.000001, the smallest deploy accepted)
+    #[serde(default)]
+    pub on_max_losses: OnMaxLosses,   // "stop" (default) or "cooldown" when max_consecutive_losses is hit
+    #[serde(default = "default_cooldown_minutes")]
+    pub cooldown_minutes: u64,        // Cooldown mode: pause before resuming at base bet (default: 30)
+    #[serde(default)]
+    pub cooldown_rounds: Option<u64>, // Cooldown mode: skip this many rounds instead of waiting cooldown_minutes
+    #[serde(default)]
+    pub min_ev_ratio: Option<f64>,    // Skip rounds whose expected return per SOL staked is below this (e.g. 0.9)
+    #[serde(default)]
+    pub min_ev_threshold: Option<f64>, // Skip rounds whose expected net result in SOL is below this (e.g. 0.0)
+    #[serde(default)]
+    pub ore_price_sol: Option<f64>,   // ORE price in SOL, counts the motherlode in the expected net result (default: motherlode ignored)
+    #[serde(default)]
+    pub notify_ev_skips: bool,        // Send a Discord notice when a round is skipped for low EV (default: false)
+    #[serde(default)]
+    pub win_rate_guard: Option<WinRateGuardConfig>, // Back off while the recent win rate is below a floor (default: off)
+}
+
+/// Back off while the win rate over the last `window_rounds` rounds is below `min_win_rate`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WinRateGuardConfig {
+    pub window_rounds: usize,         // Rounds the win rate is measured over (at most monitoring.rolling_window_rounds)
+    pub min_win_rate: f64,            // Floor in percent (e.g. 8.0)
+    #[serde(default)]
+    pub action: WinRateGuardAction,   // "base_bet" (default) or "skip"
+    #[serde(default = "default_guard_skip_rounds")]
+    pub skip_rounds: u64,             // Skip mode: rounds to sit out before measuring a fresh window (default: 10)
+}
+
+/// What the win-rate guard does while the win rate is below the floor
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WinRateGuardAction {
+    /// Keep betting, but drop the progression back to the base bet
+    #[default]
+    BaseBet,
+    /// Sit out `skip_rounds` rounds, keeping the progression where it was
+    Skip,
 }
 
+fn default_guard_skip_rounds() -> u64 {
+    10
+}
+
+/// What to do when max_consecutive_losses is reached
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnMaxLosses {
+    /// Stop the bot
+    #[default]
+    Stop,
+    /// Pause for `cooldown_minutes`, then resume from the base bet
+    Cooldown,
+}
+
+/// Betting progression selected in config
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyKind {
+    /// Multiply the bet on every loss, back to base on a win
+    #[default]
+    Martingale,
+    /// Step forward through the Fibonacci sequence on a loss, two steps back on a win
+    Fibonacci,
+    /// Anti-martingale: multiply the bet on each win up to `win_streak_cap`, back to base on a loss
+    Paroli,
+    /// Base bet times `custom_sequence[n - 1]` after n losses in a row, back to base on a win
+    Custom,
+    /// Always the base bet; `max_consecutive_losses` only notifies unless `stop_on_max_losses`
+    Flat,
+}
+
+/// How the base bet of each cycle is sized
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BaseBetMode {
+    /// Always `base_bet_amount`
+    #[default]
+    Fixed,
+    /// `base_bet_percent` of the wallet balance, recalculated at the start of each cycle
+    #[serde(alias = "percent_balance")]
+    Percent,
+}
+
+/// Block selection mode selected in config
+/// Unit modes are plain strings (`"random"`); list modes are objects (`{"fixed": [0, 6, 12]}`)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockSelection {
+    /// Pick squares uniformly at random
+    #[default]
+    Random,
+    /// Pick the squares with the least SOL deployed in the current round
+    #[serde(alias = "least_crowded")]
+    LeastDeployed,
+    /// Pick at random among the 16 squares on the grid's outer ring
+    CornersEdges,
+    /// Pick at random, skipping the previous round's winning square
+    AvoidLastWinner,
+    /// Always bet on these squares (must list exactly `blocks_per_bet` squares)
+    Fixed(Vec<u8>),
+    /// Pick at random, never choosing these squares
+    Exclude(Vec<u8>),
+}
+
+/// Parse a decimal amount ("1.23456789") into base units with `decimals` fractional digits
+/// Exact fixed-point parsing: no sign, no exponent, no more fractional digits than the unit has
+pub fn parse_decimal_units(text: &str, decimals: u32) -> Result<u64> {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        anyhow::bail!("empty amount");
+    }
+    if !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+        anyhow::bail!("\"{}\" is not a non-negative decimal number", text);
+    }
+    if fraction.len() > decimals as usize {
+        anyhow::bail!("\"{}\" has more than {} decimal places", text, decimals);
+    }
+
+    let scale = 10u64.pow(decimals);
+    let whole_units = if whole.is_empty() { 0 } else { whole.parse::<u64>()? }.checked_mul(scale);
+    let fraction_units = if fraction.is_empty() { 0 } else { fraction.parse::<u64>()? * 10u64.pow(decimals - fraction.len() as u32) };
+    whole_units
+        .and_then(|units| units.checked_add(fraction_units))
+        .with_context(|| format!("\"{}\" is too large", text))
+}
+
+/// Parse a decimal SOL amount into lamports exactly
+pub fn parse_sol(text: &str) -> Result<u64> {
+    parse_decimal_units(text, 9)
+}
+
+/// Lamports of a SOL amount from the config
+///
+/// Goes through the number's shortest decimal form (0.3 stays "0.3", not 0.29999…), so the amount
+/// written in config.json converts exactly. Only for amounts checked with `validate_sol` at load.
+pub fn sol_to_lamports(sol: f64) -> u64 {
+    parse_sol(&sol.to_string()).unwrap_or_else(|e| panic!("unvalidated SOL amount {}: {}", sol, e))
+}
+
+/// ORE base units (11 decimals) of an ORE amount from the config; same rules as `sol_to_lamports`
+pub fn ore_to_units(ore: f64) -> u64 {
+    parse_decimal_units(&ore.to_string(), 11).unwrap_or_else(|e| panic!("unvalidated ORE amount {}: {}", ore, e))
+}
+
+/// Check that `value` converts to lamports: finite, not negative, at most 9 decimals
+fn validate_sol(name: &str, value: f64) -> Result<u64> {
+    parse_sol(&value.to_string())
+        .with_context(|| format!("{} must be a SOL amount >= 0 with at most 9 decimals (got: {})", name, value))
+}
+
+/// Multiply a lamport amount by `multiplier` in integer math
+/// The multiplier is taken to 6 decimal places (2.0 → 2_000_000 ppm) and the product is rounded
+/// half up to the nearest lamport, so a progression gives the same lamports on every platform
+pub fn scale_lamports(lamports: u64, multiplier: f64) -> u64 {
+    const PPM: u128 = 1_000_000;
+    let multiplier_ppm = (multiplier * PPM as f64).round() as u128;
+    let scaled = (lamports as u128 * multiplier_ppm + PPM / 2) / PPM;
+    scaled.min(u64::MAX as u128) as u64
+}
+
+/// Smallest bet per block accepted (1000 lamports = 0.000001 SOL)
+pub const MIN_BET_LAMPORTS: u64 = 1000;
+
 impl MartingaleConfig {
     /// Convert SOL amount to lamports
     pub fn base_bet_lamports(&self) -> u64 {
-        (self.base_bet_amount * 1_000_000_000.0) as u64
+        sol_to_lamports(self.base_bet_amount)
+    }
+
+    /// Base bet for a new cycle given the latest wallet balance (None = not fetched yet)
+    pub fn base_bet_for_balance(&self, balance: Option<u64>) -> u64 {
+        match (self.base_bet_mode, balance) {
+            (BaseBetMode::Percent, Some(balance)) => percent_of_balance(balance, self.base_bet_percent)
+                .max(self.min_bet_per_block_lamports()),
+            _ => self.base_bet_lamports(),
+        }
+    }
+
+    /// Smallest bet per block: min_bet_per_block_sol, never below MIN_BET_LAMPORTS
+    pub fn min_bet_per_block_lamports(&self) -> u64 {
+        self.min_bet_per_block_sol
+            .map_or(MIN_BET_LAMPORTS, |floor| sol_to_lamports(floor).max(MIN_BET_LAMPORTS))
+    }
+
+    /// Blocks to bet with `losses` losses in a row: the blocks_per_loss_level entry, or blocks_per_bet past its end
+    pub fn blocks_for_losses(&self, losses: u8) -> u8 {
+        self.blocks_per_loss_level.get(losses as usize).copied().unwrap_or(self.blocks_per_bet)
+    }
+
+    /// Most blocks any loss level bets
+    pub fn max_blocks_per_bet(&self) -> u8 {
+        self.blocks_per_loss_level.iter().copied().fold(self.blocks_per_bet, u8::max)
+    }
+
+    /// Convert max_bet_per_block_sol to lamports
+    pub fn max_bet_per_block_lamports(&self) -> Option<u64> {
+        self.max_bet_per_block_sol.map(sol_to_lamports)
     }
 }
 
+/// `percent` % of `balance`, rounded down, never below MIN_BET_LAMPORTS
+/// Fixed-point like the SOL amounts: 0.57% of 0.1 SOL is 570_000 lamports, not 569_999 from 56.999…%
+pub fn percent_of_balance(balance: u64, percent: f64) -> u64 {
+    const DECIMALS: u32 = 15;
+    let units = parse_decimal_units(&format!("{:.*}", DECIMALS as usize, percent), DECIMALS)
+        .unwrap_or_else(|e| panic!("unvalidated percent {}: {}", percent, e));
+    let lamports = balance as u128 * units as u128 / (100 * 10u128.pow(DECIMALS));
+    (lamports as u64).max(MIN_BET_LAMPORTS)
+}
+
 fn default_multiplier() -> f64 {
     2.0
 }
 
+fn default_win_streak_cap() -> u8 {
+    3
+}
+
+fn default_cooldown_minutes() -> u64 {
+    30
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct MonitoringConfig {
     pub min_balance_sol: f64,         // Minimum balance in SOL (emergency stop threshold)
     #[serde(default = "default_auto_claim_threshold")]
     pub auto_claim_sol_threshold: f64, // Auto-claim SOL when rewards >= this (default: 0.1 SOL)
+    #[serde(default)]
+    pub auto_claim_ore_threshold: Option<f64>, // Auto-claim ORE when rewards >= this many ORE (default: never)
+    #[serde(default, alias = "daily_loss_limit_sol")]
+    pub daily_max_loss_sol: Option<f64>, // Pause betting for the rest of the UTC day past this net loss
+    #[serde(default)]
+    pub daily_profit_target_sol: Option<f64>, // Pause betting for the rest of the UTC day past this net profit
+    #[serde(default)]
+    pub max_session_loss_sol: Option<f64>, // Stop the bot once cumulative net loss reaches this
+    #[serde(default = "default_ws_stale_alert_minutes")]
+    pub ws_stale_alert_minutes: u64,  // Warn on Discord when the WebSocket is stale/down this long (default: 5)
+    #[serde(default = "default_rolling_window_rounds")]
+    pub rolling_window_rounds: usize, // Rounds in the rolling win rate shown in stats (default: 100)
+    #[serde(default)]
+    pub allow_underfunded: bool,      // Start (with a warning) even if the balance can't cover a full losing streak (default: false = refuse)
+    #[serde(default)]
+    pub daily_summary_hour_utc: Option<u8>, // Send a daily digest to the stats webhook at this hour (0-23 UTC, default: off)
+    #[serde(default)]
+    pub sweep_to_address: Option<String>, // Cold wallet that receives profits above sweep_above_sol (default: off)
+    #[serde(default)]
+    pub sweep_above_sol: Option<f64>, // SOL kept on top of the bankroll requirement before sweeping the rest
 }
 
 impl MonitoringConfig {
     /// Convert min_balance_sol to lamports
     pub fn min_balance_lamports(&self) -> u64 {
-        (self.min_balance_sol * 1_000_000_000.0) as u64
+        sol_to_lamports(self.min_balance_sol)
     }
 
     /// Convert auto_claim_sol_threshold to lamports
     pub fn auto_claim_sol_threshold_lamports(&self) -> u64 {
-        (self.auto_claim_sol_threshold * 1_000_000_000.0) as u64
+        sol_to_lamports(self.auto_claim_sol_threshold)
     }
+
+    /// Convert auto_claim_ore_threshold to ORE base units (11 decimals)
+    pub fn auto_claim_ore_threshold_units(&self) -> Option<u64> {
+        self.auto_claim_ore_threshold.map(ore_to_units)
+    }
+
+    /// Convert daily_max_loss_sol to lamports
+    pub fn daily_max_loss_lamports(&self) -> Option<u64> {
+        self.daily_max_loss_sol.map(sol_to_lamports)
+    }
+
+    /// Convert daily_profit_target_sol to lamports
+    pub fn daily_profit_target_lamports(&self) -> Option<u64> {
+        self.daily_profit_target_sol.map(sol_to_lamports)
+    }
+
+    /// Convert max_session_loss_sol to lamports
+    pub fn max_session_loss_lamports(&self) -> Option<u64> {
+        self.max_session_loss_sol.map(sol_to_lamports)
+    }
+
+    /// Cold wallet and the SOL kept above the bankroll requirement, when sweeping is configured
+    pub fn sweep(&self) -> Option<(Pubkey, u64)> {
+        let address = self.sweep_to_address.as_deref()?.parse().ok()?;
+        Some((address, sol_to_lamports(self.sweep_above_sol?)))
+    }
+}
+
+fn default_ws_stale_alert_minutes() -> u64 {
+    5
+}
+
+fn default_rolling_window_rounds() -> usize {
+    100
 }
 
 fn default_auto_claim_threshold() -> f64 {
@@ -57,59 +509,958 @@ fn default_auto_claim_threshold() -> f64 {
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DiscordConfig {
-    pub webhook_url: String,
-    pub stats_webhook_url: String,
-    pub warn_webhook_url: String,
+    #[serde(default = "default_discord_enabled")]
+    pub enabled: bool,                    // false = no webhooks, notifications go to the log (default: true)
+    #[serde(default)]
+    pub webhook_url: Option<String>,      // Main channel (bets, results, claims, errors)
+    #[serde(default)]
+    pub stats_webhook_url: Option<String>, // Stats channel (default: webhook_url)
+    #[serde(default)]
+    pub warn_webhook_url: Option<String>, // Warnings channel (default: webhook_url)
     #[serde(default = "default_stats_interval")]
     pub stats_notification_interval: u32,
+    #[serde(default = "default_explorer_url")]
+    pub explorer_url: String,             // Block explorer for transaction/account links (default: https://solscan.io)
+    #[serde(default)]
+    pub explorer_cluster: Option<String>, // Appended as ?cluster=... for non-mainnet clusters (e.g. "devnet")
+    #[serde(default = "default_show_grid")]
+    pub show_grid: bool,                  // Draw the 5x5 grid in bet/win/loss embeds (default: true)
+    #[serde(default = "default_min_interval_ms")]
+    pub min_interval_ms: u64,             // Minimum gap between Discord messages (default: 1000)
+    #[serde(default)]
+    pub notification_mode: NotificationMode, // "verbose" (default) or "summary"
+    #[serde(default)]
+    pub summary_win_threshold_sol: f64,   // Summary mode: wins with net profit >= this still get their own embed (default: 0)
+    #[serde(default = "default_discord_max_attempts")]
+    pub max_attempts: u8,                 // Tries per message on network errors, timeouts and 5xx (default: 3)
+    #[serde(default)]
+    pub warn_mention_role_id: Option<String>, // Role pinged on loss-streak warnings (default: no ping)
+    #[serde(default)]
+    pub critical_mention: Option<String>, // "here", "everyone" or a role id, pinged one loss from the limit (default: warn role)
+    #[serde(default)]
+    pub heartbeat_interval_minutes: u64,  // Post a short "alive" message to the stats channel this often (default: 0 = off)
+}
+
+impl DiscordConfig {
+    /// At least one non-blank webhook URL is configured
+    pub fn has_webhook(&self) -> bool {
+        [&self.webhook_url, &self.stats_webhook_url, &self.warn_webhook_url]
+            .iter()
+            .any(|url| url.as_deref().is_some_and(|u| !u.trim().is_empty()))
+    }
+}
+
+/// Telegram Bot API notifications
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TelegramConfig {
+    pub bot_token: String,            // Token from @BotFather
+    pub chat_id: String,              // Numeric chat id (as a string) or "@channel_username"
+}
+
+/// Betting windows
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ScheduleConfig {
+    #[serde(default)]
+    pub active_hours: Vec<String>,    // UTC ranges, e.g. ["00:00-06:00", "Sat,Sun 00:00-24:00"] (empty = always on)
+}
+
+/// Shortest accepted status.auth_token
+const MIN_STATUS_TOKEN_LEN: usize = 16;
+
+/// Local HTTP endpoint: GET /status, POST /pause and /resume
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StatusConfig {
+    pub listen_addr: String,          // e.g. "127.0.0.1:8080"
+    pub auth_token: String,           // Required on every request as "Authorization: Bearer <token>"
+}
+
+impl StatusConfig {
+    pub fn socket_addr(&self) -> Result<SocketAddr> {
+        self.listen_addr.parse()
+            .with_context(|| format!("status.listen_addr must be host:port, e.g. 127.0.0.1:8080 (got: {})", self.listen_addr))
+    }
+}
+
+/// Used when the `discord` section is left out entirely
+fn default_discord_config() -> DiscordConfig {
+    DiscordConfig {
+        enabled: false,
+        webhook_url: None,
+        stats_webhook_url: None,
+        warn_webhook_url: None,
+        stats_notification_interval: default_stats_interval(),
+        explorer_url: default_explorer_url(),
+        explorer_cluster: None,
+        show_grid: default_show_grid(),
+        min_interval_ms: default_min_interval_ms(),
+        notification_mode: NotificationMode::default(),
+        summary_win_threshold_sol: 0.0,
+        max_attempts: default_discord_max_attempts(),
+        warn_mention_role_id: None,
+        critical_mention: None,
+        heartbeat_interval_minutes: 0,
+    }
+}
+
+/// How `private_key` is interpreted
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// Detect Base58, keypair file or inline byte array from the value
+    #[default]
+    Auto,
+    /// Base58-encoded 64-byte secret key
+    Base58,
+    /// Solana CLI keypair file
+    File,
+    /// BIP39 seed phrase (12 or 24 words), derived like Phantom and Solflare
+    Mnemonic,
+}
+
+/// Where notifications go
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifierKind {
+    /// Discord and/or Telegram as configured, the log if neither is
+    #[default]
+    Auto,
+    /// Only log what would have been sent; webhook settings are ignored
+    #[serde(alias = "log")]
+    Stdout,
+}
+
+/// How chatty per-round notifications are
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationMode {
+    /// One embed per bet, win and loss
+    #[default]
+    Verbose,
+    /// No bet/loss embeds; a roll-up of recent rounds every `stats_notification_interval` rounds
+    Summary,
+}
+
+fn default_discord_enabled() -> bool {
+    true
 }
 
 fn default_stats_interval() -> u32 {
     10
 }
 
-pub fn load_config(path: &str) -> Result<BotConfig> {
-    let config_str = read_to_string(path)
-        .with_context(|| format!("Failed to read config file: {}", path))?;
+fn default_discord_max_attempts() -> u8 {
+    3
+}
 
-    let config: BotConfig = serde_json::from_str(&config_str)
-        .context("Failed to parse config JSON")?;
+fn default_min_interval_ms() -> u64 {
+    1000
+}
+
+fn default_show_grid() -> bool {
+    true
+}
+
+fn default_explorer_url() -> String {
+    "https://solscan.io".to_string()
+}
+
+/// Transaction submission settings (all optional)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TransactionConfig {
+    #[serde(flatten)]
+    pub priority_fee: PriorityFeeConfig,
+    #[serde(default)]
+    pub commitment: Commitment,           // RPC reads and confirmation level (default: confirmed)
+    #[serde(default)]
+    pub skip_preflight: bool,             // Submit without simulating first (default: false)
+    #[serde(default = "default_confirm_timeout_secs", alias = "tx_confirmation_timeout_secs")]
+    pub confirm_timeout_secs: u64,        // Give up polling a signature after this long (default: 30)
+    #[serde(default = "default_max_retries", alias = "max_tx_retries")]
+    pub max_retries: u8,                  // Send attempts per transaction, each with a fresh blockhash (default: 3)
+    #[serde(default)]
+    pub confirm_via_websocket: bool,      // Wait for signatureSubscribe before falling back to polling (default: false)
+    #[serde(default = "default_websocket_confirm_timeout_secs")]
+    pub websocket_confirm_timeout_secs: u64, // How long to wait on the WebSocket before polling (default: 10)
+    #[serde(default)]
+    pub min_remaining_slots: Option<u64>, // Skip a round with fewer slots left than this (default: derived from retries)
+    #[serde(default)]
+    pub bet_delay_jitter_ms: Option<u64>, // Wait a random 0..=N ms before each new bet (default: bet right away)
+}
+
+impl Default for TransactionConfig {
+    fn default() -> Self {
+        Self {
+            priority_fee: PriorityFeeConfig::default(),
+            commitment: Commitment::default(),
+            skip_preflight: false,
+            confirm_timeout_secs: default_confirm_timeout_secs(),
+            max_retries: default_max_retries(),
+            confirm_via_websocket: false,
+            websocket_confirm_timeout_secs: default_websocket_confirm_timeout_secs(),
+            min_remaining_slots: None,
+            bet_delay_jitter_ms: None,
+        }
+    }
+}
+
+fn default_confirm_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_retries() -> u8 {
+    3
+}
+
+fn default_websocket_confirm_timeout_secs() -> u64 {
+    10
+}
+
+/// Commitment level for RPC reads and transaction confirmation
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Commitment {
+    Processed,
+    #[default]
+    Confirmed,
+    Finalized,
+}
+
+impl Commitment {
+    pub fn config(self) -> CommitmentConfig {
+        match self {
+            Commitment::Processed => CommitmentConfig::processed(),
+            Commitment::Confirmed => CommitmentConfig::confirmed(),
+            Commitment::Finalized => CommitmentConfig::finalized(),
+        }
+    }
+}
+
+/// Compute budget settings attached to every transaction
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PriorityFeeConfig {
+    #[serde(default)]
+    pub priority_fee_microlamports: Option<u64>, // Compute unit price (micro-lamports per CU); fallback in dynamic mode
+    #[serde(default)]
+    pub compute_unit_limit: Option<u32>,         // Compute unit limit per transaction
+    #[serde(default)]
+    pub priority_fee_mode: PriorityFeeMode,      // "static" (default) or "dynamic"
+    #[serde(default = "default_priority_fee_percentile")]
+    pub priority_fee_percentile: u8,             // Dynamic mode: percentile of recent fees (default: 75)
+    #[serde(default)]
+    pub min_priority_fee_microlamports: Option<u64>, // Lower bound on the compute unit price
+    #[serde(default)]
+    pub max_priority_fee_microlamports: Option<u64>, // Upper bound on the compute unit price
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            priority_fee_microlamports: None,
+            compute_unit_limit: None,
+            priority_fee_mode: PriorityFeeMode::default(),
+            priority_fee_percentile: default_priority_fee_percentile(),
+            min_priority_fee_microlamports: None,
+            max_priority_fee_microlamports: None,
+        }
+    }
+}
+
+impl PriorityFeeConfig {
+    /// Priority fee in lamports for a given compute unit price, if the CU limit is known
+    pub fn fee_lamports(&self, price_microlamports: u64) -> Option<u64> {
+        self.compute_unit_limit
+            .map(|units| (price_microlamports as u128 * units as u128 / 1_000_000) as u64)
+    }
+}
+
+/// How the compute unit price is chosen
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityFeeMode {
+    /// Use `priority_fee_microlamports` as-is
+    #[default]
+    Static,
+    /// Estimate from getRecentPrioritizationFees
+    Dynamic,
+}
+
+fn default_priority_fee_percentile() -> u8 {
+    75
+}
+
+/// Read just `log_format` so the logger can be set up before the full config is loaded and logged
+/// Falls back to text on any problem; load_config reports real errors afterwards
+pub fn read_log_format(path: &str) -> LogFormat {
+    read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        .and_then(|v| v.get("log_format").cloned())
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
 
-    // Validate config
-    if config.martingale.blocks_per_bet == 0 || config.martingale.blocks_per_bet > 25 {
+/// Validate the martingale section and resolve the `fixed_blocks` shorthand
+/// Shared by `load_config` and the backtest, which only needs this section
+pub fn validate_martingale(martingale: &mut MartingaleConfig) -> Result<()> {
+    if let Some(squares) = martingale.fixed_blocks.take() {
+        if martingale.block_selection != BlockSelection::Random {
+            anyhow::bail!("fixed_blocks cannot be combined with block_selection {:?}", martingale.block_selection);
+        }
+        martingale.block_selection = BlockSelection::Fixed(squares);
+    }
+
+    if martingale.blocks_per_bet == 0 || martingale.blocks_per_bet > 25 {
         anyhow::bail!("blocks_per_bet must be between 1 and 25");
     }
 
-    if config.martingale.warn_consecutive_losses > config.martingale.max_consecutive_losses {
+    if let Some(bad) = martingale.blocks_per_loss_level.iter().find(|&&blocks| blocks == 0 || blocks > 25) {
+        anyhow::bail!("blocks_per_loss_level entries must be between 1 and 25 (got: {})", bad);
+    }
+
+    if martingale.max_consecutive_losses == 0 {
+        anyhow::bail!("max_consecutive_losses must be >= 1");
+    }
+
+    if martingale.warn_consecutive_losses > martingale.max_consecutive_losses {
         anyhow::bail!("warn_consecutive_losses must be <= max_consecutive_losses");
     }
 
-    // Validate multiplier range
-    if config.martingale.multiplier < 1.0 {
-        anyhow::bail!("multiplier must be >= 1.0 (got: {})", config.martingale.multiplier);
+    // Validate multiplier range (1.0 never raises the bet, use strategy "flat" for that)
+    if !(martingale.multiplier > 1.0 && martingale.multiplier < 10.0) {
+        anyhow::bail!("multiplier must be > 1.0 and < 10.0 (got: {})", martingale.multiplier);
+    }
+
+    if martingale.stop_on_max_losses && martingale.strategy != StrategyKind::Flat {
+        log::warn!("⚠️ Warning: stop_on_max_losses only applies to strategy \"flat\" (other strategies always stop or cool down)");
+    }
+
+    if martingale.strategy == StrategyKind::Custom {
+        if martingale.custom_sequence.is_empty() {
+            anyhow::bail!("custom_sequence must list at least one multiplier when strategy is \"custom\"");
+        }
+        if let Some(bad) = martingale.custom_sequence.iter().find(|m| !(m.is_finite() && **m > 0.0)) {
+            anyhow::bail!("custom_sequence multipliers must be > 0 (got: {})", bad);
+        }
+        // The loss that reaches max_consecutive_losses resets, so only max - 1 escalations are ever bet
+        let usable = martingale.max_consecutive_losses.saturating_sub(1) as usize;
+        if martingale.custom_sequence.len() > usable {
+            log::warn!("⚠️ Warning: custom_sequence has {} entries but max_consecutive_losses {} only reaches the first {}",
+                martingale.custom_sequence.len(), martingale.max_consecutive_losses, usable);
+        }
+    } else if !martingale.custom_sequence.is_empty() {
+        log::warn!("⚠️ Warning: custom_sequence is ignored unless strategy is \"custom\"");
+    }
+
+    if martingale.on_max_losses == OnMaxLosses::Cooldown && martingale.cooldown_minutes == 0 {
+        anyhow::bail!("cooldown_minutes must be >= 1 when on_max_losses is \"cooldown\"");
+    }
+
+    if martingale.cooldown_rounds == Some(0) {
+        anyhow::bail!("cooldown_rounds must be >= 1 (leave it out to cool down by cooldown_minutes)");
+    }
+
+    if let Some(guard) = &martingale.win_rate_guard {
+        if guard.window_rounds == 0 {
+            anyhow::bail!("win_rate_guard.window_rounds must be >= 1");
+        }
+        if !(guard.min_win_rate > 0.0 && guard.min_win_rate <= 100.0) {
+            anyhow::bail!("win_rate_guard.min_win_rate must be between 0 and 100 (got: {})", guard.min_win_rate);
+        }
+        if guard.action == WinRateGuardAction::Skip && guard.skip_rounds == 0 {
+            anyhow::bail!("win_rate_guard.skip_rounds must be >= 1");
+        }
+        let expected = martingale.blocks_per_bet as f64 / TOTAL_BLOCKS as f64 * 100.0;
+        if guard.min_win_rate >= expected {
+            log::warn!("⚠️ Warning: win_rate_guard.min_win_rate {}% is at or above the expected win rate ({:.1}% with {} blocks), the guard will trip most of the time",
+                guard.min_win_rate, expected, martingale.blocks_per_bet);
+        }
+    }
+
+    if let Some(ratio) = martingale.min_ev_ratio {
+        if ratio <= 0.0 {
+            anyhow::bail!("min_ev_ratio must be > 0 (got: {})", ratio);
+        }
+    }
+
+    if let Some(threshold) = martingale.min_ev_threshold {
+        if !threshold.is_finite() {
+            anyhow::bail!("min_ev_threshold must be a finite number of SOL (got: {})", threshold);
+        }
+    }
+
+    if let Some(price) = martingale.ore_price_sol {
+        if !(price.is_finite() && price >= 0.0) {
+            anyhow::bail!("ore_price_sol must be a finite price >= 0 (got: {})", price);
+        }
+    }
+
+    if martingale.win_streak_cap < 1 {
+        anyhow::bail!("win_streak_cap must be >= 1");
     }
-    
-    if config.martingale.multiplier > 10.0 {
-        log::warn!("⚠️ Warning: multiplier {} is very high, bet amounts will grow rapidly!", config.martingale.multiplier);
+
+    // SOL amounts convert to lamports exactly or not at all
+    validate_sol("base_bet_amount", martingale.base_bet_amount)?;
+    if let Some(cap) = martingale.max_bet_per_block_sol {
+        validate_sol("max_bet_per_block_sol", cap)?;
+    }
+    if let Some(floor) = martingale.min_bet_per_block_sol {
+        validate_sol("min_bet_per_block_sol", floor)?;
     }
 
-    // Validate minimum bet (1000 lamports = 0.000001 SOL)
-    const MIN_BET_LAMPORTS: u64 = 1000;
-    let base_bet_lamports = config.martingale.base_bet_lamports();
-    if base_bet_lamports < MIN_BET_LAMPORTS {
+    // Validate minimum bet
+    if let Some(floor) = martingale.min_bet_per_block_sol {
+        if floor < MIN_BET_LAMPORTS as f64 / 1e9 {
+            anyhow::bail!("min_bet_per_block_sol must be >= {:.9} SOL (got: {})", MIN_BET_LAMPORTS as f64 / 1e9, floor);
+        }
+    }
+    let min_bet_lamports = martingale.min_bet_per_block_lamports();
+    let base_bet_lamports = martingale.base_bet_lamports();
+    if base_bet_lamports < min_bet_lamports {
         anyhow::bail!(
             "base_bet_amount too small: {:.9} SOL (minimum: {:.9} SOL)",
-            config.martingale.base_bet_amount,
-            MIN_BET_LAMPORTS as f64 / 1e9
+            martingale.base_bet_amount,
+            min_bet_lamports as f64 / 1e9
         );
     }
 
+    if martingale.base_bet_mode == BaseBetMode::Percent
+        && !(martingale.base_bet_percent > 0.0 && martingale.base_bet_percent <= 100.0)
+    {
+        anyhow::bail!("base_bet_percent must be > 0 and <= 100 in percent mode (got: {})",
+            martingale.base_bet_percent);
+    }
+
+    if let Some(cap) = martingale.max_bet_per_block_lamports() {
+        if cap < min_bet_lamports {
+            anyhow::bail!("max_bet_per_block_sol ({:.9}) must be >= the minimum bet ({:.9} SOL)",
+                cap as f64 / 1e9, min_bet_lamports as f64 / 1e9);
+        }
+    }
+
+    if let Some(cap) = martingale.max_bet_per_block_sol {
+        if cap < martingale.base_bet_amount {
+            anyhow::bail!("max_bet_per_block_sol ({}) must be >= base_bet_amount ({})",
+                cap, martingale.base_bet_amount);
+        }
+    }
+
+    match &martingale.block_selection {
+        BlockSelection::Fixed(squares) => {
+            validate_squares("fixed", squares)?;
+            if squares.len() != martingale.blocks_per_bet as usize {
+                anyhow::bail!("block_selection.fixed lists {} squares but blocks_per_bet is {}",
+                    squares.len(), martingale.blocks_per_bet);
+            }
+            // Shorter loss levels bet the first squares of the list
+            if martingale.max_blocks_per_bet() > martingale.blocks_per_bet {
+                anyhow::bail!("blocks_per_loss_level can't exceed the {} fixed squares", squares.len());
+            }
+        }
+        BlockSelection::Exclude(squares) => {
+            validate_squares("exclude", squares)?;
+            let remaining = TOTAL_BLOCKS - squares.len();
+            if remaining < martingale.max_blocks_per_bet() as usize {
+                anyhow::bail!("block_selection.exclude leaves {} squares, fewer than blocks_per_bet ({})",
+                    remaining, martingale.max_blocks_per_bet());
+            }
+        }
+        BlockSelection::CornersEdges => {
+            let perimeter = perimeter_squares().len();
+            if perimeter < martingale.max_blocks_per_bet() as usize {
+                anyhow::bail!("block_selection corners_edges has {} squares, fewer than blocks_per_bet ({})",
+                    perimeter, martingale.max_blocks_per_bet());
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+pub fn load_config(path: &str) -> Result<BotConfig> {
+    let config_str = read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path))?;
+
+    let mut config: BotConfig = serde_json::from_str(&config_str)
+        .context("Failed to parse config JSON")?;
+
+    validate_martingale(&mut config.martingale)?;
+
+    let monitoring = &config.monitoring;
+    validate_sol("min_balance_sol", monitoring.min_balance_sol)?;
+    validate_sol("auto_claim_sol_threshold", monitoring.auto_claim_sol_threshold)?;
+    for (name, value) in [
+        ("daily_max_loss_sol", monitoring.daily_max_loss_sol),
+        ("daily_profit_target_sol", monitoring.daily_profit_target_sol),
+        ("max_session_loss_sol", monitoring.max_session_loss_sol),
+        ("sweep_above_sol", monitoring.sweep_above_sol),
+    ] {
+        if let Some(value) = value {
+            validate_sol(name, value)?;
+        }
+    }
+    if let Some(threshold) = monitoring.auto_claim_ore_threshold {
+        parse_decimal_units(&threshold.to_string(), 11)
+            .with_context(|| format!("auto_claim_ore_threshold must be an ORE amount >= 0 with at most 11 decimals (got: {})", threshold))?;
+    }
+
+    let rpc_url = reqwest::Url::parse(&config.rpc_url)
+        .with_context(|| format!("rpc_url is not a valid URL (got: {})", config.rpc_url))?;
+    if !matches!(rpc_url.scheme(), "http" | "https") || rpc_url.host_str().is_none() {
+        anyhow::bail!("rpc_url must be an http:// or https:// URL (got: {})", config.rpc_url);
+    }
+
+    if let Some(ws_url) = &config.ws_url {
+        if !(ws_url.starts_with("ws://") || ws_url.starts_with("wss://")) {
+            anyhow::bail!("ws_url must start with ws:// or wss:// (got: {})", ws_url);
+        }
+    }
+
+    if let Some(limit) = config.monitoring.daily_max_loss_sol {
+        if limit <= 0.0 {
+            anyhow::bail!("daily_max_loss_sol must be > 0 (got: {})", limit);
+        }
+    }
+
+    if let Some(target) = config.monitoring.daily_profit_target_sol {
+        if target <= 0.0 {
+            anyhow::bail!("daily_profit_target_sol must be > 0 (got: {})", target);
+        }
+    }
+
+    if let Some(threshold) = config.monitoring.auto_claim_ore_threshold {
+        if threshold <= 0.0 {
+            anyhow::bail!("auto_claim_ore_threshold must be > 0 (got: {})", threshold);
+        }
+    }
+
+    if config.monitoring.ws_stale_alert_minutes == 0 {
+        anyhow::bail!("ws_stale_alert_minutes must be >= 1");
+    }
+
+    if config.monitoring.rolling_window_rounds == 0 {
+        anyhow::bail!("rolling_window_rounds must be >= 1");
+    }
+
+    if let Some(guard) = &config.martingale.win_rate_guard {
+        if guard.window_rounds > config.monitoring.rolling_window_rounds {
+            anyhow::bail!("win_rate_guard.window_rounds ({}) can't exceed monitoring.rolling_window_rounds ({}), which bounds the round history",
+                guard.window_rounds, config.monitoring.rolling_window_rounds);
+        }
+    }
+
+    if let Some(hour) = config.monitoring.daily_summary_hour_utc {
+        if hour > 23 {
+            anyhow::bail!("daily_summary_hour_utc must be 0-23 (got: {})", hour);
+        }
+    }
+
+    if let Some(limit) = config.monitoring.max_session_loss_sol {
+        if limit <= 0.0 {
+            anyhow::bail!("max_session_loss_sol must be > 0 (got: {})", limit);
+        }
+    }
+
+    match (&config.monitoring.sweep_to_address, config.monitoring.sweep_above_sol) {
+        (Some(address), Some(_)) => {
+            address.parse::<Pubkey>()
+                .with_context(|| format!("sweep_to_address is not a valid Solana address: {}", address))?;
+        }
+        (None, None) => {}
+        _ => anyhow::bail!("sweep_to_address and sweep_above_sol must be set together"),
+    }
+
+    if config.notifier == NotifierKind::Auto && config.discord.enabled && !config.discord.has_webhook() {
+        anyhow::bail!("discord.enabled is true but no webhook URL is set (webhook_url, stats_webhook_url or warn_webhook_url)");
+    }
+
+    if let Some(telegram) = config.telegram.as_ref().filter(|_| config.notifier == NotifierKind::Auto) {
+        if telegram.bot_token.trim().is_empty() || telegram.chat_id.trim().is_empty() {
+            anyhow::bail!("telegram.bot_token and telegram.chat_id are both required when the telegram section is present");
+        }
+    }
+
+    Schedule::parse(&config.schedule.active_hours)?;
+
+    if let Some(status) = &config.status {
+        let addr = status.socket_addr()?;
+        if status.auth_token.trim().len() < MIN_STATUS_TOKEN_LEN {
+            anyhow::bail!("status.auth_token must be at least {} characters: the endpoint can pause betting", MIN_STATUS_TOKEN_LEN);
+        }
+        if !addr.ip().is_loopback() {
+            log::warn!("⚠️ Status endpoint listens on {}, reachable from outside this machine (plain HTTP)", addr);
+        }
+    }
+
+    if let Some(role) = &config.discord.warn_mention_role_id {
+        if !matches!(Mention::parse(role), Some(Mention::Role(_))) {
+            anyhow::bail!("discord.warn_mention_role_id must be a numeric role id (got: {})", role);
+        }
+    }
+
+    if let Some(mention) = &config.discord.critical_mention {
+        if Mention::parse(mention).is_none() {
+            anyhow::bail!("discord.critical_mention must be \"here\", \"everyone\" or a numeric role id (got: {})", mention);
+        }
+    }
+
+    if !config.wallets.is_empty() {
+        validate_wallets(&mut config)?;
+    }
+
+    match (&config.encrypted_key_path, config.private_key.trim().is_empty()) {
+        (None, true) if config.wallets.is_empty() => anyhow::bail!("Set private_key or encrypted_key_path"),
+        (Some(path), _) if path.trim().is_empty() => anyhow::bail!("encrypted_key_path must not be empty"),
+        (Some(_), false) => log::warn!("⚠️ Warning: private_key is ignored because encrypted_key_path is set"),
+        _ => {}
+    }
+
+    if config.derivation_account_index.is_some() && config.key_source != KeySource::Mnemonic {
+        log::warn!("⚠️ Warning: derivation_account_index is ignored unless key_source is \"mnemonic\"");
+    }
+
+    if config.discord.max_attempts == 0 {
+        anyhow::bail!("discord.max_attempts must be >= 1");
+    }
+
+    if config.transaction.confirm_timeout_secs == 0 {
+        anyhow::bail!("confirm_timeout_secs must be >= 1");
+    }
+
+    if config.transaction.max_retries == 0 {
+        anyhow::bail!("max_retries must be >= 1");
+    }
+
+    if config.transaction.confirm_via_websocket && config.transaction.websocket_confirm_timeout_secs == 0 {
+        anyhow::bail!("websocket_confirm_timeout_secs must be >= 1 when confirm_via_websocket is enabled");
+    }
+
+    if let Some(jitter) = config.transaction.bet_delay_jitter_ms {
+        if jitter > 30_000 {
+            log::warn!("⚠️ Warning: bet_delay_jitter_ms ({}) can delay a bet past half a round; rounds may be skipped for lack of slots", jitter);
+        }
+    }
+
+    if config.transaction.priority_fee.priority_fee_percentile > 100 {
+        anyhow::bail!("priority_fee_percentile must be between 0 and 100");
+    }
+
+    if let (Some(min), Some(max)) = (
+        config.transaction.priority_fee.min_priority_fee_microlamports,
+        config.transaction.priority_fee.max_priority_fee_microlamports,
+    ) {
+        if min > max {
+            anyhow::bail!("min_priority_fee_microlamports ({}) must not exceed max_priority_fee_microlamports ({})", min, max);
+        }
+    }
+
     log::info!("Loaded config from: {}", path);
     log::info!("  RPC URL: {}", config.rpc_url);
-    log::info!("  Base bet: {} SOL", config.martingale.base_bet_amount);
-    log::info!("  Multiplier: {}x", config.martingale.multiplier);
+    log::info!("  WebSocket URL: {}", config.ws_url());
+    log::info!("  Commitment: {:?}", config.transaction.commitment);
+    log::info!("  State file: {}", config.effective_state_file().display());
+    if let Some(path) = &config.encrypted_key_path {
+        log::info!("  Encrypted key file: {}", path);
+    }
+    if !config.wallets.is_empty() {
+        let names: Vec<&str> = config.wallets.iter().map(|wallet| wallet.name.as_str()).collect();
+        log::info!("  Wallets: {}", names.join(", "));
+    }
+    if let Some(ledger) = config.effective_ledger_file() {
+        log::info!("  Ledger file: {}", ledger.display());
+    }
+    if let Some(history) = config.effective_history_db() {
+        log::info!("  History database: {}", history.display());
+    }
+    if let Some(pause_file) = &config.pause_file {
+        log::info!("  Pause file: {}", pause_file);
+    }
+    if !config.schedule.active_hours.is_empty() {
+        log::info!("  Active hours (UTC): {}", config.schedule.active_hours.join(", "));
+    }
+    match config.transaction.priority_fee.priority_fee_mode {
+        PriorityFeeMode::Static => {
+            if let Some(fee) = config.transaction.priority_fee.priority_fee_microlamports {
+                log::info!("  Priority fee: {} micro-lamports/CU", fee);
+            }
+        }
+        PriorityFeeMode::Dynamic => {
+            log::info!("  Priority fee: dynamic (p{}, min: {}, max: {})",
+                config.transaction.priority_fee.priority_fee_percentile,
+                config.transaction.priority_fee.min_priority_fee_microlamports.map_or("none".to_string(), |m| m.to_string()),
+                config.transaction.priority_fee.max_priority_fee_microlamports.map_or("none".to_string(), |m| m.to_string()));
+        }
+    }
+    if let Some(jitter) = config.transaction.bet_delay_jitter_ms {
+        log::info!("  Bet delay jitter: 0-{} ms", jitter);
+    }
+    if config.dry_run {
+        log::warn!("  🧪 DRY RUN mode: transactions will NOT be submitted");
+    }
+    match config.martingale.base_bet_mode {
+        BaseBetMode::Fixed => log::info!("  Base bet: {} SOL", config.martingale.base_bet_amount),
+        BaseBetMode::Percent => log::info!("  Base bet: {}% of balance (fallback: {} SOL)",
+            config.martingale.base_bet_percent,
+            config.martingale.base_bet_amount),
+    }
+    log::info!("  Strategy: {:?}", config.martingale.strategy);
+    if config.martingale.strategy == StrategyKind::Custom {
+        log::info!("  Custom sequence: {:?}", config.martingale.custom_sequence);
+    }
+    if config.martingale.strategy == StrategyKind::Martingale {
+        // The bet before the loss that hits max_consecutive_losses is the largest of a streak
+        let top = config.martingale.multiplier.powi(config.martingale.max_consecutive_losses as i32 - 1);
+        log::info!("  Multiplier: {}x (up to {:.1}x the base bet over {} losses, before any cap)",
+            config.martingale.multiplier, top, config.martingale.max_consecutive_losses);
+    } else {
+        log::info!("  Multiplier: {}x", config.martingale.multiplier);
+    }
+    if let Some(cap) = config.martingale.max_bet_per_block_sol {
+        log::info!("  Max bet per block: {} SOL", cap);
+    }
+    if let Some(floor) = config.martingale.min_bet_per_block_sol {
+        log::info!("  Min bet per block: {} SOL", floor);
+    }
     log::info!("  Max consecutive losses: {}", config.martingale.max_consecutive_losses);
+    if config.martingale.on_max_losses == OnMaxLosses::Cooldown {
+        match config.martingale.cooldown_rounds {
+            Some(rounds) => log::info!("  On max losses: cool down for {} rounds", rounds),
+            None => log::info!("  On max losses: cool down for {} minutes", config.martingale.cooldown_minutes),
+        }
+    }
     log::info!("  Blocks per bet: {}", config.martingale.blocks_per_bet);
+    if !config.martingale.blocks_per_loss_level.is_empty() {
+        log::info!("  Blocks per loss level: {:?}", config.martingale.blocks_per_loss_level);
+    }
+    log::info!("  Block selection: {:?}", config.martingale.block_selection);
+    if let (Some(address), Some(above)) = (&config.monitoring.sweep_to_address, config.monitoring.sweep_above_sol) {
+        log::info!("  Sweep: balance above bankroll + {} SOL to {}", above, address);
+    }
+    if let Some(guard) = &config.martingale.win_rate_guard {
+        match guard.action {
+            WinRateGuardAction::BaseBet => log::info!("  Win-rate guard: back to base bet below {}% over {} rounds",
+                guard.min_win_rate, guard.window_rounds),
+            WinRateGuardAction::Skip => log::info!("  Win-rate guard: skip {} rounds below {}% over {} rounds",
+                guard.skip_rounds, guard.min_win_rate, guard.window_rounds),
+        }
+    }
 
     Ok(config)
 }
+
+/// What a runtime reload changed
+pub struct ConfigReload {
+    pub changed: Vec<String>,   // "martingale.base_bet_amount: 0.001 → 0.002"
+    pub ignored: Vec<String>,   // Settings that differ from the running config but need a restart
+}
+
+/// Re-read and validate `path`, applying its `martingale` and `monitoring` sections to `config`
+///
+/// Everything else needs a restart, as do `martingale.strategy` (the persisted state can't switch
+/// type) and `monitoring.daily_summary_hour_utc` (scheduled once at startup). Those are reported by
+/// name only, since other sections hold secrets such as the private key.
+pub fn reload_config(path: &str, config: &mut BotConfig) -> Result<ConfigReload> {
+    let mut new = load_config(path)?;
+    if let Some(name) = &config.wallet_name {
+        // A wallet re-applies its own section on top of the new file
+        let wallet = new.wallets.iter()
+            .find(|wallet| &wallet.name == name)
+            .with_context(|| format!("wallet {} is no longer in the config (removing a wallet needs a restart)", name))?;
+        new = new.for_wallet(wallet);
+    }
+    let mut ignored = Vec::new();
+
+    if new.martingale.strategy != config.martingale.strategy {
+        ignored.push("martingale.strategy".to_string());
+        new.martingale.strategy = config.martingale.strategy;
+    }
+    if new.monitoring.daily_summary_hour_utc != config.monitoring.daily_summary_hour_utc {
+        ignored.push("monitoring.daily_summary_hour_utc".to_string());
+        new.monitoring.daily_summary_hour_utc = config.monitoring.daily_summary_hour_utc;
+    }
+
+    let mut changed = diff_section("martingale", &config.martingale, &new.martingale)?;
+    changed.extend(diff_section("monitoring", &config.monitoring, &new.monitoring)?);
+
+    let old_value = serde_json::to_value(&*config)?;
+    let new_value = serde_json::to_value(&new)?;
+    for (key, value) in new_value.as_object().into_iter().flatten() {
+        // A wallet's own entry was applied above; other wallets' entries are theirs to report
+        let handled = key == "martingale" || key == "monitoring" || (key == "wallets" && config.wallet_name.is_some());
+        if !handled && old_value.get(key) != Some(value) {
+            ignored.push(key.clone());
+        }
+    }
+
+    config.martingale = new.martingale;
+    config.monitoring = new.monitoring;
+    Ok(ConfigReload { changed, ignored })
+}
+
+/// Check the `wallets` list: unique file-safe names, a key each, valid martingale overrides
+fn validate_wallets(config: &mut BotConfig) -> Result<()> {
+    if config.encrypted_key_path.is_some() {
+        anyhow::bail!("encrypted_key_path can't be combined with wallets; give each wallet its own private_key");
+    }
+
+    for (i, wallet) in config.wallets.iter_mut().enumerate() {
+        if wallet.name.is_empty() || !wallet.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            anyhow::bail!("wallets[{}].name must be non-empty and use only letters, digits, '-' and '_' (got: {:?})", i, wallet.name);
+        }
+        if wallet.private_key.trim().is_empty() {
+            anyhow::bail!("wallet {} needs a private_key", wallet.name);
+        }
+        if let Some(martingale) = &mut wallet.martingale {
+            validate_martingale(martingale).with_context(|| format!("wallet {}", wallet.name))?;
+        }
+    }
+
+    let mut names: Vec<&str> = config.wallets.iter().map(|wallet| wallet.name.as_str()).collect();
+    names.sort_unstable();
+    if let Some(pair) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+        anyhow::bail!("wallet name {} is used twice", pair[0]);
+    }
+
+    if config.wallets.len() > 1 && config.status.is_some() {
+        log::warn!("⚠️ Warning: the status endpoint only reports the first wallet ({})", config.wallets[0].name);
+    }
+    Ok(())
+}
+
+/// "section.field: old → new" for every top-level field of a section that differs
+fn diff_section<T: Serialize>(section: &str, old: &T, new: &T) -> Result<Vec<String>> {
+    let old = serde_json::to_value(old)?;
+    let new = serde_json::to_value(new)?;
+
+    Ok(new.as_object().into_iter().flatten()
+        .filter_map(|(key, value)| {
+            let previous = old.get(key).unwrap_or(&serde_json::Value::Null);
+            (previous != value).then(|| format!("{}.{}: {} → {}", section, key, previous, value))
+        })
+        .collect())
+}
+
+/// Check a configured square list: indices in 0..25, no duplicates
+fn validate_squares(mode: &str, squares: &[u8]) -> Result<()> {
+    for (i, &square) in squares.iter().enumerate() {
+        if square as usize >= TOTAL_BLOCKS {
+            anyhow::bail!("block_selection.{}: square {} is out of range (0-{})", mode, square, TOTAL_BLOCKS - 1);
+        }
+        if squares[..i].contains(&square) {
+            anyhow::bail!("block_selection.{}: square {} is listed twice", mode, square);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn martingale(multiplier: f64) -> MartingaleConfig {
+        serde_json::from_value(serde_json::json!({
+            "base_bet_amount": 0.001,
+            "max_consecutive_losses": 8,
+            "warn_consecutive_losses": 5,
+            "blocks_per_bet": 1,
+        }))
+        .map(|config: MartingaleConfig| MartingaleConfig { multiplier, ..config })
+        .unwrap()
+    }
+
+    #[test]
+    fn multiplier_must_be_between_one_and_ten() {
+        for ok in [1.01, 1.5, 2.0, 9.99] {
+            assert!(validate_martingale(&mut martingale(ok)).is_ok(), "{} should load", ok);
+        }
+        for bad in [0.5, 1.0, 10.0, 50.0, f64::NAN, f64::INFINITY] {
+            assert!(validate_martingale(&mut martingale(bad)).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn sol_amounts_convert_to_exact_lamports() {
+        let table: [(f64, u64); 12] = [
+            (0.1, 100_000_000),
+            (0.000000001, 1),
+            (1.23456789, 1_234_567_890),
+            (0.3, 300_000_000),
+            (0.001, 1_000_000),
+            (0.07, 70_000_000),
+            (0.29, 290_000_000),
+            (1.0, 1_000_000_000),
+            (0.0, 0),
+            (12.5, 12_500_000_000),
+            (0.000001, 1000),
+            (18_446_744_073.0, 18_446_744_073_000_000_000),
+        ];
+        for (sol, lamports) in table {
+            assert_eq!(sol_to_lamports(sol), lamports, "{} SOL", sol);
+            assert_eq!(validate_sol("amount", sol).unwrap(), lamports, "{} SOL", sol);
+        }
+    }
+
+    #[test]
+    fn invalid_sol_amounts_are_rejected() {
+        for bad in [-1.0, -0.000000001, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 0.0000000001, 1.0000000001, 1e20] {
+            assert!(validate_sol("amount", bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn decimal_parser() {
+        assert_eq!(parse_sol("1").unwrap(), 1_000_000_000);
+        assert_eq!(parse_sol("1.").unwrap(), 1_000_000_000);
+        assert_eq!(parse_sol(".5").unwrap(), 500_000_000);
+        assert_eq!(parse_sol("0.123456789").unwrap(), 123_456_789);
+        assert_eq!(parse_sol("18446744073.709551615").unwrap(), u64::MAX);
+        for bad in ["", ".", "-1", "+1", "1e3", "0.1234567891", "1.2.3", "abc", "18446744073.709551616"] {
+            assert!(parse_sol(bad).is_err(), "{:?} should be rejected", bad);
+        }
+        assert_eq!(parse_decimal_units("0.00000000001", 11).unwrap(), 1);
+        assert_eq!(ore_to_units(2.5), 250_000_000_000);
+    }
+
+    #[test]
+    fn one_and_a_half_progression_in_lamports() {
+        // Each bet scales the previous (already rounded) one, as the strategy does
+        let mut bet = 1_000_000;
+        let mut progression = Vec::new();
+        for _ in 0..8 {
+            bet = scale_lamports(bet, 1.5);
+            progression.push(bet);
+        }
+        assert_eq!(progression, [1_500_000, 2_250_000, 3_375_000, 5_062_500, 7_593_750, 11_390_625, 17_085_938, 25_628_907]);
+    }
+
+    #[test]
+    fn uneven_products_round_half_up() {
+        assert_eq!(scale_lamports(1, 1.5), 2);
+        assert_eq!(scale_lamports(3, 1.5), 5);
+        assert_eq!(scale_lamports(1001, 1.5), 1502);
+        assert_eq!(scale_lamports(2253, 1.5), 3380);
+        assert_eq!(scale_lamports(1000, 1.0005), 1001);
+        assert_eq!(scale_lamports(0, 1.5), 0);
+        assert_eq!(scale_lamports(u64::MAX, 2.0), u64::MAX);
+    }
+
+    #[test]
+    fn percent_of_balance_rounds_down_exactly() {
+        // f64 math gave 569_999 here (0.57 * 1e8 / 100 = 569999.99…)
+        assert_eq!(percent_of_balance(100_000_000, 0.57), 570_000);
+        assert_eq!(percent_of_balance(100_000, 0.29), 1_000); // 290 lamports, lifted to the minimum
+        assert_eq!(percent_of_balance(1_234_567_891, 0.5), 6_172_839); // 6_172_839.455 floored
+        assert_eq!(percent_of_balance(999_999, 33.333), 333_329);
+    }
+
+    #[test]
+    fn percent_of_balance_bounds() {
+        assert_eq!(percent_of_balance(5_000_000_000, 0.0), MIN_BET_LAMPORTS);
+        assert_eq!(percent_of_balance(0, 50.0), MIN_BET_LAMPORTS);
+        assert_eq!(percent_of_balance(5_000_000_000, 100.0), 5_000_000_000);
+        // Above 2^53 an f64 can't hold every lamport; the fixed-point path still can
+        assert_eq!(percent_of_balance(123_456_789_123_456_789, 100.0), 123_456_789_123_456_789);
+        assert_eq!(percent_of_balance(u64::MAX, 100.0), u64::MAX);
+        assert_eq!(percent_of_balance(u64::MAX, 50.0), u64::MAX / 2);
+    }
+}
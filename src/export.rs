@@ -0,0 +1,278 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::{Connection, OpenFlags};
+use std::fs::{read_to_string, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use crate::config::BotConfig;
+
+const HEADER: &str = "round_id,timestamp,blocks,bet_per_block_sol,total_bet_sol,outcome,winning_square,sol_earned,net_cycle_profit_sol,signature";
+
+/// One bet joined with its round and outcome, as read from the history database
+pub struct ExportRow {
+    pub round_id: u64,
+    pub placed_at: String,            // RFC 3339, UTC
+    pub blocks: String,               // Space-separated square indices
+    pub bet_per_block: u64,
+    pub total_bet: u64,
+    pub fee: u64,
+    pub won: Option<bool>,            // None while the round hasn't settled
+    pub winning_square: Option<u8>,
+    pub sol_earned: u64,
+    pub signature: Option<String>,
+}
+
+impl ExportRow {
+    fn outcome(&self) -> &'static str {
+        match self.won {
+            Some(true) => "won",
+            Some(false) => "lost",
+            None => "pending",
+        }
+    }
+
+    /// CSV row; `cycle_net` is the running net of the martingale cycle this bet belongs to
+    pub fn to_csv_row(&self, cycle_net: i64) -> String {
+        format!("{},{},{},{:.9},{:.9},{},{},{:.9},{:.9},{}",
+            self.round_id,
+            self.placed_at,
+            self.blocks,
+            self.bet_per_block as f64 / 1e9,
+            self.total_bet as f64 / 1e9,
+            self.outcome(),
+            self.winning_square.map_or(String::new(), |square| square.to_string()),
+            self.sol_earned as f64 / 1e9,
+            cycle_net as f64 / 1e9,
+            self.signature.as_deref().unwrap_or(""))
+    }
+}
+
+/// Running net of the cycle after each bet: stakes and fees since the last win, plus the win's earnings
+/// A win closes the cycle, so the next bet starts from zero again
+pub fn cycle_nets(rows: &[ExportRow]) -> Vec<i64> {
+    let mut net: i64 = 0;
+    rows.iter()
+        .map(|row| {
+            net += row.sol_earned as i64 - row.total_bet as i64 - row.fee as i64;
+            let current = net;
+            if row.won == Some(true) {
+                net = 0;
+            }
+            current
+        })
+        .collect()
+}
+
+/// Whether a bet placed at `placed_at` falls within `since..=until` (whole UTC days)
+fn in_range(placed_at: &str, since: Option<NaiveDate>, until: Option<NaiveDate>) -> bool {
+    let Ok(placed) = DateTime::parse_from_rfc3339(placed_at) else {
+        return true; // Keep rows with an unreadable timestamp rather than dropping them silently
+    };
+    let day = placed.with_timezone(&Utc).date_naive();
+    since.is_none_or(|since| day >= since) && until.is_none_or(|until| day <= until)
+}
+
+/// Every bet in the history database, oldest first
+pub fn load_rows(path: &PathBuf) -> Result<Vec<ExportRow>> {
+    if !path.exists() {
+        anyhow::bail!("History database {} not found (set history.sqlite_path and let the bot run, or pass --db)", path.display());
+    }
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open history database {}", path.display()))?;
+
+    // Created but never migrated: nothing recorded yet
+    let version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT b.round_id, b.placed_at, b.blocks, b.bet_per_block, b.total_bet, b.fee,
+                o.won, r.winning_square, COALESCE(o.sol_earned, 0), b.signature
+         FROM bets b
+         LEFT JOIN rounds r ON r.round_id = b.round_id
+         LEFT JOIN outcomes o ON o.round_id = b.round_id
+         ORDER BY b.id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(ExportRow {
+            round_id: row.get::<_, i64>(0)? as u64,
+            placed_at: row.get(1)?,
+            blocks: row.get(2)?,
+            bet_per_block: row.get::<_, i64>(3)? as u64,
+            total_bet: row.get::<_, i64>(4)? as u64,
+            fee: row.get::<_, i64>(5)? as u64,
+            won: row.get(6)?,
+            winning_square: row.get(7)?,
+            sol_earned: row.get::<_, i64>(8)? as u64,
+            signature: row.get(9)?,
+        })
+    })?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read bets from the history database")
+}
+
+/// History database named by the config, with the same dry-run suffix the bot uses
+fn history_path_from_config(path: &str) -> Result<PathBuf> {
+    let contents = read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path))?;
+    let config: BotConfig = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file: {}", path))?;
+    config.effective_history_db()
+        .with_context(|| format!("No history.sqlite_path in {} (pass --db instead)", path))
+}
+
+fn parse_date(flag: &str, value: Option<&String>) -> Result<NaiveDate> {
+    let value = value.with_context(|| format!("{} needs a date (YYYY-MM-DD)", flag))?;
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid {}: {} (expected YYYY-MM-DD)", flag, value))
+}
+
+/// `export [--format csv] [--out FILE] [--since DATE] [--until DATE] [--db FILE]`
+///
+/// Dumps one row per bet from the history database; no RPC or wallet needed.
+pub fn run(args: &[String], default_config: &str) -> Result<()> {
+    let mut out = None;
+    let mut db = None;
+    let mut since = None;
+    let mut until = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = iter.next().context("--format needs a value")?;
+                if value != "csv" {
+                    anyhow::bail!("Unsupported --format: {} (only csv)", value);
+                }
+            }
+            "--out" => out = Some(iter.next().context("--out needs a file path")?.clone()),
+            "--db" => db = Some(PathBuf::from(iter.next().context("--db needs a file path")?)),
+            "--since" => since = Some(parse_date("--since", iter.next())?),
+            "--until" => until = Some(parse_date("--until", iter.next())?),
+            _ => anyhow::bail!("Usage: export [--format csv] [--out FILE] [--since YYYY-MM-DD] [--until YYYY-MM-DD] [--db FILE]"),
+        }
+    }
+
+    if let (Some(since), Some(until)) = (since, until) {
+        if since > until {
+            anyhow::bail!("--since ({}) is after --until ({})", since, until);
+        }
+    }
+
+    let db = match db {
+        Some(db) => db,
+        None => history_path_from_config(default_config)?,
+    };
+    let rows = load_rows(&db)?;
+
+    // Cycle nets need every earlier bet, so filter only after computing them
+    let nets = cycle_nets(&rows);
+    let mut csv = String::from(HEADER);
+    csv.push('\n');
+    let mut exported = 0;
+    for (row, net) in rows.iter().zip(nets) {
+        if in_range(&row.placed_at, since, until) {
+            csv.push_str(&row.to_csv_row(net));
+            csv.push('\n');
+            exported += 1;
+        }
+    }
+
+    match &out {
+        Some(path) => {
+            File::create(path)
+                .and_then(|mut file| file.write_all(csv.as_bytes()))
+                .with_context(|| format!("Failed to write {}", path))?;
+            println!("📤 Exported {} bet(s) from {} to {}", exported, db.display(), path);
+        }
+        None => {
+            io::stdout().write_all(csv.as_bytes())?;
+            eprintln!("📤 Exported {} bet(s) from {}", exported, db.display());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::tests::wait_for_rows;
+    use crate::test_util::TempDir;
+    use crate::history::{BetRecord, History, OutcomeRecord, RoundRecord};
+
+    fn bet(round_id: u64, bet_per_block: u64) -> BetRecord {
+        BetRecord {
+            round_id,
+            blocks: vec![1, 2],
+            bet_per_block,
+            fee: 5_000,
+            signature: Some(format!("sig{}", round_id)),
+            consecutive_losses: 0,
+        }
+    }
+
+    fn round(round_id: u64, winning_square: u8) -> RoundRecord {
+        RoundRecord {
+            round_id,
+            start_slot: round_id * 150,
+            end_slot: round_id * 150 + 150,
+            winning_square: Some(winning_square),
+            total_deployed: 0,
+            motherlode: 0,
+            slot_hash: [1; 32],
+        }
+    }
+
+    fn outcome(round_id: u64, won: bool, total_bet: u64, sol_earned: u64) -> OutcomeRecord {
+        OutcomeRecord { round_id, won, sol_earned, ore_earned: 0, total_bet, fees: 5_000 }
+    }
+
+    #[test]
+    fn csv_round_trip_with_cycle_resets() {
+        let dir = TempDir::new("export");
+        let path = dir.0.join("history.db");
+        let history = History::open(&path).unwrap();
+
+        // Lose, lose, win closes the first cycle; then win, then a bet still pending
+        let rounds = [(1, 1_000_000, Some((false, 0))), (2, 2_000_000, Some((false, 0))), (3, 4_000_000, Some((true, 12_000_000))),
+            (4, 1_000_000, Some((true, 3_000_000))), (5, 1_000_000, None)];
+        for (round_id, bet_per_block, result) in rounds {
+            history.record_bet(bet(round_id, bet_per_block));
+            if let Some((won, sol_earned)) = result {
+                history.record_round(round(round_id, if won { 2 } else { 9 }));
+                history.record_outcome(outcome(round_id, won, bet_per_block * 2, sol_earned));
+            }
+        }
+        drop(history);
+        wait_for_rows(&Connection::open(&path).unwrap(), "bets", 5);
+        wait_for_rows(&Connection::open(&path).unwrap(), "outcomes", 4);
+
+        let rows = load_rows(&path).unwrap();
+        assert_eq!(rows.iter().map(|row| row.round_id).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+        // Cycle 1 (in 0.001 SOL): -2.005, -6.01, then +12 - 8.005 = -2.015; the win resets, so round 4 starts from zero
+        let nets = cycle_nets(&rows);
+        assert_eq!(nets, [-2_005_000, -6_010_000, -2_015_000, 995_000, -2_005_000]);
+
+        let csv: Vec<String> = rows.iter().zip(&nets).map(|(row, &net)| row.to_csv_row(net)).collect();
+        let fields = |line: &str| line.split(',').map(str::to_string).collect::<Vec<_>>();
+        assert_eq!(fields(&csv[0])[2..], ["1 2", "0.001000000", "0.002000000", "lost", "9", "0.000000000", "-0.002005000", "sig1"]);
+        assert_eq!(fields(&csv[2])[2..], ["1 2", "0.004000000", "0.008000000", "won", "2", "0.012000000", "-0.002015000", "sig3"]);
+        assert_eq!(fields(&csv[3])[2..], ["1 2", "0.001000000", "0.002000000", "won", "2", "0.003000000", "0.000995000", "sig4"]);
+        assert_eq!(fields(&csv[4])[2..], ["1 2", "0.001000000", "0.002000000", "pending", "", "0.000000000", "-0.002005000", "sig5"]);
+        for line in &csv {
+            assert_eq!(fields(line).len(), HEADER.split(',').count());
+            assert!(DateTime::parse_from_rfc3339(&fields(line)[1]).is_ok());
+        }
+    }
+
+    #[test]
+    fn date_range_is_inclusive() {
+        let day = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok();
+        assert!(in_range("2025-01-31T23:59:59+00:00", day("2025-01-01"), day("2025-01-31")));
+        assert!(!in_range("2025-02-01T00:00:00+00:00", day("2025-01-01"), day("2025-01-31")));
+        assert!(in_range("not a timestamp", day("2025-01-01"), None));
+    }
+}
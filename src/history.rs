@@ -0,0 +1,417 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex, Weak};
+use std::thread::JoinHandle;
+
+/// Schema changes, applied in order; `PRAGMA user_version` counts how many already ran
+/// Only ever append here: a database created by an older build picks up the rest on startup
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS rounds (
+        round_id INTEGER PRIMARY KEY,
+        start_slot INTEGER NOT NULL,
+        end_slot INTEGER NOT NULL,
+        winning_square INTEGER,
+        total_deployed INTEGER NOT NULL,
+        motherlode INTEGER NOT NULL,
+        slot_hash TEXT NOT NULL,
+        recorded_at TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS bets (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        round_id INTEGER NOT NULL,
+        blocks TEXT NOT NULL,
+        bet_per_block INTEGER NOT NULL,
+        total_bet INTEGER NOT NULL,
+        fee INTEGER NOT NULL,
+        signature TEXT,
+        consecutive_losses INTEGER NOT NULL,
+        placed_at TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS bets_round_id ON bets (round_id);
+    CREATE TABLE IF NOT EXISTS outcomes (
+        round_id INTEGER PRIMARY KEY,
+        won INTEGER NOT NULL,
+        sol_earned INTEGER NOT NULL,
+        ore_earned INTEGER NOT NULL,
+        fees INTEGER NOT NULL,
+        net INTEGER NOT NULL,
+        settled_at TEXT NOT NULL
+    );",
+];
+
+/// A bet as sent, written once the Deploy confirms
+pub struct BetRecord {
+    pub round_id: u64,
+    pub blocks: Vec<u8>,
+    pub bet_per_block: u64,
+    pub fee: u64,                    // Transaction fee of the Deploy in lamports
+    pub signature: Option<String>,
+    pub consecutive_losses: u8,      // Streak going into this bet
+}
+
+/// Final state of a round, written once the winning square is known
+pub struct RoundRecord {
+    pub round_id: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub winning_square: Option<u8>,  // None if the RNG never became available
+    pub total_deployed: u64,
+    pub motherlode: u64,
+    pub slot_hash: [u8; 32],
+}
+
+/// What our bet on a round returned
+pub struct OutcomeRecord {
+    pub round_id: u64,
+    pub won: bool,
+    pub sol_earned: u64,
+    pub ore_earned: u64,
+    pub total_bet: u64,
+    pub fees: u64,
+}
+
+impl OutcomeRecord {
+    /// SOL earned minus the stake and the bet's fee, in lamports
+    pub fn net(&self) -> i64 {
+        self.sol_earned as i64 - self.total_bet as i64 - self.fees as i64
+    }
+}
+
+enum Row {
+    Bet(BetRecord),
+    ResumedBet(BetRecord), // Skipped if the round already has a bet row
+    Round(RoundRecord),
+    Outcome(OutcomeRecord),
+}
+
+/// Every writer still open, so a forced exit can flush them too
+static WRITERS: Mutex<Vec<Weak<Writer>>> = Mutex::new(Vec::new());
+
+/// The writer thread and the one sender feeding it, shared by every clone of a History
+struct Writer {
+    sender: Mutex<Option<mpsc::Sender<Row>>>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Writer {
+    /// Stop taking rows and wait for the queued ones to be written
+    fn close(&self) {
+        drop(self.sender.lock().unwrap().take());
+        if let Some(thread) = self.thread.lock().unwrap().take() {
+            if thread.join().is_err() {
+                log::warn!("⚠️ History writer panicked, queued rows may be lost");
+            }
+        }
+    }
+}
+
+/// SQLite history of every round, bet and outcome
+///
+/// Rows go through a channel to a dedicated writer thread that owns the connection,
+/// so a slow disk never blocks the betting loop. Write errors are logged, never fatal.
+#[derive(Clone)]
+pub struct History {
+    writer: Arc<Writer>,
+}
+
+impl History {
+    /// Open (or create) the database, bring its schema up to date and start the writer thread
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut conn = Connection::open(path)
+            .with_context(|| format!("Failed to open history database {}", path.display()))?;
+        migrate(&mut conn)
+            .with_context(|| format!("Failed to migrate history database {}", path.display()))?;
+
+        let (sender, receiver) = mpsc::channel::<Row>();
+        let thread = std::thread::Builder::new()
+            .name("history".to_string())
+            .spawn(move || {
+                for row in receiver {
+                    if let Err(e) = write_row(&conn, &row) {
+                        log::warn!("⚠️ Failed to write history row: {}", e);
+                    }
+                }
+            })
+            .context("Failed to start history writer")?;
+
+        let writer = Arc::new(Writer { sender: Mutex::new(Some(sender)), thread: Mutex::new(Some(thread)) });
+        let mut writers = WRITERS.lock().unwrap();
+        writers.retain(|writer| writer.strong_count() > 0);
+        writers.push(Arc::downgrade(&writer));
+        Ok(Self { writer })
+    }
+
+    /// Write out the queued rows and stop the writer thread; rows recorded afterwards are dropped
+    pub fn close(&self) {
+        self.writer.close();
+    }
+
+    pub fn record_bet(&self, bet: BetRecord) {
+        self.send(Row::Bet(bet));
+    }
+
+    /// A bet found on chain after a restart; only written if the original never was
+    pub fn record_resumed_bet(&self, bet: BetRecord) {
+        self.send(Row::ResumedBet(bet));
+    }
+
+    pub fn record_round(&self, round: RoundRecord) {
+        self.send(Row::Round(round));
+    }
+
+    pub fn record_outcome(&self, outcome: OutcomeRecord) {
+        self.send(Row::Outcome(outcome));
+    }
+
+    fn send(&self, row: Row) {
+        let sent = match &*self.writer.sender.lock().unwrap() {
+            Some(sender) => sender.send(row).is_ok(),
+            None => false,
+        };
+        if !sent {
+            log::warn!("⚠️ History writer stopped, row dropped");
+        }
+    }
+}
+
+/// Close every open history database, waiting for queued rows to reach disk
+/// Called before the process exits, including the forced exit on a second signal
+pub fn close_all() {
+    let writers = std::mem::take(&mut *WRITERS.lock().unwrap());
+    for writer in writers.iter().filter_map(Weak::upgrade) {
+        writer.close();
+    }
+}
+
+/// Run the migrations this database hasn't seen yet, each in its own transaction
+fn migrate(conn: &mut Connection) -> Result<()> {
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        anyhow::bail!("schema version {} is newer than this build ({})", version, MIGRATIONS.len());
+    }
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+        log::debug!("🗄️ History schema migrated to version {}", index + 1);
+    }
+    Ok(())
+}
+
+fn write_row(conn: &Connection, row: &Row) -> rusqlite::Result<()> {
+    let now = Utc::now().to_rfc3339();
+    match row {
+        Row::Bet(bet) => insert_bet(conn, bet, &now)?,
+        Row::ResumedBet(bet) => {
+            let recorded: bool = conn.query_row(
+                "SELECT EXISTS (SELECT 1 FROM bets WHERE round_id = ?1)",
+                params![bet.round_id as i64],
+                |row| row.get(0),
+            )?;
+            if !recorded {
+                insert_bet(conn, bet, &now)?;
+            }
+        }
+        Row::Round(round) => {
+            let slot_hash: String = round.slot_hash.iter().map(|b| format!("{:02x}", b)).collect();
+            conn.execute(
+                "INSERT OR REPLACE INTO rounds (round_id, start_slot, end_slot, winning_square, total_deployed, motherlode, slot_hash, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    round.round_id as i64,
+                    round.start_slot as i64,
+                    round.end_slot as i64,
+                    round.winning_square,
+                    round.total_deployed as i64,
+                    round.motherlode as i64,
+                    slot_hash,
+                    now,
+                ],
+            )?;
+        }
+        Row::Outcome(outcome) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO outcomes (round_id, won, sol_earned, ore_earned, fees, net, settled_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    outcome.round_id as i64,
+                    outcome.won,
+                    outcome.sol_earned as i64,
+                    outcome.ore_earned as i64,
+                    outcome.fees as i64,
+                    outcome.net(),
+                    now,
+                ],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn insert_bet(conn: &Connection, bet: &BetRecord, now: &str) -> rusqlite::Result<()> {
+    let blocks: Vec<String> = bet.blocks.iter().map(|b| b.to_string()).collect();
+    conn.execute(
+        "INSERT INTO bets (round_id, blocks, bet_per_block, total_bet, fee, signature, consecutive_losses, placed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            bet.round_id as i64,
+            blocks.join(" "),
+            bet.bet_per_block as i64,
+            (bet.bet_per_block * bet.blocks.len() as u64) as i64,
+            bet.fee as i64,
+            bet.signature,
+            bet.consecutive_losses,
+            now,
+        ],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use std::time::{Duration, Instant};
+
+    /// Wait for the writer thread to get `count` rows into `table`
+    pub(crate) fn wait_for_rows(conn: &Connection, table: &str, count: i64) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let rows: i64 = conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap();
+            if rows >= count {
+                return;
+            }
+            assert!(Instant::now() < deadline, "timed out waiting for {} rows in {}", count, table);
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn records_bet_round_and_outcome() {
+        let dir = TempDir::new("history");
+        let path = dir.0.join("history.db");
+        let history = History::open(&path).unwrap();
+
+        history.record_bet(BetRecord {
+            round_id: 7,
+            blocks: vec![3, 12],
+            bet_per_block: 1_000_000,
+            fee: 5_000,
+            signature: Some("sig7".to_string()),
+            consecutive_losses: 2,
+        });
+        let mut slot_hash = [0u8; 32];
+        slot_hash[0] = 0xab;
+        history.record_round(RoundRecord {
+            round_id: 7,
+            start_slot: 100,
+            end_slot: 250,
+            winning_square: Some(12),
+            total_deployed: 9_000_000,
+            motherlode: 42,
+            slot_hash,
+        });
+        history.record_outcome(OutcomeRecord {
+            round_id: 7,
+            won: true,
+            sol_earned: 3_500_000,
+            ore_earned: 100,
+            total_bet: 2_000_000,
+            fees: 5_000,
+        });
+        drop(history);
+
+        let conn = Connection::open(&path).unwrap();
+        wait_for_rows(&conn, "outcomes", 1);
+
+        let bet: (i64, String, i64, i64, i64, Option<String>, i64) = conn.query_row(
+            "SELECT round_id, blocks, bet_per_block, total_bet, fee, signature, consecutive_losses FROM bets",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?)),
+        ).unwrap();
+        assert_eq!(bet, (7, "3 12".to_string(), 1_000_000, 2_000_000, 5_000, Some("sig7".to_string()), 2));
+
+        let round: (i64, i64, Option<u8>, i64, i64, String) = conn.query_row(
+            "SELECT start_slot, end_slot, winning_square, total_deployed, motherlode, slot_hash FROM rounds WHERE round_id = 7",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
+        ).unwrap();
+        assert_eq!(round, (100, 250, Some(12), 9_000_000, 42, format!("ab{}", "00".repeat(31))));
+
+        let outcome: (bool, i64, i64, i64, i64) = conn.query_row(
+            "SELECT won, sol_earned, ore_earned, fees, net FROM outcomes WHERE round_id = 7",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        ).unwrap();
+        assert_eq!(outcome, (true, 3_500_000, 100, 5_000, 1_495_000));
+    }
+
+    #[test]
+    fn close_writes_queued_rows_before_returning() {
+        let dir = TempDir::new("history-close");
+        let path = dir.0.join("history.db");
+        let history = History::open(&path).unwrap();
+        let clone = history.clone();
+        for round_id in 0..100 {
+            clone.record_round(RoundRecord {
+                round_id,
+                start_slot: 0,
+                end_slot: 150,
+                winning_square: None,
+                total_deployed: 0,
+                motherlode: 0,
+                slot_hash: [0; 32],
+            });
+        }
+        // Closing one handle stops the writer even while a clone is still alive
+        history.close();
+
+        let conn = Connection::open(&path).unwrap();
+        let rows: i64 = conn.query_row("SELECT COUNT(*) FROM rounds", [], |row| row.get(0)).unwrap();
+        assert_eq!(rows, 100);
+        // Rows after the close are dropped rather than blocking
+        clone.record_outcome(OutcomeRecord { round_id: 0, won: false, sol_earned: 0, ore_earned: 0, total_bet: 0, fees: 0 });
+        clone.close();
+    }
+
+    #[test]
+    fn resumed_bet_fills_in_a_missing_row_only() {
+        let dir = TempDir::new("history-resumed");
+        let path = dir.0.join("history.db");
+        let history = History::open(&path).unwrap();
+        let bet = |round_id, signature: Option<&str>| BetRecord {
+            round_id,
+            blocks: vec![4],
+            bet_per_block: 1_000_000,
+            fee: 0,
+            signature: signature.map(str::to_string),
+            consecutive_losses: 0,
+        };
+        // Round 8 was written before the restart, round 9 never was
+        history.record_bet(bet(8, Some("sig8")));
+        history.record_resumed_bet(bet(8, None));
+        history.record_resumed_bet(bet(9, None));
+        history.close();
+
+        let conn = Connection::open(&path).unwrap();
+        let rows: Vec<(i64, Option<String>)> = conn.prepare("SELECT round_id, signature FROM bets ORDER BY id").unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+            .collect::<rusqlite::Result<_>>().unwrap();
+        assert_eq!(rows, vec![(8, Some("sig8".to_string())), (9, None)]);
+    }
+
+    #[test]
+    fn reopening_keeps_the_schema_version() {
+        let dir = TempDir::new("history-reopen");
+        let path = dir.0.join("history.db");
+        drop(History::open(&path).unwrap());
+        drop(History::open(&path).unwrap());
+
+        let conn = Connection::open(&path).unwrap();
+        let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+    }
+}
@@ -1,29 +1,486 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use bip39::{Language, Mnemonic};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use solana_derivation_path::DerivationPath;
 use solana_sdk::signature::{Keypair, Signer};
-use solana_sdk::signer::keypair::keypair_from_seed;
+use solana_sdk::signer::keypair::keypair_from_seed_and_derivation_path;
 use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use zeroize::Zeroizing;
+use crate::config::{BotConfig, KeySource};
 
-/// Load keypair from Base58-encoded private key string
+/// Prefix selecting an environment variable as the key source (e.g. "env:ORE_BOT_KEY")
+const ENV_PREFIX: &str = "env:";
+
+/// Passphrase for `encrypted_key_path`, read before falling back to an interactive prompt
+const PASSPHRASE_ENV: &str = "ORE_BOT_KEY_PASSPHRASE";
+
+/// Encrypted key file format written by `keygen encrypt`
+const ENCRYPTED_KEY_VERSION: u8 = 1;
+const DEFAULT_ENCRYPTED_KEY_PATH: &str = "key.enc";
+const ARGON2_MEMORY_KIB: u32 = 64 * 1024; // 64 MiB
+const ARGON2_ITERATIONS: u32 = 3;
+const ARGON2_PARALLELISM: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Keypair decrypted from `encrypted_key_path`, so the passphrase is asked for only once
+static DECRYPTED_KEYPAIR: OnceLock<Keypair> = OnceLock::new();
+
+/// Load keypair from Base58-encoded private key string, from a Solana CLI keypair file
+/// if the value looks like a path (e.g. "~/.config/solana/id.json") or names an existing file,
+/// from the file's byte array written inline ("[12,34,...]"), or from an
+/// environment variable when written as "env:VAR_NAME"
 /// Example: "4YFq9y5f5hi77Bq8kDCE6VgqoAqKGSQN87yW9YeGybpNfqKUG4WxnwhboHGUeXjY7g8262mhL1kCCM9yy8uGvdj7"
 pub fn load_keypair(private_key_base58: &str) -> Result<Keypair> {
+    if let Some(var) = private_key_base58.strip_prefix(ENV_PREFIX) {
+        return load_keypair_from_env(var);
+    }
+
+    if private_key_base58.trim_start().starts_with('[') {
+        return load_keypair_from_json(private_key_base58);
+    }
+
+    if looks_like_path(private_key_base58) || Path::new(private_key_base58).is_file() {
+        return load_keypair_from_file(private_key_base58);
+    }
+
+    // Auto mode doesn't derive from seed phrases: the account index lives in the config
+    if private_key_base58.split_whitespace().count() >= 12 {
+        anyhow::bail!("private_key looks like a seed phrase; set \"key_source\": \"mnemonic\" to derive the keypair from it");
+    }
+
+    load_keypair_from_base58(private_key_base58)
+}
+
+/// Load the signing key as configured: `key_source` picks how `private_key` is read
+/// ("env:VAR_NAME" works with every source)
+pub fn load_signer(config: &BotConfig) -> Result<Keypair> {
+    if let Some(path) = &config.encrypted_key_path {
+        return load_encrypted_keypair(path);
+    }
+
+    if config.key_source == KeySource::Auto {
+        return load_keypair(&config.private_key);
+    }
+
+    let value = match config.private_key.strip_prefix(ENV_PREFIX) {
+        Some(var) => {
+            log::info!("Loading keypair from environment variable {}", var.trim());
+            Zeroizing::new(env_value(var)?)
+        }
+        None => config.private_key.clone(),
+    };
+
+    match config.key_source {
+        KeySource::Auto | KeySource::Base58 => load_keypair_from_base58(value.trim()),
+        KeySource::File => load_keypair_from_file(value.trim()),
+        KeySource::Mnemonic => load_keypair_from_mnemonic(&value, config.derivation_account_index.unwrap_or(0)),
+    }
+}
+
+/// `keygen encrypt` output: the 64-byte secret key sealed with ChaCha20-Poly1305
+/// under a key stretched from the passphrase with Argon2id
+#[derive(Serialize, Deserialize)]
+struct EncryptedKeyFile {
+    version: u8,
+    pubkey: String,     // Lets the file be identified without the passphrase
+    memory_kib: u32,    // Argon2id parameters used for this file
+    iterations: u32,
+    parallelism: u32,
+    salt: String,       // Base64
+    nonce: String,      // Base64
+    ciphertext: String, // Base64, includes the Poly1305 tag
+}
+
+/// Decrypt `encrypted_key_path` with the passphrase from ORE_BOT_KEY_PASSPHRASE or the terminal
+/// Later calls (e.g. for claims) reuse the keypair already decrypted
+pub fn load_encrypted_keypair(path: &str) -> Result<Keypair> {
+    if let Some(keypair) = DECRYPTED_KEYPAIR.get() {
+        return Ok(keypair.insecure_clone());
+    }
+
+    let path = expand_home(path);
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            anyhow::bail!("Encrypted key file not found: {}", path.display());
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read encrypted key file: {}", path.display()));
+        }
+    };
+    let file: EncryptedKeyFile = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid encrypted key file: {}", path.display()))?;
+
+    let passphrase = read_passphrase(&format!("🔑 Passphrase for {}: ", path.display()))?;
+    let keypair = decrypt_keypair(&file, &passphrase)
+        .with_context(|| format!("Failed to decrypt {}", path.display()))?;
+
+    log::info!("Decrypted keypair from {}: {}", path.display(), keypair.pubkey());
+    Ok(DECRYPTED_KEYPAIR.get_or_init(|| keypair).insecure_clone())
+}
+
+/// Passphrase from ORE_BOT_KEY_PASSPHRASE if set, otherwise typed at the terminal (not echoed)
+fn read_passphrase(prompt: &str) -> Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        if !passphrase.is_empty() {
+            log::info!("Using the key passphrase from {}", PASSPHRASE_ENV);
+            return Ok(Zeroizing::new(passphrase));
+        }
+    }
+
+    rpassword::prompt_password(prompt)
+        .map(Zeroizing::new)
+        .with_context(|| format!("Failed to read the key passphrase (no terminal? set {})", PASSPHRASE_ENV))
+}
+
+/// Stretch a passphrase into a 32-byte ChaCha20-Poly1305 key
+fn derive_key(passphrase: &str, salt: &[u8], memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Zeroizing<[u8; 32]>> {
+    let params = Params::new(memory_kib, iterations, parallelism, Some(32))
+        .map_err(|e| anyhow::anyhow!("Invalid Argon2 parameters: {}", e))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| anyhow::anyhow!("Failed to derive key from passphrase: {}", e))?;
+    Ok(key)
+}
+
+fn encrypt_keypair(keypair: &Keypair, passphrase: &str) -> Result<EncryptedKeyFile> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut salt);
+    rand::rng().fill_bytes(&mut nonce);
+
+    let key = derive_key(passphrase, &salt, ARGON2_MEMORY_KIB, ARGON2_ITERATIONS, ARGON2_PARALLELISM)?;
+    let secret = Zeroizing::new(keypair.to_bytes());
+    let ciphertext = ChaCha20Poly1305::new(key.as_ref().into())
+        .encrypt(&Nonce::from(nonce), secret.as_ref())
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt the key"))?;
+
+    Ok(EncryptedKeyFile {
+        version: ENCRYPTED_KEY_VERSION,
+        pubkey: keypair.pubkey().to_string(),
+        memory_kib: ARGON2_MEMORY_KIB,
+        iterations: ARGON2_ITERATIONS,
+        parallelism: ARGON2_PARALLELISM,
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn decrypt_keypair(file: &EncryptedKeyFile, passphrase: &str) -> Result<Keypair> {
+    if file.version != ENCRYPTED_KEY_VERSION {
+        anyhow::bail!("Unsupported encrypted key file version {} (expected {})", file.version, ENCRYPTED_KEY_VERSION);
+    }
+    let salt = BASE64.decode(&file.salt).context("Invalid salt")?;
+    let nonce: [u8; NONCE_LEN] = BASE64.decode(&file.nonce).context("Invalid nonce")?
+        .try_into()
+        .map_err(|nonce: Vec<u8>| anyhow::anyhow!("Invalid nonce: expected {} bytes, got {}", NONCE_LEN, nonce.len()))?;
+    let ciphertext = BASE64.decode(&file.ciphertext).context("Invalid ciphertext")?;
+
+    let key = derive_key(passphrase, &salt, file.memory_kib, file.iterations, file.parallelism)?;
+    let secret = ChaCha20Poly1305::new(key.as_ref().into())
+        .decrypt(&Nonce::from(nonce), ciphertext.as_ref())
+        .map(Zeroizing::new)
+        .map_err(|_| anyhow::anyhow!("Wrong passphrase (or the file was modified)"))?;
+
+    keypair_from_bytes(&secret)
+}
+
+/// `keygen encrypt [--out key.enc] [--force]`
+///
+/// Prompts for the key (anything `private_key` accepts, not echoed) and a passphrase typed twice,
+/// then writes the encrypted key file for `encrypted_key_path`
+pub fn run_keygen(args: &[String]) -> Result<()> {
+    const USAGE: &str = "Usage: keygen encrypt [--out key.enc] [--force]";
+    let (command, options) = args.split_first().context(USAGE)?;
+    if command != "encrypt" {
+        anyhow::bail!("Unknown keygen command: {}\n{}", command, USAGE);
+    }
+
+    let mut out = DEFAULT_ENCRYPTED_KEY_PATH.to_string();
+    let mut force = false;
+    let mut iter = options.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--out" => out = iter.next().context("--out needs a path")?.clone(),
+            "--force" => force = true,
+            _ => anyhow::bail!("Unknown option: {}\n{}", arg, USAGE),
+        }
+    }
+
+    let out = expand_home(&out);
+    if out.exists() && !force {
+        anyhow::bail!("{} already exists (use --force to overwrite)", out.display());
+    }
+
+    let key = Zeroizing::new(rpassword::prompt_password("🔑 Private key (Base58, keypair file path or byte array): ")?);
+    let keypair = load_keypair(key.trim())?;
+
+    let passphrase = Zeroizing::new(rpassword::prompt_password("🔒 New passphrase: ")?);
+    if passphrase.is_empty() {
+        anyhow::bail!("The passphrase must not be empty");
+    }
+    if *passphrase != *Zeroizing::new(rpassword::prompt_password("🔒 Repeat passphrase: ")?) {
+        anyhow::bail!("Passphrases don't match");
+    }
+    if passphrase.chars().count() < 12 {
+        println!("⚠️ Warning: passphrases shorter than 12 characters are easy to brute-force if the file leaks");
+    }
+
+    let file = encrypt_keypair(&keypair, &passphrase)?;
+    write_private_file(&out, &serde_json::to_string_pretty(&file)?)?;
+
+    println!("✅ Wrote {} for {}", out.display(), keypair.pubkey());
+    println!("   Set \"encrypted_key_path\": \"{}\" in config.json and remove private_key", out.display());
+    Ok(())
+}
+
+/// Write a file only the owner can read (0600 on Unix)
+fn write_private_file(path: &Path, contents: &str) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(contents.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Load keypair from a Base58-encoded 64-byte secret key
+pub fn load_keypair_from_base58(private_key_base58: &str) -> Result<Keypair> {
     // Decode Base58 string to bytes
-    let keypair_bytes = bs58::decode(private_key_base58)
+    let keypair_bytes = Zeroizing::new(bs58::decode(private_key_base58)
         .into_vec()
-        .context("Failed to decode Base58 private key")?;
+        .context("Failed to decode Base58 private key")?);
 
-    // Solana private key contains 64 bytes (32-byte seed + 32-byte public key)
-    if keypair_bytes.len() != 64 {
-        anyhow::bail!("Invalid private key: expected 64 bytes, got {}", keypair_bytes.len());
+    let keypair = keypair_from_bytes(&keypair_bytes)?;
+
+    log::info!("Loaded keypair: {}", keypair.pubkey());
+    Ok(keypair)
+}
+
+/// Derive a keypair from a BIP39 seed phrase (no passphrase) at m/44'/501'/`account_index`'/0',
+/// the path Phantom and Solflare use and `solana-keygen recover 'prompt://?key=N/0'` takes
+/// The phrase, seed and any error detail are never logged, only the derived public key
+pub fn load_keypair_from_mnemonic(phrase: &str, account_index: u32) -> Result<Keypair> {
+    let normalized = Zeroizing::new(phrase.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase());
+    let words = normalized.split(' ').filter(|word| !word.is_empty()).count();
+    if words != 12 && words != 24 {
+        anyhow::bail!("Invalid mnemonic: expected 12 or 24 words, got {}", words);
     }
 
-    // Extract the first 32 bytes as the seed
-    let seed: [u8; 32] = keypair_bytes[0..32]
-        .try_into()
-        .context("Failed to extract seed from private key")?;
+    // Mnemonic zeroizes itself on drop
+    let mnemonic = Mnemonic::parse_in_normalized(Language::English, &normalized)
+        .map_err(|e| match e {
+            bip39::Error::InvalidChecksum => anyhow::anyhow!("Invalid mnemonic: checksum mismatch (a word is wrong or out of order)"),
+            bip39::Error::UnknownWord(index) => anyhow::anyhow!("Invalid mnemonic: word #{} is not in the BIP39 English word list", index + 1),
+            _ => anyhow::anyhow!("Invalid mnemonic"),
+        })?;
+    let seed = Zeroizing::new(mnemonic.to_seed_normalized(""));
+
+    let derivation_path = DerivationPath::new_bip44(Some(account_index), Some(0));
+    let keypair = keypair_from_seed_and_derivation_path(&seed[..], Some(derivation_path))
+        .map_err(|e| anyhow::anyhow!("Failed to derive keypair from mnemonic: {}", e))?;
+
+    log::info!("Loaded keypair from mnemonic (m/44'/501'/{}'/0'): {}", account_index, keypair.pubkey());
+    Ok(keypair)
+}
+
+/// Load keypair from the named environment variable (Base58 key or keypair file path)
+/// The variable's value is never logged
+pub fn load_keypair_from_env(var: &str) -> Result<Keypair> {
+    let value = Zeroizing::new(env_value(var)?);
+    let var = var.trim();
+
+    log::info!("Loading keypair from environment variable {}", var);
+    let value = value.trim();
+    if value.starts_with(ENV_PREFIX) {
+        anyhow::bail!("Environment variable {} must contain a key or file path, not another env reference", var);
+    }
+    load_keypair(value).with_context(|| format!("Invalid key in environment variable {}", var))
+}
+
+/// Read a non-empty environment variable holding key material
+fn env_value(var: &str) -> Result<String> {
+    let var = var.trim();
+    if var.is_empty() {
+        anyhow::bail!("private_key \"{}\" is missing the environment variable name", ENV_PREFIX);
+    }
+
+    match std::env::var(var) {
+        Ok(value) if !value.trim().is_empty() => Ok(value),
+        Ok(_) => anyhow::bail!("Environment variable {} is empty", var),
+        Err(std::env::VarError::NotPresent) => anyhow::bail!("Environment variable {} is not set", var),
+        Err(std::env::VarError::NotUnicode(_)) => anyhow::bail!("Environment variable {} is not valid UTF-8", var),
+    }
+}
+
+/// Load keypair from a Solana CLI JSON keypair file (array of 64 bytes)
+pub fn load_keypair_from_file(path: &str) -> Result<Keypair> {
+    let path = expand_home(path);
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            anyhow::bail!("Keypair file not found: {}", path.display());
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read keypair file: {}", path.display()));
+        }
+    };
 
-    // Create keypair from seed
-    let keypair = keypair_from_seed(&seed)
-        .map_err(|e| anyhow::anyhow!("Failed to create keypair: {}", e))?;
+    let keypair_bytes: Vec<u8> = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid keypair file contents in {}: expected a JSON array of 64 bytes", path.display()))?;
+
+    let keypair = keypair_from_bytes(&keypair_bytes)
+        .with_context(|| format!("Invalid keypair file contents in {}", path.display()))?;
+
+    log::info!("Loaded keypair from {}: {}", path.display(), keypair.pubkey());
+    Ok(keypair)
+}
+
+/// Load keypair from a Solana CLI byte array given inline (e.g. `"private_key": [12, 34, ...]`)
+/// The bytes are never logged
+pub fn load_keypair_from_json(json: &str) -> Result<Keypair> {
+    let keypair_bytes: Vec<u8> = serde_json::from_str(json)
+        .context("Invalid inline keypair: expected a JSON array of 64 bytes")?;
+
+    let keypair = keypair_from_bytes(&keypair_bytes)
+        .context("Invalid inline keypair")?;
 
     log::info!("Loaded keypair: {}", keypair.pubkey());
     Ok(keypair)
 }
+
+/// Build a keypair from the 64-byte secret key format (32-byte seed + 32-byte public key)
+/// The public key half must match the seed, so a corrupted or mixed-up key is rejected
+fn keypair_from_bytes(keypair_bytes: &[u8]) -> Result<Keypair> {
+    if keypair_bytes.len() != 64 {
+        anyhow::bail!("Invalid private key: expected 64 bytes, got {}", keypair_bytes.len());
+    }
+
+    Keypair::try_from(keypair_bytes)
+        .map_err(|_| anyhow::anyhow!("Invalid private key: the last 32 bytes are not the public key of the first 32"))
+}
+
+/// Base58 never contains path separators, dots or '~', so any of those means a file path
+fn looks_like_path(value: &str) -> bool {
+    value.starts_with('~') || value.contains(['/', '\\', '.'])
+}
+
+/// Expand a leading "~/" to the user's home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn keypair() -> Keypair {
+        Keypair::new_from_array([7; 32])
+    }
+
+    fn byte_array(keypair: &Keypair) -> String {
+        serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn loads_base58() {
+        let expected = keypair();
+        let loaded = load_keypair(&expected.to_base58_string()).unwrap();
+        assert_eq!(loaded.pubkey(), expected.pubkey());
+        assert!(load_keypair("not-base58!").is_err());
+        // Valid Base58, wrong length
+        assert!(load_keypair(&bs58::encode([1u8; 32]).into_string()).is_err());
+    }
+
+    #[test]
+    fn loads_inline_byte_array() {
+        let expected = keypair();
+        let loaded = load_keypair(&format!("  {}", byte_array(&expected))).unwrap();
+        assert_eq!(loaded.pubkey(), expected.pubkey());
+        assert!(load_keypair("[1, 2, 3]").is_err());
+        assert!(load_keypair("[1, 2,").is_err());
+    }
+
+    #[test]
+    fn loads_keypair_file() {
+        let dir = TempDir::new("keypair-file");
+        let path = dir.0.join("id.json");
+        let expected = keypair();
+        std::fs::write(&path, byte_array(&expected)).unwrap();
+        let loaded = load_keypair(path.to_str().unwrap()).unwrap();
+        assert_eq!(loaded.pubkey(), expected.pubkey());
+
+        let missing = dir.0.join("missing.json");
+        let error = load_keypair(missing.to_str().unwrap()).unwrap_err().to_string();
+        assert!(error.contains("not found"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_malformed_keypair_files() {
+        let dir = TempDir::new("keypair-malformed");
+        let mut bytes = keypair().to_bytes();
+        bytes[63] ^= 1; // Public key half no longer matches the seed
+        for (name, contents) in [
+            ("text.json", "not json".to_string()),
+            ("short.json", "[1, 2, 3]".to_string()),
+            ("mismatched.json", serde_json::to_string(&bytes.to_vec()).unwrap()),
+        ] {
+            let path = dir.0.join(name);
+            std::fs::write(&path, contents).unwrap();
+            assert!(load_keypair_from_file(path.to_str().unwrap()).is_err(), "{} should be rejected", name);
+        }
+    }
+
+    const ABANDON_12: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const ABANDON_24: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+    #[test]
+    fn mnemonic_matches_solana_keygen() {
+        // solana-keygen pubkey 'prompt://?key=N/0' with an empty passphrase
+        let pubkey = |phrase, index| load_keypair_from_mnemonic(phrase, index).unwrap().pubkey().to_string();
+        assert_eq!(pubkey(ABANDON_12, 0), "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+        assert_eq!(pubkey(ABANDON_12, 1), "Hh8QwFUA6MtVu1qAoq12ucvFHNwCcVTV7hpWjeY1Hztb");
+        assert_eq!(pubkey(ABANDON_24, 0), "3Cy3YNTFywCmxoxt8n7UH6hg6dLo5uACowX3CFceaSnx");
+        // Extra whitespace and capitals are normalized away
+        assert_eq!(pubkey(&format!("  {}\n", ABANDON_12.to_uppercase().replace(' ', "   ")), 0), pubkey(ABANDON_12, 0));
+    }
+
+    #[test]
+    fn invalid_mnemonics_are_rejected() {
+        let error = |phrase: &str| load_keypair_from_mnemonic(phrase, 0).unwrap_err().to_string();
+        assert!(error(&ABANDON_12.replace("about", "abandon")).contains("checksum"));
+        assert!(error(&ABANDON_12.replace("about", "abaut")).contains("word #12"));
+        assert!(error("abandon abandon about").contains("got 3"));
+    }
+
+    #[test]
+    fn auto_mode_points_seed_phrases_at_key_source() {
+        for phrase in [ABANDON_12, ABANDON_24] {
+            let error = load_keypair(phrase).unwrap_err().to_string();
+            assert!(error.contains("\"key_source\": \"mnemonic\""), "{}", error);
+            assert!(!error.contains("abandon"), "the phrase must not leak into the error");
+        }
+    }
+}
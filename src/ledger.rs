@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+const HEADER: &str = "round_id,timestamp,blocks,bet_per_block_sol,total_bet_sol,winning_square,result,sol_earned,ore_earned,net_profit_sol,consecutive_losses";
+
+/// One completed round as written to the ledger
+pub struct LedgerEntry<'a> {
+    pub round_id: u64,
+    pub blocks: &'a [u8],
+    pub bet_per_block: u64,
+    pub winning_square: u8,
+    pub won: bool,
+    pub sol_earned: u64,          // SOL actually credited for the round (0 on a loss)
+    pub ore_earned: u64,
+    pub consecutive_losses: u8,   // Streak after this round
+}
+
+impl LedgerEntry<'_> {
+    /// CSV row; blocks are space-separated so the field needs no quoting
+    pub fn to_csv_row(&self) -> String {
+        let total_bet = self.bet_per_block * self.blocks.len() as u64;
+        let blocks: Vec<String> = self.blocks.iter().map(|b| b.to_string()).collect();
+
+        format!("{},{},{},{:.9},{:.9},{},{},{:.9},{:.11},{:.9},{}",
+            self.round_id,
+            Utc::now().to_rfc3339(),
+            blocks.join(" "),
+            self.bet_per_block as f64 / 1e9,
+            total_bet as f64 / 1e9,
+            self.winning_square,
+            if self.won { "won" } else { "lost" },
+            self.sol_earned as f64 / 1e9,
+            self.ore_earned as f64 / 1e11,
+            (self.sol_earned as i64 - total_bet as i64) as f64 / 1e9,
+            self.consecutive_losses)
+    }
+}
+
+/// Append-only CSV record of every completed round
+///
+/// The file is reopened for each row, so rows written before a restart are kept and
+/// nothing is buffered in memory when the process dies.
+#[derive(Clone)]
+pub struct Ledger {
+    path: PathBuf,
+}
+
+impl Ledger {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Append one row, writing the header first if the file is new or empty
+    pub fn append(&self, entry: &LedgerEntry) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open ledger {}", self.path.display()))?;
+
+        let len = file.metadata()?.len();
+        let mut row = String::new();
+        if len == 0 {
+            row.push_str(HEADER);
+            row.push('\n');
+        } else {
+            // A crash mid-write can leave a partial last line; start on a fresh one
+            let mut last = [0u8; 1];
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                row.push('\n');
+            }
+        }
+        row.push_str(&entry.to_csv_row());
+        row.push('\n');
+
+        file.write_all(row.as_bytes())
+            .with_context(|| format!("Failed to write ledger {}", self.path.display()))
+    }
+
+    /// Append a row, logging instead of failing the round on error
+    pub fn record(&self, entry: &LedgerEntry) {
+        if let Err(e) = self.append(entry) {
+            log::warn!("⚠️ Failed to write ledger row for round #{}: {}", entry.round_id, e);
+        }
+    }
+}
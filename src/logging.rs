@@ -0,0 +1,52 @@
+use chrono::Utc;
+use log::kv::{Error, Key, Value, VisitSource};
+use serde_json::{json, Map};
+use std::io::Write;
+use crate::config::LogFormat;
+
+/// Initialize the global logger (RUST_LOG still controls the level)
+///
+/// `json` emits one object per line: timestamp, level, target, message, plus any
+/// key-value fields attached to the record (e.g. `round_id`, `bet_lamports`)
+pub fn init(format: LogFormat) {
+    let mut builder = env_logger::Builder::from_default_env();
+
+    if format == LogFormat::Json {
+        builder.format(|buf, record| {
+            let mut entry = Map::new();
+            entry.insert("timestamp".to_string(), json!(Utc::now().to_rfc3339()));
+            entry.insert("level".to_string(), json!(record.level().as_str()));
+            entry.insert("target".to_string(), json!(record.target()));
+            entry.insert("message".to_string(), json!(record.args().to_string()));
+
+            let mut fields = JsonFields(&mut entry);
+            let _ = record.key_values().visit(&mut fields);
+
+            writeln!(buf, "{}", serde_json::Value::Object(entry))
+        });
+    }
+
+    builder.init();
+}
+
+/// Copies a record's key-value pairs into the JSON line, keeping numbers and booleans typed
+struct JsonFields<'a>(&'a mut Map<String, serde_json::Value>);
+
+impl<'kvs> VisitSource<'kvs> for JsonFields<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), Error> {
+        let value = if let Some(v) = value.to_u64() {
+            json!(v)
+        } else if let Some(v) = value.to_i64() {
+            json!(v)
+        } else if let Some(v) = value.to_f64() {
+            json!(v)
+        } else if let Some(v) = value.to_bool() {
+            json!(v)
+        } else {
+            json!(value.to_string())
+        };
+
+        self.0.insert(key.to_string(), value);
+        Ok(())
+    }
+}
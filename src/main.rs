@@ -1,163 +1,649 @@
+mod backtest;
 mod client;
 mod config;
 mod discord;
+mod export;
+mod history;
 mod keypair;
+mod ledger;
+mod logging;
 mod mining;
+mod notifier;
 mod ore;
+mod pause;
+mod reload;
+mod schedule;
+mod shutdown;
+mod status;
 mod subscription;
+mod telegram;
+#[cfg(test)]
+mod test_util;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use client::SolanaClient;
-use config::load_config;
-use discord::DiscordNotifier;
-use keypair::load_keypair;
-use mining::executor::TransactionExecutor;
-use mining::grid;
-use mining::strategy::MartingaleState;
+use config::{load_config, reload_config, OnMaxLosses, WinRateGuardAction};
+use history::{BetRecord, History, OutcomeRecord, RoundRecord};
+use keypair::load_signer;
+use ledger::{Ledger, LedgerEntry};
+use mining::ev;
+use mining::executor::{estimated_bet_fee, TransactionExecutor};
+use mining::grid::{self, BlockSelector, SelectionContext};
+use mining::strategy::{bankroll_requirement, build_strategy, next_utc_hour, BettingStrategy, MartingaleState, RoundEntry};
+use notifier::{Notifications, RoundResult};
 use ore::OreClient;
-use solana_sdk::signature::Signer;
+use ore::state::{Board, Miner, Round};
+use pause::Pause;
+use rand::Rng;
+use reload::Reload;
+use schedule::Schedule;
+use shutdown::Shutdown;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use subscription::MinerSubscription;
-use tokio::time::sleep;
+use subscription::{BoardSubscription, MinerSubscription, SlotSubscription, SubscriptionHealth, SubscriptionManager};
+use tokio::task::JoinSet;
 
 // Application-wide constants
 const SOLANA_SLOT_TIME_SECONDS: f64 = 0.4; // ~400ms per slot
 const ROUND_START_BUFFER_SECONDS: u64 = 2; // Buffer before round starts
-const ROUND_COMPLETION_POLL_INTERVAL_SECS: u64 = 10; // Polling interval for round completion
+const ROUND_COMPLETION_POLL_INTERVAL_SECS: u64 = 10; // RPC polling interval for round completion (slot stream stale)
+const SLOT_WAIT_POLL_INTERVAL_MS: u64 = 200; // How often the slot stream is checked while waiting for end_slot
+const SLOT_STREAM_STALE_SECS: u64 = 3; // Slot stream older than this falls back to RPC polling
+const ROUND_END_SLOT_BUFFER: u64 = 1; // Slots past end_slot before checking the result
 const ROUND_COMPLETION_TIMEOUT_SECS: u64 = 120; // 2 minute timeout
 const RNG_RETRY_INTERVAL_SECS: u64 = 2; // Retry interval for RNG availability
 const MAX_RNG_ATTEMPTS: u8 = 20; // Max attempts to get RNG
 const REWARDS_RETRY_INTERVAL_SECS: u64 = 2; // Retry interval for rewards update
 const MAX_REWARDS_RETRIES: u8 = 10; // Max retries for rewards update
 const WSS_UPDATE_TIMEOUT_SECS: u64 = 3; // WebSocket update timeout
-const MAX_TX_RETRIES: u8 = 3; // Max transaction retry attempts
+const TX_CONFIRMATION_SLOTS: u64 = 10; // Typical slots for one attempt to land and confirm (~4s)
 const DEFAULT_NEXT_ROUND_WAIT_SECS: u64 = 5; // Default wait time for next round
 const ERROR_RETRY_WAIT_SECS: u64 = 10; // Wait time before retry on error
 const RPC_ERROR_WAIT_SECS: u64 = 10; // Wait time on RPC error
+const CONFIG_PATH: &str = "config.json";
+const WS_STALE_AFTER_SECS: u64 = 60; // No message (not even a pong to the 30s ping) this long = stale
+const PAUSE_POLL_SECS: u64 = 5; // How often a paused bot checks whether it was resumed
+const CHI_SQUARE_CRITICAL_24DF: f64 = 36.42; // 95th percentile of chi-square with 24 degrees of freedom
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    env_logger::init();
+    logging::init(config::read_log_format(CONFIG_PATH));
+
+    // Offline subcommands, no RPC or wallet needed
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("backtest") => return backtest::run(&args[1..], CONFIG_PATH),
+        Some("export") => return export::run(&args[1..], CONFIG_PATH),
+        Some("keygen") => return keypair::run_keygen(&args[1..]),
+        _ => {}
+    }
 
     log::info!("🚀 Ore Martingale Bot starting...");
 
     // Load configuration
-    let config = load_config("config.json")?;
+    let config = load_config(CONFIG_PATH)?;
 
     // Initialize Solana client
-    let solana_client = SolanaClient::new(&config.rpc_url).await?;
+    let solana_client = SolanaClient::new(&config.rpc_url, config.transaction.commitment.config()).await?;
     log::info!("✅ Connected to Solana RPC");
 
-    // Load keypair
-    let signer = load_keypair(&config.private_key)?;
-    log::info!("✅ Loaded keypair: {}", signer.pubkey());
+    // One betting loop per wallet: just private_key, or every entry of `wallets`
+    let mut wallets = Vec::new();
+    for wallet_config in config.wallet_configs() {
+        let label = wallet_config.wallet_name.clone().unwrap_or_else(|| "wallet".to_string());
 
-    // Check balance
-    let balance = solana_client.get_balance(&signer.pubkey()).await?;
-    log::info!("💰 Balance: {:.6} SOL", balance as f64 / 1e9);
+        // Load keypair
+        let signer = load_signer(&wallet_config).with_context(|| format!("Failed to load the key of {}", label))?;
+        log::info!("✅ Loaded keypair: {}", signer.pubkey());
 
-    if balance < config.monitoring.min_balance_lamports() {
-        anyhow::bail!(
-            "⚠️ Balance ({:.6} SOL) is below minimum threshold ({:.6} SOL). Please top up.",
-            balance as f64 / 1e9,
-            config.monitoring.min_balance_sol
-        );
+        // Check balance
+        let balance = solana_client.get_balance(&signer.pubkey()).await?;
+        log::info!("💰 Balance: {:.6} SOL", balance as f64 / 1e9);
+
+        if balance < wallet_config.monitoring.min_balance_lamports() {
+            anyhow::bail!(
+                "⚠️ Balance of {} ({:.6} SOL) is below minimum threshold ({:.6} SOL). Please top up.",
+                label,
+                balance as f64 / 1e9,
+                wallet_config.monitoring.min_balance_sol
+            );
+        }
+
+        // Can the wallet survive a full losing streak?
+        check_bankroll(&wallet_config, balance).with_context(|| format!("Bankroll check failed for {}", label))?;
+
+        // Sweeping into the betting wallet itself would just burn a fee on every win
+        if wallet_config.monitoring.sweep().is_some_and(|(destination, _)| destination == signer.pubkey()) {
+            anyhow::bail!("sweep_to_address is the address of {} itself, use a separate cold wallet", label);
+        }
+        wallets.push((wallet_config, signer));
     }
 
     // Initialize Ore client
     let ore_client = OreClient::new(solana_client.clone());
     log::info!("✅ Ore client initialized");
 
-    // Initialize Discord notifier
-    let discord = DiscordNotifier::new(
-        config.discord.webhook_url.clone(),
-        config.discord.stats_webhook_url.clone(),
-        config.discord.warn_webhook_url.clone(),
-    );
-    log::info!("✅ Discord notifier initialized");
+    // Initialize notifications (Discord, Telegram, or the log)
+    let notifier = Notifications::new(notifier::build_backends(&config), &config.discord, config.dry_run);
+    log::info!("✅ Notifications initialized");
+
+    // Start the WebSocket connection for real-time miner, board and slot updates (shared by every wallet)
+    let mut subscriptions = SubscriptionManager::new(config.ws_url(), config.transaction.commitment.config());
+    let miner_subscriptions: Vec<MinerSubscription> = wallets.iter()
+        .map(|(_, signer)| subscriptions.subscribe_miner(ore_client.get_miner_pda(&signer.pubkey())))
+        .collect();
+    let boards = subscriptions.subscribe_board();
+    let slots = subscriptions.subscribe_slots();
+    subscriptions.start();
+    log::info!("📡 WebSocket subscription started");
+
+    // SIGINT/SIGTERM: finish the current round, then stop
+    let shutdown = Shutdown::listen();
+
+    // Manual kill switch for new bets: SIGUSR1, the pause file, or the status endpoint
+    let pause = Pause::listen(config.pause_file.as_ref().map(PathBuf::from));
+
+    let multi_wallet = !config.wallets.is_empty();
+    let all_wallet_states = Arc::new(Mutex::new(AllWalletStates { states: vec![None; wallets.len()], sent_through: 0 }));
+    let all_wallets_notifier = notifier.for_wallet("all wallets");
+    let runs = wallets.into_iter().zip(miner_subscriptions).enumerate().map(|(index, ((wallet_config, signer), subscription))| {
+        let notifier = match &wallet_config.wallet_name {
+            Some(name) => notifier.for_wallet(name),
+            None => notifier.clone(),
+        };
+        run_wallet(
+            wallet_config,
+            signer,
+            subscription,
+            notifier,
+            WalletShared {
+                solana_client: &solana_client,
+                ore_client: &ore_client,
+                boards: &boards,
+                slots: &slots,
+                shutdown: shutdown.clone(),
+                pause: pause.clone(),
+                primary: index == 0,
+                all_wallets: multi_wallet.then(|| AllWalletStats {
+                    index,
+                    states: all_wallet_states.clone(),
+                    notifier: all_wallets_notifier.clone(),
+                }),
+            },
+        )
+    });
+    // Every wallet runs on this task; one stopping (max losses, low balance, error) leaves the others betting
+    let results = futures::future::join_all(runs).await;
+
+    subscriptions.shutdown();
+    // A wallet that stopped on an error left its history open
+    history::close_all();
+
+    let mut summaries = Vec::new();
+    let mut first_error = None;
+    for result in results {
+        match result {
+            Ok(summary) => summaries.push(summary),
+            Err(e) if multi_wallet => {
+                log::error!("❌ Wallet stopped with an error: {:#}", e);
+                if let Err(e) = notifier.notify_error(&format!("Wallet stopped: {:#}", e)).await {
+                    log::error!("Failed to send notification: {}", e);
+                }
+            }
+            Err(e) => first_error = Some(e),
+        }
+    }
+
+    // All wallets together, next to each wallet's own shutdown message
+    if multi_wallet && shutdown.is_requested() && !summaries.is_empty() {
+        let win_count: u32 = summaries.iter().map(|s| s.win_count).sum();
+        let loss_count: u32 = summaries.iter().map(|s| s.loss_count).sum();
+        let net_profit: i64 = summaries.iter().map(|s| s.net_profit).sum();
+        let max_drawdown = summaries.iter().map(|s| s.max_drawdown).max().unwrap_or(0);
+        let rounds = win_count + loss_count;
+        let win_rate = if rounds > 0 { win_count as f64 / rounds as f64 * 100.0 } else { 0.0 };
+        log::info!("📊 All wallets: {}W/{}L ({:.1}%), net {:+.6} SOL", win_count, loss_count, win_rate, net_profit as f64 / 1e9);
+        if let Err(e) = notifier.for_wallet("all wallets").notify_shutdown(win_count, loss_count, win_rate, net_profit, max_drawdown).await {
+            log::error!("Failed to send notification: {}", e);
+        }
+    }
+
+    // Deliver queued notifications (including the shutdown message) before exiting
+    notifier.flush().await;
+
+    log::info!("👋 Bot shutting down gracefully");
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Shared by every wallet's betting loop
+struct WalletShared<'a> {
+    solana_client: &'a SolanaClient,
+    ore_client: &'a OreClient,
+    boards: &'a BoardSubscription,
+    slots: &'a SlotSubscription,
+    shutdown: Shutdown,
+    pause: Pause,
+    primary: bool, // First wallet: also watches the shared WebSocket and serves the status endpoint
+    all_wallets: Option<AllWalletStats>, // Multi-wallet runs: feeds the periodic all-wallets stats
+}
+
+/// Latest state of every wallet, combined for the all-wallets stats
+struct AllWalletStates {
+    states: Vec<Option<MartingaleState>>, // By wallet index; None until the wallet settles a round
+    sent_through: u32,                    // Combined round count the last all-wallets stats covered
+}
+
+/// One wallet's handle on the shared all-wallets stats
+#[derive(Clone)]
+struct AllWalletStats {
+    index: usize,
+    states: Arc<Mutex<AllWalletStates>>,
+    notifier: Notifications, // Tagged "all wallets"
+}
+
+impl AllWalletStats {
+    /// Store this wallet's latest state; every `interval` rounds across all wallets, send the combined stats
+    async fn record(&self, state: &MartingaleState, interval: u32) {
+        let combined = {
+            let mut shared = self.states.lock().unwrap();
+            shared.states[self.index] = Some(state.clone());
+            let combined = MartingaleState::combined(shared.states.iter().flatten());
+            let rounds = combined.win_count + combined.loss_count;
+            // Wallets settle concurrently, so the total can skip a multiple: send once per interval crossed
+            if interval == 0 || rounds / interval <= shared.sent_through / interval {
+                return;
+            }
+            shared.sent_through = rounds;
+            combined
+        };
+        if let Err(e) = self.notifier.notify_stats(&combined).await {
+            log::error!("Failed to send all-wallets stats notification: {}", e);
+        }
+    }
+}
+
+/// Stats every `stats_notification_interval` rounds for this wallet, and for all wallets together
+async fn send_periodic_stats(state: &MartingaleState, interval: u32, notifier: &Notifications, all_wallets: Option<&AllWalletStats>) {
+    let total_rounds = state.win_count + state.loss_count;
+    if total_rounds.is_multiple_of(interval) && total_rounds > 0 {
+        log_square_stats(state);
+        if let Err(e) = notifier.notify_round_summary().await {
+            log::error!("Failed to send round summary: {}", e);
+        }
+        if let Err(e) = notifier.notify_stats(state).await {
+            log::error!("Failed to send stats notification: {}", e);
+        }
+    }
+    if let Some(all_wallets) = all_wallets {
+        all_wallets.record(state, interval).await;
+    }
+}
+
+/// Final record of one wallet, for the all-wallets shutdown message
+struct WalletSummary {
+    win_count: u32,
+    loss_count: u32,
+    net_profit: i64,
+    max_drawdown: u64,
+}
+
+/// One wallet's betting loop, until shutdown, a stop condition or an unrecoverable error
+async fn run_wallet(
+    mut config: config::BotConfig,
+    signer: Keypair,
+    subscription: MinerSubscription,
+    notifier: Notifications,
+    shared: WalletShared<'_>,
+) -> Result<WalletSummary> {
+    let WalletShared { solana_client, ore_client, boards, slots, mut shutdown, pause, primary, all_wallets } = shared;
+    let solana_client = solana_client.clone();
 
     // Initialize transaction executor
-    let executor = TransactionExecutor::new(solana_client.clone(), MAX_TX_RETRIES);
-    log::info!("✅ Transaction executor initialized (max retries: {})", MAX_TX_RETRIES);
+    let executor = TransactionExecutor::new(
+        solana_client.clone(),
+        ore_client.clone(),
+        config.dry_run,
+        config.transaction.clone(),
+        config.ws_url(),
+    );
+    log::info!("✅ Transaction executor initialized (max retries: {})", config.transaction.max_retries);
+
+    // Per-round CSV record (optional)
+    let ledger = config.effective_ledger_file().map(Ledger::new);
 
-    log::info!("✅ Grid selector initialized (random selection)");
+    // Per-round SQLite history (optional)
+    let history = config.effective_history_db().map(|path| History::open(&path)).transpose()?;
 
-    // Initialize martingale state (wrapped in Arc<Mutex> for sharing with async tasks)
-    let martingale_state = Arc::new(Mutex::new(MartingaleState::new(config.martingale.base_bet_lamports())));
+    // Initialize betting strategy, restoring any persisted state (wrapped in Arc<Mutex> for sharing with async tasks)
+    let martingale_state = Arc::new(Mutex::new(build_strategy(&config.martingale, &config.effective_state_file())));
+    {
+        let mut strategy = martingale_state.lock().unwrap();
+        strategy.state_mut().set_rolling_window(config.monitoring.rolling_window_rounds);
+        let state = strategy.state();
+        log::info!("   Current bet: {:.6} SOL per block, consecutive losses: {}, record: {}W/{}L",
+            strategy.next_bet() as f64 / 1e9,
+            state.consecutive_losses,
+            state.win_count,
+            state.loss_count);
+    }
 
     // Check initial rewards from miner account (if exists)
     if let Some(miner) = ore_client.get_miner(&signer.pubkey()).await? {
         log::info!("💰 Existing unclaimed rewards: {:.6} SOL", miner.rewards_sol as f64 / 1e9);
     }
 
-    log::info!("✅ Martingale state initialized");
+    log::info!("✅ Betting strategy initialized ({:?})", config.martingale.strategy);
     log::info!("   Base bet: {:.6} SOL per block", config.martingale.base_bet_amount);
     log::info!("   Max consecutive losses: {}", config.martingale.max_consecutive_losses);
     log::info!("   Warning threshold: {}", config.martingale.warn_consecutive_losses);
     log::info!("   Blocks per bet: {}", config.martingale.blocks_per_bet);
 
-    // Start WebSocket subscription for real-time miner updates
-    let miner_pda = ore_client.get_miner_pda(&signer.pubkey());
-    let subscription = MinerSubscription::new(config.rpc_url.clone(), miner_pda).await?;
-    log::info!("📡 WebSocket subscription started");
+    // Block selector (rebuilt when a reload changes block_selection)
+    let mut selector = grid::build_selector(&config.martingale.block_selection);
+    log::info!("✅ Grid selector initialized ({:?})", config.martingale.block_selection);
+
+    // SIGHUP: re-read martingale/monitoring settings between rounds
+    let reload = Reload::listen();
+
+    // WebSocket health: when it went unhealthy, and whether that was already reported
+    let mut ws_unhealthy_since: Option<std::time::Instant> = None;
+    let mut ws_alert_sent = false;
+    let mut was_paused = false;
+
+    // Digest and heartbeat tasks, stopped when this wallet's loop ends
+    let mut background_tasks = Vec::new();
+
+    // Daily digest to the stats webhook at the configured UTC hour
+    if let Some(hour) = config.monitoring.daily_summary_hour_utc {
+        background_tasks.push(tokio::spawn(daily_summary_task(hour, martingale_state.clone(), notifier.clone())));
+        log::info!("📅 Daily summary scheduled at {:02}:00 UTC", hour);
+    }
+
+    // Periodic "still alive" ping to the stats channel
+    if config.discord.heartbeat_interval_minutes > 0 {
+        background_tasks.push(tokio::spawn(heartbeat_task(
+            Duration::from_secs(config.discord.heartbeat_interval_minutes * 60),
+            solana_client.clone(),
+            signer.pubkey(),
+            martingale_state.clone(),
+            subscription.clone(),
+            notifier.clone(),
+        )));
+        log::info!("💓 Heartbeat every {} minutes", config.discord.heartbeat_interval_minutes);
+    }
+
+    // Betting windows (validated when the config was loaded)
+    let schedule = Schedule::parse(&config.schedule.active_hours)?;
+    let mut outside_schedule = false;
+
+    // Local status endpoint with pause/resume
+    if let Some(status_config) = config.status.as_ref().filter(|_| primary) {
+        status::start(status_config.socket_addr()?, status::StatusContext {
+            martingale_state: martingale_state.clone(),
+            solana_client: solana_client.clone(),
+            wallet: signer.pubkey(),
+            subscription: subscription.clone(),
+            boards: boards.clone(),
+            board_stale: Duration::from_secs(config.board_stale_secs),
+            ws_stale: Duration::from_secs(WS_STALE_AFTER_SECS),
+            pause: pause.clone(),
+            auth_token: status_config.auth_token.clone(),
+            started: std::time::Instant::now(),
+        }).await?;
+    }
+
+    // Post-win reward tasks (claims, notifications); awaited before exit so a claim isn't cut off
+    let mut reward_tasks = JoinSet::new();
 
     log::info!("🚀 Starting main betting loop...");
 
     // Main event loop
     loop {
+        if shutdown.is_requested() {
+            break;
+        }
+        while reward_tasks.try_join_next().is_some() {}
+
+        if reload.take() {
+            let block_selection = config.martingale.block_selection.clone();
+            match reload_config(CONFIG_PATH, &mut config) {
+                Ok(result) => {
+                    for change in &result.changed {
+                        log::info!("🔄 {}", change);
+                    }
+                    if !result.ignored.is_empty() {
+                        log::warn!("⚠️ Ignored until restart: {}", result.ignored.join(", "));
+                    }
+                    martingale_state.lock().unwrap().state_mut().set_rolling_window(config.monitoring.rolling_window_rounds);
+                    if config.martingale.block_selection != block_selection {
+                        selector = grid::build_selector(&config.martingale.block_selection);
+                    }
+                    log::info!("✅ Config reloaded ({} change(s))", result.changed.len());
+
+                    // New bet sizing or reserve: check the ladder again (alert only, the bot is mid-session)
+                    if !result.changed.is_empty() {
+                        match solana_client.get_balance(&signer.pubkey()).await {
+                            Ok(balance) => {
+                                if let Err(e) = check_bankroll(&config, balance) {
+                                    log::error!("❌ After reload: {:#}", e);
+                                    if let Err(e) = notifier.notify_error(&format!("After config reload: {:#}", e)).await {
+                                        log::error!("Failed to send notification: {}", e);
+                                    }
+                                }
+                            }
+                            Err(e) => log::warn!("⚠️ Failed to fetch balance for the bankroll check: {}", e),
+                        }
+                    }
+                    if let Err(e) = notifier.notify_config_reloaded(&result.changed, &result.ignored).await {
+                        log::error!("Failed to send notification: {}", e);
+                    }
+                }
+                Err(e) => {
+                    log::error!("❌ Config reload failed, keeping the running config: {:#}", e);
+                    if let Err(e) = notifier.notify_error(&format!("Config reload failed: {:#}", e)).await {
+                        log::error!("Failed to send notification: {}", e);
+                    }
+                }
+            }
+        }
+
+        // Once per round (first wallet only, the connection is shared): alert if the WebSocket has been stale/down too long, and when it recovers
+        if primary {
+            let health = subscription.health(Duration::from_secs(WS_STALE_AFTER_SECS));
+            let reconnects = subscription.reconnect_count();
+            match health {
+                SubscriptionHealth::Connected => {
+                    if let Some(since) = ws_unhealthy_since.take() {
+                        if ws_alert_sent {
+                            let minutes = since.elapsed().as_secs() / 60;
+                            log::info!("📡 WebSocket recovered after ~{} minutes ({} reconnects so far)", minutes, reconnects);
+                            if let Err(e) = notifier.notify_infra_recovered(minutes, reconnects).await {
+                                log::error!("Failed to send notification: {}", e);
+                            }
+                        }
+                        ws_alert_sent = false;
+                    }
+                }
+                SubscriptionHealth::Stale(_) | SubscriptionHealth::Disconnected => {
+                    let since = *ws_unhealthy_since.get_or_insert_with(std::time::Instant::now);
+                    let alert_after = Duration::from_secs(config.monitoring.ws_stale_alert_minutes * 60);
+                    log::debug!("📡 WebSocket health: {:?} ({} reconnects)", health, reconnects);
+                    if !ws_alert_sent && since.elapsed() >= alert_after {
+                        let status = if health == SubscriptionHealth::Disconnected { "disconnected" } else { "stale" };
+                        log::warn!("📡 WebSocket {} for {}+ minutes ({} reconnects)", status, config.monitoring.ws_stale_alert_minutes, reconnects);
+                        if let Err(e) = notifier.notify_infra_warning(status, config.monitoring.ws_stale_alert_minutes, reconnects).await {
+                            log::error!("Failed to send notification: {}", e);
+                        }
+                        ws_alert_sent = true;
+                    }
+                }
+            }
+        }
+
+        // Paused by hand: skip betting (the cycle stays as is) and poll cheaply until resumed
+        let pause_reason = pause.reason();
+        if pause_reason.is_some() != was_paused {
+            was_paused = pause_reason.is_some();
+            match pause_reason {
+                Some(reason) => log::warn!("⏸️ Paused ({}), no new bets until resumed", reason),
+                None => log::info!("▶️ Resumed, betting again"),
+            }
+            if let Err(e) = notifier.notify_pause_changed(was_paused, pause_reason).await {
+                log::error!("Failed to send notification: {}", e);
+            }
+        }
+        if was_paused {
+            shutdown.sleep(Duration::from_secs(PAUSE_POLL_SECS)).await;
+            continue;
+        }
+
+        // Outside the betting windows: sleep until the next one opens (skipped rounds aren't losses)
+        let now = chrono::Utc::now();
+        if !schedule.is_active(now) {
+            if let Some(next) = schedule.next_start(now) {
+                if !outside_schedule {
+                    outside_schedule = true;
+                    log::info!("😴 Outside schedule, betting resumes at {} UTC", next.format("%a %Y-%m-%d %H:%M"));
+                    if let Err(e) = notifier.notify_outside_schedule(next).await {
+                        log::error!("Failed to send notification: {}", e);
+                    }
+                }
+                let wait = (next - now).to_std().unwrap_or_default();
+                shutdown.sleep(wait).await;
+                continue;
+            }
+        } else if outside_schedule {
+            outside_schedule = false;
+            log::info!("⏰ Betting window open, resuming");
+        }
+
         match run_betting_round(
-            &ore_client,
+            ore_client,
             &executor,
             &martingale_state,
-            &discord,
+            &notifier,
             &signer,
             &config,
             &subscription,
+            slots,
+            boards,
+            selector.as_ref(),
+            ledger.as_ref(),
+            history.as_ref(),
+            all_wallets.as_ref(),
+            &mut reward_tasks,
         ).await {
-            Ok(should_continue) => {
-                if !should_continue {
-                    log::warn!("⚠️ Max consecutive losses reached. Pausing bot.");
+            Ok(outcome) => {
+                collect_fees(&executor, &martingale_state);
+
+                // Checkpoint state after every round so a restart resumes the cycle at the right bet level
+                martingale_state.lock().unwrap().persist();
+
+                if config.dry_run {
+                    let strategy = martingale_state.lock().unwrap();
+                    let state = strategy.state();
+                    log::info!("🧪 DRY RUN P&L: {:+.6} SOL (theoretical), max drawdown: {:.6} SOL, record: {}W/{}L ({:.1}% win rate)",
+                        state.net_profit_sol() as f64 / 1e9,
+                        state.max_drawdown_lamports as f64 / 1e9,
+                        state.win_count,
+                        state.loss_count,
+                        state.win_rate());
+                }
 
-                    // Send error notification
-                    if let Err(e) = discord.notify_error("Max consecutive losses reached. Bot paused.").await {
-                        log::error!("Failed to send Discord notification: {}", e);
+                match outcome {
+                    RoundOutcome::Continue => {}
+                    RoundOutcome::MaxLossesReached => match config.martingale.on_max_losses {
+                        OnMaxLosses::Stop => {
+                            log::warn!("⚠️ Pausing bot.");
+                            if let Err(e) = notifier.notify_max_losses("Max consecutive losses reached. Bot paused.").await {
+                                log::error!("Failed to send notification: {}", e);
+                            }
+                            break;
+                        }
+                        OnMaxLosses::Cooldown => {
+                            // The strategy already reset its progression in on_loss; stats counters are kept
+                            if let Some(rounds) = config.martingale.cooldown_rounds {
+                                // Counted in rounds: the loop keeps following rounds and run_betting_round skips them
+                                {
+                                    let mut strategy = martingale_state.lock().unwrap();
+                                    let state = strategy.state_mut();
+                                    state.cooldown_until_round = Some(state.current_round + rounds);
+                                }
+                                martingale_state.lock().unwrap().persist();
+                                log::warn!("⏸️ Cooling down for {} rounds before resuming at base bet", rounds);
+                                if let Err(e) = notifier.notify_cooldown(&format!("{} rounds", rounds)).await {
+                                    log::error!("Failed to send notification: {}", e);
+                                }
+                            } else {
+                                let minutes = config.martingale.cooldown_minutes;
+                                log::warn!("⏸️ Cooling down for {} minutes before resuming at base bet", minutes);
+                                if let Err(e) = notifier.notify_cooldown(&format!("{} minutes", minutes)).await {
+                                    log::error!("Failed to send notification: {}", e);
+                                }
+                                if shutdown.sleep(Duration::from_secs(minutes * 60)).await {
+                                    break;
+                                }
+                                log::info!("▶️ Cooldown over, resuming betting");
+                                if let Err(e) = notifier.notify_cooldown_over().await {
+                                    log::error!("Failed to send notification: {}", e);
+                                }
+                                continue;
+                            }
+                        }
+                    },
+                    RoundOutcome::Stop => {
+                        // The round already logged and notified why it stopped
+                        log::warn!("⚠️ Pausing bot.");
+                        break;
                     }
-
-                    break;
                 }
             }
             Err(e) => {
-                log::error!("❌ Error in betting round: {}", e);
+                log::error!("❌ Error in betting round: {:#}", e);
+                // A transaction that landed but failed is still charged
+                collect_fees(&executor, &martingale_state);
 
                 // Send error notification
-                if let Err(e) = discord.notify_error(&format!("Error: {}", e)).await {
-                    log::error!("Failed to send Discord notification: {}", e);
+                if let Err(e) = notifier.notify_error(&format!("Error: {}", e)).await {
+                    log::error!("Failed to send notification: {}", e);
                 }
 
                 // Wait before retrying
                 log::info!("⏳ Waiting {} seconds before retry...", ERROR_RETRY_WAIT_SECS);
-                sleep(Duration::from_secs(ERROR_RETRY_WAIT_SECS)).await;
+                shutdown.sleep(Duration::from_secs(ERROR_RETRY_WAIT_SECS)).await;
             }
         }
 
-        // Check balance periodically
-        let balance = solana_client.get_balance(&signer.pubkey()).await?;
+        // Check balance periodically (an RPC hiccup must not end this wallet's loop)
+        let balance = match solana_client.get_balance(&signer.pubkey()).await {
+            Ok(balance) => balance,
+            Err(e) => {
+                log::warn!("⚠️ Failed to fetch balance: {}. Retrying in {} seconds...", e, ERROR_RETRY_WAIT_SECS);
+                shutdown.sleep(Duration::from_secs(ERROR_RETRY_WAIT_SECS)).await;
+                continue;
+            }
+        };
         if balance < config.monitoring.min_balance_lamports() {
             log::error!("⚠️ Balance too low: {:.6} SOL", balance as f64 / 1e9);
 
-            if let Err(e) = discord.notify_error(&format!(
+            if let Err(e) = notifier.notify_error(&format!(
                 "Balance too low: {:.6} SOL. Please top up.",
                 balance as f64 / 1e9
             )).await {
-                log::error!("Failed to send Discord notification: {}", e);
+                log::error!("Failed to send notification: {}", e);
             }
 
             break;
         }
 
         // Calculate dynamic wait time until next round
-        match ore_client.get_board().await {
+        match current_board(ore_client, boards, &config).await {
             Ok(current_board) => {
                 match ore_client.solana.rpc.get_slot().await {
                     Ok(current_slot) => {
@@ -168,46 +654,110 @@ async fn main() -> Result<()> {
                             let wait_time = seconds_until_start + ROUND_START_BUFFER_SECONDS;
                             log::info!("⏳ Next round starts in ~{} seconds (slot {} -> {})",
                                 wait_time, current_slot, current_board.start_slot);
-                            sleep(Duration::from_secs(wait_time)).await;
+                            shutdown.sleep(Duration::from_secs(wait_time)).await;
                         } else {
-                            // Already past start, wait default time
-                            log::info!("⏳ Waiting for next round ({} seconds)...", DEFAULT_NEXT_ROUND_WAIT_SECS);
-                            sleep(Duration::from_secs(DEFAULT_NEXT_ROUND_WAIT_SECS)).await;
+                            // Already past start: wake as soon as the board stream shows a new round
+                            let timeout = Duration::from_secs(DEFAULT_NEXT_ROUND_WAIT_SECS);
+                            log::info!("⏳ Waiting for next round (up to {} seconds)...", DEFAULT_NEXT_ROUND_WAIT_SECS);
+                            tokio::select! {
+                                Some(board) = boards.wait_for_new_round(current_board.round_id, timeout) => {
+                                    log::debug!("📬 Round #{} announced by board stream", board.round_id);
+                                }
+                                _ = shutdown.sleep(timeout) => {}
+                            }
                         }
                     }
                     Err(e) => {
                         log::warn!("⚠️ Failed to get current slot: {}. Waiting {} seconds...", e, RPC_ERROR_WAIT_SECS);
-                        sleep(Duration::from_secs(RPC_ERROR_WAIT_SECS)).await;
+                        shutdown.sleep(Duration::from_secs(RPC_ERROR_WAIT_SECS)).await;
                     }
                 }
             }
             Err(e) => {
                 log::warn!("⚠️ Failed to get board: {}. Waiting {} seconds...", e, RPC_ERROR_WAIT_SECS);
-                sleep(Duration::from_secs(RPC_ERROR_WAIT_SECS)).await;
+                shutdown.sleep(Duration::from_secs(RPC_ERROR_WAIT_SECS)).await;
             }
         }
     }
 
-    log::info!("👋 Bot shutting down gracefully");
-    Ok(())
+    // Let in-flight reward tasks (claim confirmations, earnings updates) finish before the final save
+    if !reward_tasks.is_empty() {
+        log::info!("⏳ Waiting for {} pending reward task(s) to finish...", reward_tasks.len());
+        while reward_tasks.join_next().await.is_some() {}
+    }
+
+    // Flush state and stop background work before exiting
+    collect_fees(&executor, &martingale_state);
+    martingale_state.lock().unwrap().persist();
+    for task in &background_tasks {
+        task.abort();
+    }
+    if let Some(history) = &history {
+        history.close();
+    }
+
+    if shutdown.is_requested() {
+        let (win_count, loss_count, win_rate, net_profit, max_drawdown) = {
+            let strategy = martingale_state.lock().unwrap();
+            let state = strategy.state();
+            (state.win_count, state.loss_count, state.win_rate(), state.net_profit_sol(), state.max_drawdown_lamports)
+        };
+        // Rounds not yet rolled up into a summary
+        if let Err(e) = notifier.notify_round_summary().await {
+            log::error!("Failed to send round summary: {}", e);
+        }
+        if let Err(e) = notifier.notify_shutdown(win_count, loss_count, win_rate, net_profit, max_drawdown).await {
+            log::error!("Failed to send notification: {}", e);
+        }
+    }
+
+    let strategy = martingale_state.lock().unwrap();
+    let state = strategy.state();
+    Ok(WalletSummary {
+        win_count: state.win_count,
+        loss_count: state.loss_count,
+        net_profit: state.net_profit_sol(),
+        max_drawdown: state.max_drawdown_lamports,
+    })
+}
+
+
+
+/// What the main loop should do after a betting round
+enum RoundOutcome {
+    /// Keep betting
+    Continue,
+    /// The strategy hit max_consecutive_losses (stop or cool down per `on_max_losses`)
+    MaxLossesReached,
+    /// A stop condition was hit and already reported (e.g. session loss limit)
+    Stop,
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_betting_round(
     ore_client: &OreClient,
     executor: &TransactionExecutor,
-    martingale_state: &Arc<Mutex<MartingaleState>>,
-    discord: &DiscordNotifier,
+    martingale_state: &Arc<Mutex<Box<dyn BettingStrategy + Send>>>,
+    notifier: &Notifications,
     signer: &dyn Signer,
     config: &config::BotConfig,
     subscription: &MinerSubscription,
-) -> Result<bool> {
+    slots: &SlotSubscription,
+    boards: &BoardSubscription,
+    selector: &dyn BlockSelector,
+    ledger: Option<&Ledger>,
+    history: Option<&History>,
+    all_wallets: Option<&AllWalletStats>,
+    reward_tasks: &mut JoinSet<()>,
+) -> Result<RoundOutcome> {
     // Get current board state
-    let board = ore_client.get_board().await?;
+    let board = current_board(ore_client, boards, config).await?;
     let round_id = board.round_id;
 
     // Check if this is a new round
     {
-        let mut state = martingale_state.lock().unwrap();
+        let mut strategy = martingale_state.lock().unwrap();
+        let state = strategy.state_mut();
         if state.current_round != round_id {
             log::info!("🆕 New round detected: #{}", round_id);
             state.current_round = round_id;
@@ -216,6 +766,127 @@ async fn run_betting_round(
         }
     }
 
+    // Round-based cooldown after max losses: rounds are still followed, nothing is bet
+    let cooldown_until = martingale_state.lock().unwrap().state().cooldown_until_round;
+    if let Some(until) = cooldown_until {
+        if round_id <= until {
+            log::debug!("⏸️ Cooling down, skipping round #{} ({} left)", round_id, until - round_id + 1);
+            return Ok(RoundOutcome::Continue);
+        }
+        martingale_state.lock().unwrap().state_mut().cooldown_until_round = None;
+        log::info!("▶️ Cooldown over, resuming betting");
+        if let Err(e) = notifier.notify_cooldown_over().await {
+            log::error!("Failed to send notification: {}", e);
+        }
+    }
+
+    // Win-rate guard: back off while the recent win rate is below the floor
+    if let Some(guard) = &config.martingale.win_rate_guard {
+        let skip_until = martingale_state.lock().unwrap().state().win_rate_guard_until_round;
+        if let Some(until) = skip_until.filter(|_| guard.action == WinRateGuardAction::Skip) {
+            if round_id <= until {
+                log::debug!("📉 Win-rate guard, skipping round #{} ({} left)", round_id, until - round_id + 1);
+                return Ok(RoundOutcome::Continue);
+            }
+        }
+
+        let (below, was_tripped) = {
+            let mut strategy = martingale_state.lock().unwrap();
+            let state = strategy.state_mut();
+            let below = state.win_rate_below_floor(guard);
+            let was_tripped = state.win_rate_guard_tripped;
+            state.win_rate_guard_tripped = below.is_some();
+            (below, was_tripped)
+        };
+
+        match (below, guard.action) {
+            (Some(rate), WinRateGuardAction::Skip) => {
+                // Rounds up to `until` are skipped, and only rounds after it count toward the next check
+                let until = round_id + guard.skip_rounds - 1;
+                martingale_state.lock().unwrap().state_mut().win_rate_guard_until_round = Some(until);
+                martingale_state.lock().unwrap().persist();
+                log::warn!("📉 Win rate {:.1}% over the last {} rounds is below {}%. Skipping {} rounds.",
+                    rate, guard.window_rounds, guard.min_win_rate, guard.skip_rounds);
+                let action = format!("Skipping {} rounds, then betting until a fresh window of {} rounds has been played.",
+                    guard.skip_rounds, guard.window_rounds);
+                if let Err(e) = notifier.notify_win_rate_guard(rate, guard.window_rounds, guard.min_win_rate, &action).await {
+                    log::error!("Failed to send notification: {}", e);
+                }
+                return Ok(RoundOutcome::Continue);
+            }
+            (Some(rate), WinRateGuardAction::BaseBet) => {
+                let downshifted = {
+                    let mut strategy = martingale_state.lock().unwrap();
+                    let climbing = strategy.state().consecutive_losses > 0;
+                    if climbing {
+                        strategy.reset(&config.martingale);
+                    }
+                    climbing
+                };
+                if !was_tripped {
+                    log::warn!("📉 Win rate {:.1}% over the last {} rounds is below {}%. Betting the base bet until it recovers.",
+                        rate, guard.window_rounds, guard.min_win_rate);
+                    let action = "Losses no longer escalate the bet until the win rate is back above the floor.";
+                    if let Err(e) = notifier.notify_win_rate_guard(rate, guard.window_rounds, guard.min_win_rate, action).await {
+                        log::error!("Failed to send notification: {}", e);
+                    }
+                } else if downshifted {
+                    log::info!("📉 Win rate still {:.1}%, back to the base bet", rate);
+                }
+            }
+            (None, action) if was_tripped => {
+                let detail = match action {
+                    WinRateGuardAction::Skip => "Skipped rounds are over, betting resumed where the progression left off.",
+                    WinRateGuardAction::BaseBet => "Win rate back above the floor, losses escalate the bet again.",
+                };
+                log::info!("📈 {}", detail);
+                if let Err(e) = notifier.notify_win_rate_guard_lifted(detail).await {
+                    log::error!("Failed to send notification: {}", e);
+                }
+            }
+            (None, _) => {}
+        }
+    }
+
+    // Daily limits: skip betting until the UTC day rolls over
+    let daily_loss_limit = config.monitoring.daily_max_loss_lamports();
+    let daily_profit_target = config.monitoring.daily_profit_target_lamports();
+    if daily_loss_limit.is_some() || daily_profit_target.is_some() {
+        let (was_paused, hit, newly_hit, day_net) = {
+            let mut strategy = martingale_state.lock().unwrap();
+            let state = strategy.state_mut();
+            let was_paused = state.daily_limit_notified;
+            let hit = state.daily_limit_hit(daily_loss_limit, daily_profit_target);
+            // roll_day clears the flag at midnight, so a pause from yesterday reads as false here
+            let newly_hit = hit.is_some() && !state.daily_limit_notified;
+            state.daily_limit_notified = hit.is_some();
+            (was_paused, hit, newly_hit, state.day_net_lamports)
+        };
+
+        match hit {
+            Some(limit) => {
+                if newly_hit {
+                    log::warn!("🛑 Daily limit reached ({:?}, {:+.6} SOL today). Pausing until 00:00 UTC.",
+                        limit,
+                        day_net as f64 / 1e9);
+                    if let Err(e) = notifier.notify_daily_limit(limit, day_net).await {
+                        log::error!("Failed to send notification: {}", e);
+                    }
+                } else {
+                    log::debug!("⏸️ Daily limit reached, skipping round #{}", round_id);
+                }
+                return Ok(RoundOutcome::Continue);
+            }
+            None if was_paused => {
+                log::info!("▶️ Daily limit lifted. Resuming betting.");
+                if let Err(e) = notifier.notify_daily_resume().await {
+                    log::error!("Failed to send notification: {}", e);
+                }
+            }
+            None => {}
+        }
+    }
+
     // Check if round is active
     if !ore_client.is_round_active(&board).await? {
         let current_slot = ore_client.solana.rpc.get_slot().await?;
@@ -227,14 +898,25 @@ async fn run_betting_round(
         } else {
             log::debug!("⏸️ Round not active yet. Waiting...");
         }
-        return Ok(true);
+        return Ok(RoundOutcome::Continue);
     }
 
-    // Get current round data (for future use)
-    let _round = ore_client.get_round(round_id).await?;
+    // Random pause so our Deploy doesn't land at the same point of every round
+    if let Some(jitter) = config.transaction.bet_delay_jitter_ms.filter(|&ms| ms > 0) {
+        let delay = Duration::from_millis(rand::rng().random_range(0..=jitter));
+        log::debug!("🎲 Waiting {:?} before betting (jitter up to {} ms)", delay, jitter);
+        tokio::time::sleep(delay).await;
+    }
 
-    // Save current rewards before betting
-    let (rewards_sol_before, rewards_ore_before) = if let Some(miner) = ore_client.get_miner(&signer.pubkey()).await? {
+    // Current round data (deployed totals drive block selection), the rewards before betting and the
+    // slot for the time-left check, fetched together: every round trip here delays the bet
+    let authority = signer.pubkey();
+    let (round, miner_before, current_slot) = tokio::try_join!(
+        async { ore_client.get_round(round_id).await.with_context(|| format!("Failed to fetch round #{}", round_id)) },
+        async { ore_client.get_miner(&authority).await.context("Failed to fetch miner account") },
+        async { ore_client.solana.rpc.get_slot().await.context("Failed to fetch current slot") },
+    )?;
+    let (rewards_sol_before, rewards_ore_before) = if let Some(miner) = &miner_before {
         log::debug!("💰 Current rewards before bet: {:.6} SOL, {:.6} ORE",
             miner.rewards_sol as f64 / 1e9,
             miner.rewards_ore as f64 / 1e11);
@@ -243,293 +925,291 @@ async fn run_betting_round(
         (0, 0)
     };
 
-    // Select blocks to bet on
-    let blocks = grid::select_blocks(config.martingale.blocks_per_bet);
-    let block_indices: Vec<u8> = blocks.iter().map(|b| b.index).collect();
-
-    let (bet_per_block, consecutive_losses) = {
-        let state = martingale_state.lock().unwrap();
-        (state.current_bet_per_block, state.consecutive_losses)
+    // A bet already on chain for this round (e.g. placed before a restart): don't bet twice,
+    // just wait for the result
+    let existing_bet = miner_before.as_ref().and_then(|miner| existing_deployment(miner, round_id));
+    let (block_indices, bet_per_block, bet_signature, bet_fee) = if let Some((block_indices, bet_per_block, total_bet)) = existing_bet {
+        log::info!("♻️ Resumed existing bet on round #{}: blocks {:?}, {:.6} SOL per block, total: {:.6} SOL",
+            round_id,
+            block_indices,
+            bet_per_block as f64 / 1e9,
+            total_bet as f64 / 1e9);
+        // Landed but never counted (crash before the state was saved, or a send reported as failed
+        // that made it on chain): count it now so totals and the cycle stake stay right
+        let (counted, consecutive_losses) = {
+            let strategy = martingale_state.lock().unwrap();
+            (strategy.state().last_bet_round == Some(round_id), strategy.state().consecutive_losses)
+        };
+        if !counted {
+            log::warn!("♻️ Bet on round #{} wasn't in the saved state, counting it now", round_id);
+            martingale_state.lock().unwrap().record_bet(round_id, total_bet);
+        }
+        // Without a bet row the round's outcome would be left out of the export
+        if let Some(history) = history {
+            history.record_resumed_bet(BetRecord {
+                round_id,
+                blocks: block_indices.clone(),
+                bet_per_block,
+                fee: 0,
+                signature: None,
+                consecutive_losses,
+            });
+        }
+        if let Err(e) = notifier.notify_resumed_bet(round_id, &block_indices, total_bet).await {
+            log::error!("Failed to send notification: {}", e);
+        }
+        (block_indices, bet_per_block, None, 0) // Signature and fee of the original Deploy aren't known
+    } else {
+        // Too close to end_slot: the Deploy would likely land after the round closes
+        // Default: enough time for every retry
+        let min_remaining_slots = config.transaction.min_remaining_slots
+            .unwrap_or(config.transaction.max_retries as u64 * TX_CONFIRMATION_SLOTS);
+        let remaining_slots = board.end_slot.saturating_sub(current_slot);
+        if remaining_slots < min_remaining_slots {
+            log::info!("⏭️ Skipping round #{}, only {} slots remain (min: {})",
+                round_id, remaining_slots, min_remaining_slots);
+            return Ok(RoundOutcome::Continue);
+        }
+        // Confirmation polling gives up once the round is over
+        let round_deadline = std::time::Instant::now()
+            + Duration::from_secs_f64(remaining_slots as f64 * SOLANA_SLOT_TIME_SECONDS);
+
+        match place_bet(
+            ore_client,
+            executor,
+            martingale_state,
+            notifier,
+            signer,
+            config,
+            selector,
+            &round,
+            round_id,
+            round_deadline,
+            history,
+        ).await? {
+            Some(bet) => bet,
+            None => return Ok(RoundOutcome::Continue), // Round skipped, no bet placed
+        }
     };
-    let total_bet = bet_per_block * (blocks.len() as u64);
 
-    // Check if we have enough balance for this bet
-    // Reserve some SOL for transaction fees and rent-exempt minimum
-    let current_balance = ore_client.solana.get_balance(&signer.pubkey()).await?;
-    let required_balance = total_bet;
-    
-    if current_balance < required_balance {
-        log::error!("⚠️ Insufficient balance for bet!");
-        log::error!("   Current: {:.6} SOL", current_balance as f64 / 1e9);
-        log::error!("   Required: {:.6} SOL (bet) = {:.6} SOL",
-            total_bet as f64 / 1e9,
-            required_balance as f64 / 1e9);
-        
-        if let Err(e) = discord.notify_error(&format!(
-            "Insufficient balance: {:.6} SOL < {:.6} SOL required",
-            current_balance as f64 / 1e9,
-            required_balance as f64 / 1e9
-        )).await {
-            log::error!("Failed to send Discord notification: {}", e);
-        }
-        
-        anyhow::bail!("Insufficient balance for bet");
-    }
+    // Wait for round to complete (max 2 minutes)
+    log::debug!("⏳ Waiting for round #{} to complete...", round_id);
+    wait_for_round_end(ore_client, slots, boards, config, round_id).await?;
 
-    log::info!("🎲 Betting on blocks: {:?}", block_indices);
-    log::info!("💰 Bet: {:.6} SOL per block, total: {:.6} SOL",
-        bet_per_block as f64 / 1e9,
-        total_bet as f64 / 1e9
-    );
+    // Get final round results with retry for RNG
+    log::debug!("📊 Fetching final round results...");
+    let mut final_round = ore_client.get_round(round_id).await?;
+    let mut rng_attempts = 0;
 
-    // Send bet notification to Discord
-    if let Err(e) = discord.notify_bet(
-        round_id,
-        &block_indices,
-        bet_per_block,
-        total_bet,
-        consecutive_losses,
-    ).await {
-        log::error!("Failed to send Discord notification: {}", e);
+    // Retry if RNG not available (slot_hash might not be ready immediately)
+    while final_round.rng().is_none() && rng_attempts < MAX_RNG_ATTEMPTS {
+        rng_attempts += 1;
+        log::debug!("⏳ RNG not available yet, retrying ({}/{})...", rng_attempts, MAX_RNG_ATTEMPTS);
+        tokio::time::sleep(Duration::from_secs(RNG_RETRY_INTERVAL_SECS)).await;
+        final_round = ore_client.get_round(round_id).await?;
     }
 
-    // Check if miner needs checkpoint and execute in single transaction
-    if let Some(miner) = ore_client.get_miner(&signer.pubkey()).await? {
-        if miner.checkpoint_id != miner.round_id {
-            // Checkpoint needed - combine with deploy in single transaction
-            log::info!("📤 Sending combined Checkpoint + Deploy transaction...");
-            match executor.execute_checkpoint_and_bet(
-                signer,
-                miner.round_id,
-                round_id,
-                &blocks,
-                bet_per_block,
-            ).await {
-                Ok(signature) => {
-                    log::info!("✅ Checkpoint + Bet placed successfully!");
-                    log::info!("   Signature: {}", signature);
-                    martingale_state.lock().unwrap().record_bet(total_bet);
+    if let Some(history) = history {
+        history.record_round(RoundRecord {
+            round_id,
+            start_slot: board.start_slot,
+            end_slot: board.end_slot,
+            winning_square: final_round.rng().map(|rng| final_round.winning_square(rng) as u8),
+            total_deployed: final_round.total_deployed,
+            motherlode: final_round.motherlode,
+            slot_hash: final_round.slot_hash,
+        });
+    }
+
+    // Determine winner
+    if let Some(rng) = final_round.rng() {
+        let winning_square = final_round.winning_square(rng);
+        log::info!("🎯 Winning square: {}", winning_square);
+        martingale_state.lock().unwrap().state_mut().last_winning_square = Some(winning_square as u8);
+
+        // Check if we won: trust the on-chain Miner.deployed over the locally remembered blocks
+        // (falls back to the local list in dry-run mode or if the account can't be read)
+        let expected_total = bet_per_block * block_indices.len() as u64;
+        let miner_after = match ore_client.get_miner_for_round(&signer.pubkey(), round_id).await {
+            Ok(miner) => miner,
+            Err(e) => {
+                log::warn!("⚠️ Failed to fetch miner for win verification: {}", e);
+                None
+            }
+        };
+        let won = match miner_after.as_ref().and_then(|miner| miner.won_round(round_id, winning_square)) {
+            Some(won) => {
+                let deployed_total = miner_after.as_ref().map_or(0, |miner| miner.total_deployed());
+                if deployed_total != expected_total {
+                    log::warn!("⚠️ On-chain deployment {:.6} SOL differs from intended bet {:.6} SOL",
+                        deployed_total as f64 / 1e9,
+                        expected_total as f64 / 1e9);
                 }
-                Err(e) => {
-                    log::error!("❌ Failed to place checkpoint + bet: {}", e);
-                    return Err(e);
+                if won != block_indices.contains(&(winning_square as u8)) {
+                    log::warn!("⚠️ On-chain blocks differ from intended blocks {:?}, using on-chain result", block_indices);
                 }
+                won
             }
-        } else {
-            // Already checkpointed - just deploy
-            log::info!("✅ Miner already checkpointed, sending Deploy only...");
-            log::info!("📤 Sending Deploy transaction...");
-            match executor.execute_bet(signer, round_id, &blocks, bet_per_block).await {
-                Ok(signature) => {
-                    log::info!("✅ Bet placed successfully!");
-                    log::info!("   Signature: {}", signature);
-                    martingale_state.lock().unwrap().record_bet(total_bet);
-                }
-                Err(e) => {
-                    log::error!("❌ Failed to place bet: {}", e);
-                    return Err(e);
+            None => {
+                if !config.dry_run {
+                    log::warn!("⚠️ Miner account not on round #{}, using locally remembered blocks", round_id);
                 }
+                block_indices.contains(&(winning_square as u8))
             }
+        };
+
+        {
+            let mut strategy = martingale_state.lock().unwrap();
+            let state = strategy.state_mut();
+            state.square_stats.record(&block_indices, winning_square as u8, won);
+            // A win's earnings are added once the reward task reads them
+            state.round_history.push(RoundEntry {
+                round_id,
+                winning_square: winning_square as u8,
+                won,
+                net: -((bet_per_block * block_indices.len() as u64 + bet_fee) as i64),
+            });
         }
-    } else {
-        // No miner account yet (first bet) - just deploy
-        log::info!("ℹ️ No miner account found (first bet), sending Deploy only...");
-        log::info!("📤 Sending Deploy transaction...");
-        match executor.execute_bet(signer, round_id, &blocks, bet_per_block).await {
-            Ok(signature) => {
-                log::info!("✅ Bet placed successfully!");
-                log::info!("   Signature: {}", signature);
-                martingale_state.lock().unwrap().record_bet(total_bet);
-            }
-            Err(e) => {
-                log::error!("❌ Failed to place bet: {}", e);
-                return Err(e);
-            }
-        }
-    }
 
-    // Wait for round to complete (max 2 minutes)
-    log::debug!("⏳ Waiting for round #{} to complete...", round_id);
-    let max_wait_time = Duration::from_secs(ROUND_COMPLETION_TIMEOUT_SECS);
-    let start_time = std::time::Instant::now();
+        if won {
+            log::info!(event = "win", round_id, winning_square, bet_lamports = bet_per_block; "✅ WE WON!");
 
-    loop {
-        tokio::time::sleep(Duration::from_secs(ROUND_COMPLETION_POLL_INTERVAL_SECS)).await;
+            // Get cycle bet and fee totals before resetting martingale state
+            let (cycle_bet_total, cycle_fees) = {
+                let strategy = martingale_state.lock().unwrap();
+                let state = strategy.state();
+                (state.current_cycle_bet_lamports, state.current_cycle_fees_lamports)
+            };
 
-        // Check timeout
-        if start_time.elapsed() > max_wait_time {
-            log::error!("⏰ Timeout waiting for round to complete ({} seconds)", ROUND_COMPLETION_TIMEOUT_SECS);
-            anyhow::bail!("Round completion timeout");
-        }
+            // Apply the win to the strategy immediately (e.g. martingale goes back to base bet)
+            martingale_state.lock().unwrap().on_win(&config.martingale);
 
-        // Check round status with retry on RPC error
-        match ore_client.get_board().await {
-            Ok(board_check) => {
-                if ore_client.is_round_complete(&board_check).await.unwrap_or(false) {
-                    log::debug!("🏁 Round #{} completed!", round_id);
-                    break;
-                }
+            // The top miner's ORE bonus lands in the same rewards as the win, so it's already in the
+            // ORE delta; it's called out separately in the notification and stats
+            let top_miner_reward = (final_round.top_miner == signer.pubkey()).then_some(final_round.top_miner_reward);
+            if let Some(reward) = top_miner_reward {
+                log::info!("🏆 Top miner of round #{}: {:.6} ORE bonus", round_id, reward as f64 / 1e11);
             }
-            Err(e) => {
-                log::warn!("⚠️ RPC error checking round status: {}. Retrying...", e);
-                continue;
+            if final_round.motherlode > 0 {
+                log::info!("💎 Motherlode hit: {:.6} ORE split among the winning square", final_round.motherlode as f64 / 1e11);
             }
-        }
-    }
-
-    // Get final round results with retry for RNG
-    log::debug!("📊 Fetching final round results...");
-    let mut final_round = ore_client.get_round(round_id).await?;
-    let mut rng_attempts = 0;
-
-    // Retry if RNG not available (slot_hash might not be ready immediately)
-    while final_round.rng().is_none() && rng_attempts < MAX_RNG_ATTEMPTS {
-        rng_attempts += 1;
-        log::debug!("⏳ RNG not available yet, retrying ({}/{})...", rng_attempts, MAX_RNG_ATTEMPTS);
-        tokio::time::sleep(Duration::from_secs(RNG_RETRY_INTERVAL_SECS)).await;
-        final_round = ore_client.get_round(round_id).await?;
-    }
-
-    // Determine winner
-    if let Some(rng) = final_round.rng() {
-        let winning_square = final_round.winning_square(rng);
-        log::info!("🎯 Winning square: {}", winning_square);
-
-        // Check if we won
-        let won = block_indices.contains(&(winning_square as u8));
-
-        if won {
-            log::info!("✅ WE WON!");
-
-            // Get cycle bet total before resetting martingale state
-            let cycle_bet_total = {
-                let state = martingale_state.lock().unwrap();
-                state.current_cycle_bet_lamports
-            };
-
-            // Reset martingale state immediately (won, so back to base bet)
-            martingale_state.lock().unwrap().reset_after_win(&config.martingale);
 
             // Clone all necessary values for the async task
             let subscription_clone = subscription.clone();
             let ore_client_clone = ore_client.clone();
-            let discord_clone = discord.clone();
+            let notifier_clone = notifier.clone();
             let executor_clone = executor.clone();
             let signer_pubkey = signer.pubkey();
             let config_clone = config.clone();
             let final_round_deployed = final_round.deployed[winning_square];
             let bet_per_block_clone = bet_per_block;
-            let private_key_clone = config.private_key.clone();
-            let martingale_state_clone = Arc::clone(&martingale_state);
-            let discord_stats_clone = discord.clone();
+            let bet_signature_clone = bet_signature.clone();
+            let ledger_clone = ledger.cloned();
+            let history_clone = history.cloned();
+            let block_indices_clone = block_indices.clone();
+            let martingale_state_clone = Arc::clone(martingale_state);
+            let notifier_stats_clone = notifier.clone();
             let config_stats_clone = config.clone();
+            let all_wallets_clone = all_wallets.cloned();
 
             // Process rewards fetch and notifications asynchronously (non-blocking)
-            tokio::spawn(async move {
-                // ore-app pattern: Try WebSocket first (fast), fallback to RPC
-                log::debug!("⏳ Waiting for rewards update...");
-                let (mut rewards_sol_after, mut rewards_ore_after) = if let Some(miner) = subscription_clone
-                    .wait_for_wss_update(rewards_sol_before, Duration::from_secs(WSS_UPDATE_TIMEOUT_SECS))
-                    .await
-                {
-                    log::debug!("✅ Rewards updated via WebSocket! {:.6} → {:.6} SOL",
-                        rewards_sol_before as f64 / 1e9,
-                        miner.rewards_sol as f64 / 1e9);
-                    (miner.rewards_sol, miner.rewards_ore)
+            reward_tasks.spawn(async move {
+                let (sol_earned_actual, ore_earned_actual) = if config_clone.dry_run {
+                    // Bet never landed, so the miner account won't change: estimate from the round
+                    let estimate = final_round.estimate_sol_reward(winning_square, bet_per_block_clone);
+                    log::info!("🧪 DRY RUN: estimated SOL reward: {:.6} SOL", estimate as f64 / 1e9);
+                    (estimate, 0)
                 } else {
-                    // WebSocket didn't update quickly, fetch via RPC
-                    log::debug!("📡 WebSocket timeout, fetching via RPC...");
-                    if let Ok(Some(miner)) = ore_client_clone.get_miner(&signer_pubkey).await {
-                        log::debug!("✅ Rewards fetched via RPC! {:.6} → {:.6} SOL",
-                            rewards_sol_before as f64 / 1e9,
-                            miner.rewards_sol as f64 / 1e9);
-                        (miner.rewards_sol, miner.rewards_ore)
-                    } else {
-                        log::warn!("⚠️ Failed to fetch miner account");
-                        (0, 0)
-                    }
+                    let (sol_earned, ore_earned, rewards_sol_after) = wait_for_rewards(
+                        &subscription_clone,
+                        &ore_client_clone,
+                        &signer_pubkey,
+                        rewards_sol_before,
+                        rewards_ore_before,
+                    ).await;
+                    log::info!("💰 Actual SOL earned (from protocol): {:.6} SOL", sol_earned as f64 / 1e9);
+                    log::info!("📊 Total accumulated rewards: {:.6} SOL", rewards_sol_after as f64 / 1e9);
+                    (sol_earned, ore_earned)
                 };
 
-                // Calculate actual rewards earned this round
-                let mut sol_earned_actual = rewards_sol_after.saturating_sub(rewards_sol_before);
-                let mut ore_earned_actual = rewards_ore_after.saturating_sub(rewards_ore_before);
-
-                // If rewards haven't updated yet (equal or less than before), retry up to 10 times
-                let mut retry_count = 0;
-                while rewards_sol_after <= rewards_sol_before && retry_count < MAX_REWARDS_RETRIES {
-                    retry_count += 1;
-                    log::debug!("⚠️ Rewards not updated yet (before: {:.6}, after: {:.6}), retrying {}/{}...",
-                        rewards_sol_before as f64 / 1e9,
-                        rewards_sol_after as f64 / 1e9,
-                        retry_count,
-                        MAX_REWARDS_RETRIES);
-                    tokio::time::sleep(Duration::from_secs(REWARDS_RETRY_INTERVAL_SECS)).await;
-
-                    if let Ok(Some(miner)) = ore_client_clone.get_miner(&signer_pubkey).await {
-                        rewards_sol_after = miner.rewards_sol;
-                        rewards_ore_after = miner.rewards_ore;
-                        sol_earned_actual = rewards_sol_after.saturating_sub(rewards_sol_before);
-                        ore_earned_actual = rewards_ore_after.saturating_sub(rewards_ore_before);
-
-                        if rewards_sol_after > rewards_sol_before {
-                            log::debug!("✅ Rewards updated after {} retries: {:.6} SOL, {:.6} ORE",
-                                retry_count,
-                                sol_earned_actual as f64 / 1e9,
-                                ore_earned_actual as f64 / 1e11);
-                            break;
-                        }
-                    }
-                }
-
-                if rewards_sol_after <= rewards_sol_before {
-                    log::warn!("⚠️ Rewards still not updated after {} retries (before: {:.6}, after: {:.6})",
-                        retry_count,
-                        rewards_sol_before as f64 / 1e9,
-                        rewards_sol_after as f64 / 1e9);
-                }
-
-                log::info!("💰 Actual SOL earned (from protocol): {:.6} SOL", sol_earned_actual as f64 / 1e9);
-                log::info!("📊 Total accumulated rewards: {:.6} SOL", rewards_sol_after as f64 / 1e9);
                 log::info!("📊 Our bet: {:.6} SOL / Total on square: {:.6} SOL",
                     bet_per_block_clone as f64 / 1e9,
                     final_round_deployed as f64 / 1e9);
 
                 // Check accumulated rewards for auto-claim
-                let accumulated_rewards = if let Ok(Some(miner)) = ore_client_clone.get_miner(&signer_pubkey).await {
-                    miner.rewards_sol
+                let (accumulated_rewards, accumulated_ore) = if let Ok(Some(miner)) = ore_client_clone.get_miner(&signer_pubkey).await {
+                    (miner.rewards_sol, miner.rewards_ore)
                 } else {
-                    0
+                    (0, 0)
                 };
 
-                // Auto-claim SOL if threshold reached
+                // Auto-claim SOL if threshold reached (never in dry-run mode)
                 let claim_threshold_lamports = config_clone.monitoring.auto_claim_sol_threshold_lamports();
-                if accumulated_rewards >= claim_threshold_lamports {
+                if !config_clone.dry_run && accumulated_rewards >= claim_threshold_lamports {
                     log::info!("💰 SOL rewards reached threshold: {:.6} SOL >= {:.6} SOL",
                         accumulated_rewards as f64 / 1e9,
                         config_clone.monitoring.auto_claim_sol_threshold);
                     log::info!("📤 Executing claim SOL transaction...");
 
                     // Load keypair from private key
-                    use crate::keypair::load_keypair;
-                    match load_keypair(&private_key_clone) {
+                    match load_signer(&config_clone) {
                         Ok(keypair) => {
                             match executor_clone.execute_claim_sol(keypair).await {
                                 Ok(signature) => {
-                                    log::info!("✅ SOL claimed successfully!");
+                                    log::info!(event = "claim", asset = "SOL", amount = accumulated_rewards; "✅ SOL claimed successfully!");
                                     log::info!("   Signature: {}", signature);
                                     log::info!("   Amount: {:.6} SOL", accumulated_rewards as f64 / 1e9);
 
                                     // Get new balance
                                     let new_balance = ore_client_clone.solana.get_balance(&signer_pubkey).await.unwrap_or(0);
 
-                                    if let Err(e) = discord_clone.notify_claim_sol(accumulated_rewards, new_balance).await {
-                                        log::error!("Failed to send Discord claim notification: {}", e);
+                                    if let Err(e) = notifier_clone.notify_claim_sol(accumulated_rewards, new_balance, Some(&signature)).await {
+                                        log::error!("Failed to send claim notification: {}", e);
                                     }
                                 }
                                 Err(e) => {
                                     log::error!("❌ Failed to claim SOL: {}", e);
-                                    if let Err(e) = discord_clone.notify_error(&format!("Failed to claim SOL: {}", e)).await {
-                                        log::error!("Failed to send Discord error notification: {}", e);
+                                    if let Err(e) = notifier_clone.notify_error(&format!("Failed to claim SOL: {}", e)).await {
+                                        log::error!("Failed to send error notification: {}", e);
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            log::error!("❌ Failed to load keypair for claim: {}", e);
+                        }
+                    }
+                }
+
+                // Auto-claim ORE if configured and threshold reached (never in dry-run mode)
+                let ore_threshold = config_clone.monitoring.auto_claim_ore_threshold_units();
+                if let Some(ore_threshold) = ore_threshold.filter(|&t| !config_clone.dry_run && accumulated_ore >= t) {
+                    log::info!("🪙 ORE rewards reached threshold: {:.6} ORE >= {:.6} ORE",
+                        accumulated_ore as f64 / 1e11,
+                        ore_threshold as f64 / 1e11);
+                    log::info!("📤 Executing claim ORE transaction...");
+
+                    match load_signer(&config_clone) {
+                        Ok(keypair) => {
+                            match executor_clone.execute_claim_ore(keypair).await {
+                                Ok(signature) => {
+                                    log::info!(event = "claim", asset = "ORE", amount = accumulated_ore; "✅ ORE claimed successfully!");
+                                    log::info!("   Signature: {}", signature);
+                                    log::info!("   Amount: {:.6} ORE", accumulated_ore as f64 / 1e11);
+
+                                    let token_balance = ore_client_clone.get_ore_token_balance(&signer_pubkey).await
+                                        .ok()
+                                        .flatten()
+                                        .unwrap_or(0);
+
+                                    if let Err(e) = notifier_clone.notify_claim_ore(accumulated_ore, token_balance, Some(&signature)).await {
+                                        log::error!("Failed to send claim notification: {}", e);
+                                    }
+                                }
+                                Err(e) => {
+                                    log::error!("❌ Failed to claim ORE: {}", e);
+                                    if let Err(e) = notifier_clone.notify_error(&format!("Failed to claim ORE: {}", e)).await {
+                                        log::error!("Failed to send error notification: {}", e);
                                     }
                                 }
                             }
@@ -540,124 +1220,789 @@ async fn run_betting_round(
                     }
                 }
 
+                // Move profits above the bankroll requirement to the cold wallet (never in dry-run mode)
+                sweep_profits(&config_clone, &executor_clone, &ore_client_clone.solana, &notifier_clone, &martingale_state_clone, &signer_pubkey).await;
+
                 // Send win notification
-                // Calculate net profit (earned SOL - all bets in this martingale cycle)
+                // Calculate net profit (earned SOL - all bets and fees in this martingale cycle)
                 // This includes the current bet and all previous losing bets in the cycle
-                let net_profit = (sol_earned_actual as i64) - (cycle_bet_total as i64);
+                let net_profit = (sol_earned_actual as i64) - (cycle_bet_total as i64) - (cycle_fees as i64);
 
                 log::info!("📊 Martingale cycle summary:");
                 log::info!("   Total bet in cycle: {:.6} SOL", cycle_bet_total as f64 / 1e9);
+                log::info!("   Fees in cycle: {:.6} SOL", cycle_fees as f64 / 1e9);
                 log::info!("   SOL earned: {:.6} SOL", sol_earned_actual as f64 / 1e9);
                 log::info!("   Net profit: {:.6} SOL", net_profit as f64 / 1e9);
 
                 // Update martingale state with actual earnings
-                martingale_state_clone.lock().unwrap().update_earnings(ore_earned_actual, sol_earned_actual);
+                {
+                    let mut strategy = martingale_state_clone.lock().unwrap();
+                    strategy.update_earnings(ore_earned_actual, sol_earned_actual);
+                    let state = strategy.state_mut();
+                    state.round_history.add_earnings(round_id, sol_earned_actual);
+                    if let Some(reward) = top_miner_reward {
+                        state.top_miner_wins += 1;
+                        state.top_miner_ore += reward;
+                    }
+                }
+
+                if let Some(ledger) = &ledger_clone {
+                    ledger.record(&LedgerEntry {
+                        round_id,
+                        blocks: &block_indices_clone,
+                        bet_per_block: bet_per_block_clone,
+                        winning_square: winning_square as u8,
+                        won: true,
+                        sol_earned: sol_earned_actual,
+                        ore_earned: ore_earned_actual,
+                        consecutive_losses: 0,
+                    });
+                }
 
-                if let Err(e) = discord_clone.notify_win(
+                if let Some(history) = &history_clone {
+                    history.record_outcome(OutcomeRecord {
+                        round_id,
+                        won: true,
+                        sol_earned: sol_earned_actual,
+                        ore_earned: ore_earned_actual,
+                        total_bet: bet_per_block_clone * block_indices_clone.len() as u64,
+                        fees: bet_fee,
+                    });
+                }
+
+                notifier_clone.record_round(RoundResult {
                     round_id,
+                    won: true,
+                    winning_square: winning_square as u8,
+                    total_bet: bet_per_block_clone * block_indices_clone.len() as u64,
+                    sol_earned: sol_earned_actual,
+                });
+
+                if !notifier_clone.should_notify_win(net_profit) {
+                    log::debug!("Summary mode: win notification below threshold, suppressed");
+                } else if let Err(e) = notifier_clone.notify_win(
+                    &final_round,
+                    &block_indices_clone,
                     winning_square as u8,
                     ore_earned_actual,
                     sol_earned_actual,
                     net_profit,
+                    top_miner_reward,
+                    bet_signature_clone.as_deref(),
                 ).await {
-                    log::error!("Failed to send Discord win notification: {}", e);
+                    log::error!("Failed to send win notification: {}", e);
                 }
 
                 // Send stats notification if interval reached (after earnings update)
-                let stats_interval = config_stats_clone.discord.stats_notification_interval;
-                let (total_rounds, win_count, loss_count, win_rate, total_earned_ore, net_profit) = {
-                    let state = martingale_state_clone.lock().unwrap();
-                    let total_rounds = state.win_count + state.loss_count;
-                    (
-                        total_rounds,
-                        state.win_count,
-                        state.loss_count,
-                        state.win_rate(),
-                        state.total_earned_ore,
-                        state.net_profit_sol(),
-                    )
-                };
-
-                if total_rounds % stats_interval == 0 && total_rounds > 0 {
-                    if let Err(e) = discord_stats_clone.notify_stats(
-                        total_rounds,
-                        win_count,
-                        loss_count,
-                        win_rate,
-                        total_earned_ore,
-                        net_profit,
-                    ).await {
-                        log::error!("Failed to send stats notification: {}", e);
-                    }
-                }
+                let state = martingale_state_clone.lock().unwrap().state().clone();
+                send_periodic_stats(&state, config_stats_clone.discord.stats_notification_interval,
+                    &notifier_stats_clone, all_wallets_clone.as_ref()).await;
             });
         } else {
-            log::warn!("❌ Lost. Winning square was {}, we bet on {:?}", winning_square, block_indices);
-
-            let (should_continue, should_warn) = {
-                let mut state = martingale_state.lock().unwrap();
-                state.on_loss(&config.martingale)
+            log::warn!(event = "loss", round_id, winning_square, bet_lamports = bet_per_block;
+                "❌ Lost. Winning square was {}, we bet on {:?}", winning_square, block_indices);
+
+            let (should_continue, should_warn, ceiling_hit) = {
+                let mut strategy = martingale_state.lock().unwrap();
+                let was_capped = strategy.state().uncapped_bet_per_block.is_some();
+                let (should_continue, should_warn) = strategy.on_loss(&config.martingale);
+                // Only the loss that first runs into the cap; later ones stay flat at it
+                let ceiling_hit = !was_capped && strategy.state().uncapped_bet_per_block.is_some();
+                (should_continue, should_warn, ceiling_hit)
             };
 
-            let (consecutive_losses, current_bet_per_block) = {
-                let state = martingale_state.lock().unwrap();
-                (state.consecutive_losses, state.current_bet_per_block)
+            let (consecutive_losses, current_bet_per_block, theoretical_bet) = {
+                let strategy = martingale_state.lock().unwrap();
+                (strategy.state().consecutive_losses, strategy.next_bet(), strategy.state().theoretical_bet())
             };
 
-            if let Err(e) = discord.notify_loss(
+            if let Some(ledger) = ledger {
+                ledger.record(&LedgerEntry {
+                    round_id,
+                    blocks: &block_indices,
+                    bet_per_block,
+                    winning_square: winning_square as u8,
+                    won: false,
+                    sol_earned: 0,
+                    ore_earned: 0,
+                    consecutive_losses,
+                });
+            }
+
+            if let Some(history) = history {
+                history.record_outcome(OutcomeRecord {
+                    round_id,
+                    won: false,
+                    sol_earned: 0,
+                    ore_earned: 0,
+                    total_bet: bet_per_block * block_indices.len() as u64,
+                    fees: bet_fee,
+                });
+            }
+
+            notifier.record_round(RoundResult {
                 round_id,
+                won: false,
+                winning_square: winning_square as u8,
+                total_bet: bet_per_block * block_indices.len() as u64,
+                sol_earned: 0,
+            });
+
+            if notifier.is_summary_mode() {
+                log::debug!("Summary mode: loss notification suppressed");
+            } else if let Err(e) = notifier.notify_loss(
+                &final_round,
+                &block_indices,
                 winning_square as u8,
                 consecutive_losses,
                 current_bet_per_block,
+                bet_signature.as_deref(),
             ).await {
-                log::error!("Failed to send Discord notification: {}", e);
+                log::error!("Failed to send notification: {}", e);
+            }
+
+            if ceiling_hit {
+                if let Err(e) = notifier.notify_bet_ceiling(consecutive_losses, current_bet_per_block, theoretical_bet).await {
+                    log::error!("Failed to send notification: {}", e);
+                }
             }
 
             if should_warn {
-                if let Err(e) = discord.notify_warning(
+                if let Err(e) = notifier.notify_warning(
                     consecutive_losses,
                     config.martingale.max_consecutive_losses,
                     current_bet_per_block,
+                    theoretical_bet,
                 ).await {
-                    log::error!("Failed to send Discord notification: {}", e);
+                    log::error!("Failed to send notification: {}", e);
                 }
             }
 
             // Send stats notification if interval reached (after loss)
-            let stats_interval = config.discord.stats_notification_interval;
-            let (total_rounds, win_count, loss_count, win_rate, total_earned_ore, net_profit) = {
-                let state = martingale_state.lock().unwrap();
-                let total_rounds = state.win_count + state.loss_count;
-                (
-                    total_rounds,
-                    state.win_count,
-                    state.loss_count,
-                    state.win_rate(),
-                    state.total_earned_ore,
-                    state.net_profit_sol(),
-                )
-            };
+            let state = martingale_state.lock().unwrap().state().clone();
+            send_periodic_stats(&state, config.discord.stats_notification_interval, notifier, all_wallets).await;
 
-            if total_rounds % stats_interval == 0 && total_rounds > 0 {
-                if let Err(e) = discord.notify_stats(
-                    total_rounds,
-                    win_count,
-                    loss_count,
-                    win_rate,
-                    total_earned_ore,
-                    net_profit,
-                ).await {
-                    log::error!("Failed to send stats notification: {}", e);
-                }
+            if !should_continue {
+                log::warn!("⚠️ Max consecutive losses reached.");
+                return Ok(RoundOutcome::MaxLossesReached);
             }
 
-            if !should_continue {
-                return Ok(false);
+            // Session stop-loss: independent of the consecutive-loss counter, catches a slow bleed
+            // (checked on losses only, since win earnings are credited asynchronously)
+            if let Some(limit) = config.monitoring.max_session_loss_lamports() {
+                let net_profit = martingale_state.lock().unwrap().state().net_profit_sol();
+                if net_profit <= -(limit as i64) {
+                    log::error!("🛑 Session loss limit reached: net {:+.6} SOL (limit: {:.6} SOL)",
+                        net_profit as f64 / 1e9,
+                        limit as f64 / 1e9);
+                    if let Err(e) = notifier.notify_error(&format!(
+                        "Session loss limit reached: net {:+.6} SOL (limit: {:.6} SOL). Bot paused.",
+                        net_profit as f64 / 1e9,
+                        limit as f64 / 1e9
+                    )).await {
+                        log::error!("Failed to send notification: {}", e);
+                    }
+                    return Ok(RoundOutcome::Stop);
+                }
             }
         }
     } else {
         log::warn!("⚠️ Round RNG not available yet. Will try again next round.");
     }
 
-    Ok(true)
+    Ok(RoundOutcome::Continue)
+}
+
+/// Select blocks, size the bet and send the Deploy (with a Checkpoint if needed)
+/// Returns (block_indices, bet_per_block, signature, fee), or None if the round was skipped
+#[allow(clippy::too_many_arguments)]
+async fn place_bet(
+    ore_client: &OreClient,
+    executor: &TransactionExecutor,
+    martingale_state: &Arc<Mutex<Box<dyn BettingStrategy + Send>>>,
+    notifier: &Notifications,
+    signer: &dyn Signer,
+    config: &config::BotConfig,
+    selector: &dyn BlockSelector,
+    round: &Round,
+    round_id: u64,
+    round_deadline: std::time::Instant,
+    history: Option<&History>,
+) -> Result<Option<(Vec<u8>, u64, Option<String>, u64)>> {
+    if martingale_state.lock().unwrap().state().ev_skipped_round == Some(round_id) {
+        log::debug!("⏭️ Round #{} already skipped by the EV guard", round_id);
+        return Ok(None);
+    }
+
+    // Select blocks to bet on (the count can shrink as losses mount, see blocks_per_loss_level)
+    let (previous_winner, block_count) = {
+        let strategy = martingale_state.lock().unwrap();
+        (strategy.state().last_winning_square, strategy.state().blocks_per_bet(&config.martingale))
+    };
+    let mut blocks = {
+        let mut rng = rand::rng();
+        let mut ctx = SelectionContext { round, previous_winner, rng: &mut rng };
+        selector.select(block_count, &mut ctx)
+    };
+    if blocks.is_empty() {
+        log::warn!("⚠️ Block selector returned no squares, falling back to random selection");
+        blocks = grid::select_blocks(block_count);
+    }
+    let block_indices: Vec<u8> = blocks.iter().map(|b| b.index).collect();
+
+    // Snapshot the balance before sizing the bet (percent mode sizes each cycle from it)
+    let current_balance = ore_client.solana.get_balance(&signer.pubkey()).await?;
+
+    let (bet_per_block, consecutive_losses) = {
+        let mut strategy = martingale_state.lock().unwrap();
+        strategy.state_mut().update_balance(&config.martingale, current_balance);
+        (strategy.next_bet(), strategy.state().consecutive_losses)
+    };
+    let total_bet = bet_per_block * (blocks.len() as u64);
+
+    let ratio = ev::ev_ratio(&round.deployed, &block_indices, bet_per_block);
+    let expected_sol = ev::estimate_ev(round, &block_indices, bet_per_block, config.martingale.ore_price_sol) / 1e9;
+    log::info!("📐 Round #{} EV: {:+.6} SOL (ratio {:.3}), pot: {:.6} SOL, motherlode: {:.2} ORE",
+        round_id,
+        expected_sol,
+        ratio,
+        round.total_deployed as f64 / 1e9,
+        round.motherlode as f64 / 1e11);
+
+    // EV guard: skip the round (no bet, no loss counted) if the squares are too crowded
+    let skip_reason = match (config.martingale.min_ev_ratio, config.martingale.min_ev_threshold) {
+        (Some(min_ratio), _) if ratio < min_ratio => Some(format!(
+            "Expected return {:.3} per SOL staked is below min_ev_ratio {:.3}", ratio, min_ratio)),
+        (_, Some(min_sol)) if expected_sol < min_sol => Some(format!(
+            "Expected net result {:+.6} SOL is below min_ev_threshold {:+.6} SOL", expected_sol, min_sol)),
+        _ => None,
+    };
+    if let Some(reason) = skip_reason {
+        log::info!("⏭️ Skipping round #{} on blocks {:?}: {}", round_id, block_indices, reason);
+        martingale_state.lock().unwrap().state_mut().ev_skipped_round = Some(round_id);
+        if config.martingale.notify_ev_skips {
+            if let Err(e) = notifier.notify_ev_skip(round_id, &reason).await {
+                log::error!("Failed to send notification: {}", e);
+            }
+        }
+        return Ok(None);
+    }
+
+    // Check if we have enough balance for this bet
+    // Reserve some SOL for transaction fees and rent-exempt minimum
+    let required_balance = total_bet;
+    
+    if current_balance < required_balance {
+        log::error!("⚠️ Insufficient balance for bet!");
+        log::error!("   Current: {:.6} SOL", current_balance as f64 / 1e9);
+        log::error!("   Required: {:.6} SOL (bet) = {:.6} SOL",
+            total_bet as f64 / 1e9,
+            required_balance as f64 / 1e9);
+        
+        if let Err(e) = notifier.notify_error(&format!(
+            "Insufficient balance: {:.6} SOL < {:.6} SOL required",
+            current_balance as f64 / 1e9,
+            required_balance as f64 / 1e9
+        )).await {
+            log::error!("Failed to send notification: {}", e);
+        }
+        
+        anyhow::bail!("Insufficient balance for bet");
+    }
+
+    log::info!("🎲 Betting on blocks: {:?}", block_indices);
+    log::info!("💰 Bet: {:.6} SOL per block, total: {:.6} SOL",
+        bet_per_block as f64 / 1e9,
+        total_bet as f64 / 1e9
+    );
+
+    let priority_fee = executor.refresh_priority_fee(&signer.pubkey(), round_id).await;
+
+    // Check if miner needs checkpoint and execute in single transaction
+    let signature = if let Some(miner) = ore_client.get_miner(&signer.pubkey()).await? {
+        if miner.checkpoint_id != miner.round_id {
+            // Checkpoint needed - combine with deploy in single transaction
+            log::info!("📤 Sending combined Checkpoint + Deploy transaction...");
+            match executor.execute_checkpoint_and_bet(
+                signer,
+                miner.round_id,
+                round_id,
+                &blocks,
+                bet_per_block,
+                Some(round_deadline),
+            ).await {
+                Ok(signature) => {
+                    log::info!(event = "bet", round_id, bet_lamports = bet_per_block, total_lamports = total_bet,
+                        blocks:? = block_indices; "✅ Checkpoint + Bet placed successfully!");
+                    log::info!("   Signature: {}", signature.as_deref().unwrap_or("unknown"));
+                    martingale_state.lock().unwrap().record_bet(round_id, total_bet);
+                    signature
+                }
+                Err(e) => {
+                    log::error!("❌ Failed to place checkpoint + bet: {}", e);
+                    return Err(e);
+                }
+            }
+        } else {
+            // Already checkpointed - just deploy
+            log::info!("✅ Miner already checkpointed, sending Deploy only...");
+            log::info!("📤 Sending Deploy transaction...");
+            match executor.execute_bet(signer, round_id, &blocks, bet_per_block, Some(round_deadline)).await {
+                Ok(signature) => {
+                    log::info!(event = "bet", round_id, bet_lamports = bet_per_block, total_lamports = total_bet,
+                        blocks:? = block_indices; "✅ Bet placed successfully!");
+                    log::info!("   Signature: {}", signature.as_deref().unwrap_or("unknown"));
+                    martingale_state.lock().unwrap().record_bet(round_id, total_bet);
+                    signature
+                }
+                Err(e) => {
+                    log::error!("❌ Failed to place bet: {}", e);
+                    return Err(e);
+                }
+            }
+        }
+    } else {
+        // No miner account yet (first bet) - just deploy
+        log::info!("ℹ️ No miner account found (first bet), sending Deploy only...");
+        log::info!("📤 Sending Deploy transaction...");
+        match executor.execute_bet(signer, round_id, &blocks, bet_per_block, Some(round_deadline)).await {
+            Ok(signature) => {
+                log::info!(event = "bet", round_id, bet_lamports = bet_per_block, total_lamports = total_bet,
+                    blocks:? = block_indices; "✅ Bet placed successfully!");
+                log::info!("   Signature: {}", signature.as_deref().unwrap_or("unknown"));
+                martingale_state.lock().unwrap().record_bet(round_id, total_bet);
+                signature
+            }
+            Err(e) => {
+                log::error!("❌ Failed to place bet: {}", e);
+                return Err(e);
+            }
+        }
+    };
+
+    // Count the bet's fee in this cycle before the round settles
+    let fee = collect_fees(executor, martingale_state);
+
+    if let Some(history) = history {
+        history.record_bet(BetRecord {
+            round_id,
+            blocks: block_indices.clone(),
+            bet_per_block,
+            fee,
+            signature: signature.clone(),
+            consecutive_losses,
+        });
+    }
+
+    // Send bet notification (summary mode only reports settled rounds)
+    if notifier.is_summary_mode() {
+        log::debug!("Summary mode: bet notification suppressed");
+    } else if let Err(e) = notifier.notify_bet(
+        round_id,
+        &block_indices,
+        bet_per_block,
+        total_bet,
+        consecutive_losses,
+        priority_fee,
+        signature.as_deref(),
+    ).await {
+        log::error!("Failed to send notification: {}", e);
+    }
+
+    Ok(Some((block_indices, bet_per_block, signature, fee)))
+}
+
+/// Transfer the balance above the bankroll requirement plus `sweep_above_sol` to `sweep_to_address`
+///
+/// Skipped mid-cycle (consecutive losses > 0), when the ladder needs every lamport, and in dry-run mode.
+async fn sweep_profits(
+    config: &config::BotConfig,
+    executor: &TransactionExecutor,
+    solana_client: &SolanaClient,
+    notifier: &Notifications,
+    martingale_state: &Arc<Mutex<Box<dyn BettingStrategy + Send>>>,
+    authority: &Pubkey,
+) {
+    let Some((destination, keep_above)) = config.monitoring.sweep() else {
+        return;
+    };
+
+    let consecutive_losses = martingale_state.lock().unwrap().state().consecutive_losses;
+    if consecutive_losses > 0 {
+        log::debug!("🏦 Cycle in progress ({} losses), not sweeping", consecutive_losses);
+        return;
+    }
+
+    let balance = match solana_client.get_balance(authority).await {
+        Ok(balance) => balance,
+        Err(e) => {
+            log::warn!("⚠️ Warning: Failed to fetch balance for the sweep: {}", e);
+            return;
+        }
+    };
+
+    // Keep a full losing streak, the configured cushion and the transfer's own fee
+    let fee_per_bet = estimated_bet_fee(&config.transaction.priority_fee);
+    let base_bet = config.martingale.base_bet_for_balance(Some(balance));
+    let required = bankroll_requirement(&config.martingale, base_bet, fee_per_bet, config.monitoring.min_balance_lamports());
+    let keep = required.total().saturating_add(keep_above).saturating_add(fee_per_bet);
+    let amount = balance.saturating_sub(keep);
+    if amount == 0 {
+        log::debug!("🏦 Balance {:.6} SOL is within the {:.6} SOL kept, nothing to sweep",
+            balance as f64 / 1e9,
+            keep as f64 / 1e9);
+        return;
+    }
+
+    if config.dry_run {
+        log::info!("🧪 DRY RUN: would sweep {:.6} SOL to {}", amount as f64 / 1e9, destination);
+        return;
+    }
+
+    log::info!("🏦 Balance {:.6} SOL exceeds the {:.6} SOL kept, sweeping {:.6} SOL to {}",
+        balance as f64 / 1e9,
+        keep as f64 / 1e9,
+        amount as f64 / 1e9,
+        destination);
+
+    let keypair = match load_signer(config) {
+        Ok(keypair) => keypair,
+        Err(e) => {
+            log::error!("❌ Failed to load keypair for sweep: {}", e);
+            return;
+        }
+    };
+
+    match executor.execute_transfer(keypair, destination, amount).await {
+        Ok(signature) => {
+            log::info!(event = "sweep", amount = amount; "✅ Swept {:.6} SOL to cold wallet", amount as f64 / 1e9);
+            log::info!("   Signature: {}", signature);
+
+            let new_balance = solana_client.get_balance(authority).await.unwrap_or(0);
+            if let Err(e) = notifier.notify_sweep(amount, &destination.to_string(), new_balance, Some(&signature)).await {
+                log::error!("Failed to send sweep notification: {}", e);
+            }
+        }
+        Err(e) => {
+            log::error!("❌ Failed to sweep SOL: {}", e);
+            if let Err(e) = notifier.notify_error(&format!("Failed to sweep SOL: {}", e)).await {
+                log::error!("Failed to send error notification: {}", e);
+            }
+        }
+    }
+}
+
+/// Compare the bankroll a full `max_consecutive_losses` streak needs (stakes, fees, reserve)
+/// with the wallet balance, logging the ladder. Underfunded is an error unless `allow_underfunded`
+fn check_bankroll(config: &config::BotConfig, balance: u64) -> Result<()> {
+    let martingale = &config.martingale;
+    let base_bet = martingale.base_bet_for_balance(Some(balance));
+    let fee_per_bet = estimated_bet_fee(&config.transaction.priority_fee);
+    let required = bankroll_requirement(martingale, base_bet, fee_per_bet, config.monitoring.min_balance_lamports());
+
+    log::info!("🧮 Loss ladder:");
+    log::info!("   Loss  Blocks  Bet/block (SOL)  Stake (SOL)  Cumulative (SOL)");
+    let mut cumulative = 0u64;
+    for (losses, bet) in required.ladder.iter().enumerate() {
+        let blocks = martingale.blocks_for_losses(losses as u8);
+        let stake = bet.saturating_mul(blocks as u64);
+        cumulative = cumulative.saturating_add(stake).saturating_add(fee_per_bet);
+        log::info!("   {:>4}  {:>6}  {:>15.6}  {:>11.6}  {:>16.6}",
+            losses + 1,
+            blocks,
+            *bet as f64 / 1e9,
+            stake as f64 / 1e9,
+            cumulative as f64 / 1e9);
+    }
+    log::info!("🧮 Worst case: {:.6} SOL staked + ~{:.6} SOL fees + {:.6} SOL reserve = {:.6} SOL (balance: {:.6} SOL)",
+        required.stake as f64 / 1e9,
+        required.fees as f64 / 1e9,
+        required.reserve as f64 / 1e9,
+        required.total() as f64 / 1e9,
+        balance as f64 / 1e9);
+
+    if required.total() <= balance {
+        return Ok(());
+    }
+
+    let message = format!("Balance {:.6} SOL can't cover a full losing streak ({:.6} SOL needed over {} losses)",
+        balance as f64 / 1e9,
+        required.total() as f64 / 1e9,
+        martingale.max_consecutive_losses);
+    if !config.monitoring.allow_underfunded {
+        anyhow::bail!("{}. Lower base_bet_amount/multiplier/blocks_per_bet/max_consecutive_losses, top up, or set monitoring.allow_underfunded", message);
+    }
+    log::warn!("⚠️ {}: a long streak will fail mid-way (allow_underfunded is set)", message);
+    Ok(())
+}
+
+/// Log the winning square distribution with a uniformity check, alongside the stats report
+fn log_square_stats(state: &MartingaleState) {
+    let squares = &state.square_stats;
+    let Some(chi_square) = squares.chi_square() else {
+        return;
+    };
+    let top: Vec<String> = squares.top_winners(5).iter()
+        .map(|(square, wins)| format!("{}×{}", square, wins))
+        .collect();
+    log::info!("📐 Winning squares over {} rounds: top {} | χ² = {:.1} (24 df, > {:.1} looks non-uniform)",
+        squares.rounds(),
+        top.join(", "),
+        chi_square,
+        CHI_SQUARE_CRITICAL_24DF);
+    if chi_square > CHI_SQUARE_CRITICAL_24DF {
+        log::warn!("📐 Winning square distribution deviates from uniform (χ² = {:.1})", chi_square);
+    }
+}
+
+/// Move fees of landed transactions (bets, claims, failed attempts) from the executor into the state
+/// Returns the lamports moved
+fn collect_fees(executor: &TransactionExecutor, martingale_state: &Arc<Mutex<Box<dyn BettingStrategy + Send>>>) -> u64 {
+    let fees = executor.take_fees_paid();
+    if fees > 0 {
+        log::debug!("💸 Fees paid: {} lamports", fees);
+        martingale_state.lock().unwrap().record_fees(fees);
+    }
+    fees
+}
+
+/// Send the daily digest at `hour`:00 UTC every day, then start counting a new period
+/// Counters live in the state file, so a digest missed while the bot was down rolls into the next one
+async fn daily_summary_task(
+    hour: u8,
+    martingale_state: Arc<Mutex<Box<dyn BettingStrategy + Send>>>,
+    notifier: Notifications,
+) {
+    loop {
+        let now = chrono::Utc::now().timestamp();
+        let next = next_utc_hour(now, hour);
+        tokio::time::sleep(Duration::from_secs((next - now) as u64)).await;
+
+        let (day, state) = {
+            let mut strategy = martingale_state.lock().unwrap();
+            let snapshot = (strategy.state().daily_stats.clone(), strategy.state().clone());
+            strategy.state_mut().daily_stats.reset(next);
+            strategy.persist();
+            snapshot
+        };
+
+        log::info!("📅 Daily summary: {} rounds ({}W/{}L), net {:+.6} SOL",
+            day.rounds,
+            day.wins,
+            day.losses,
+            day.net_lamports() as f64 / 1e9);
+        if let Err(e) = notifier.notify_daily_summary(&day, &state).await {
+            log::error!("Failed to send notification: {}", e);
+        }
+    }
+}
+
+/// Post a heartbeat every `period`
+/// The time since the last bet shows a wedged main loop even while the process is still up
+async fn heartbeat_task(
+    period: Duration,
+    solana_client: SolanaClient,
+    wallet: Pubkey,
+    martingale_state: Arc<Mutex<Box<dyn BettingStrategy + Send>>>,
+    subscription: MinerSubscription,
+    notifier: Notifications,
+) {
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    loop {
+        interval.tick().await;
+
+        let (round_id, consecutive_losses, last_bet_time, last_balance) = {
+            let strategy = martingale_state.lock().unwrap();
+            let state = strategy.state();
+            (state.current_round, state.consecutive_losses, state.last_bet_time, state.balance_lamports)
+        };
+        let balance = match solana_client.get_balance(&wallet).await {
+            Ok(balance) => Some(balance),
+            Err(e) => {
+                log::warn!("⚠️ Heartbeat: failed to fetch balance: {}", e);
+                last_balance
+            }
+        };
+        let ws_status = match subscription.health(Duration::from_secs(WS_STALE_AFTER_SECS)) {
+            SubscriptionHealth::Connected => "healthy",
+            SubscriptionHealth::Stale(_) => "stale",
+            SubscriptionHealth::Disconnected => "disconnected",
+        };
+        let last_bet_age = last_bet_time.map(|at| chrono::Utc::now().timestamp() - at);
+
+        log::debug!("💓 Heartbeat: round #{}, WSS {}", round_id, ws_status);
+        if let Err(e) = notifier.notify_heartbeat(round_id, balance, consecutive_losses, ws_status, last_bet_age).await {
+            log::error!("Failed to send notification: {}", e);
+        }
+    }
+}
+
+/// Bet this miner already has on `round_id`: (block_indices, bet_per_block, total_bet)
+fn existing_deployment(miner: &Miner, round_id: u64) -> Option<(Vec<u8>, u64, u64)> {
+    if miner.round_id != round_id {
+        return None;
+    }
+
+    let block_indices: Vec<u8> = (0..miner.deployed.len() as u8)
+        .filter(|&i| miner.deployed[i as usize] > 0)
+        .collect();
+    if block_indices.is_empty() {
+        return None;
+    }
+
+    let bet_per_block = miner.deployed.iter().copied().max().unwrap_or(0);
+    Some((block_indices, bet_per_block, miner.total_deployed()))
+}
+
+/// Board from the WebSocket stream, or from RPC when the stream has nothing newer than `board_stale_secs`
+async fn current_board(ore_client: &OreClient, boards: &BoardSubscription, config: &config::BotConfig) -> Result<Board> {
+    match boards.latest(Duration::from_secs(config.board_stale_secs)) {
+        Some(board) => Ok(board),
+        None => {
+            log::debug!("📡 Board stream stale or empty, fetching board over RPC");
+            ore_client.get_board().await
+        }
+    }
+}
+
+/// Wait until the round's end_slot has passed
+///
+/// Follows the WebSocket slot stream and confirms with a single RPC call once end_slot is reached.
+/// Falls back to polling the board over RPC while the slot stream is stale.
+async fn wait_for_round_end(
+    ore_client: &OreClient,
+    slots: &SlotSubscription,
+    boards: &BoardSubscription,
+    config: &config::BotConfig,
+    round_id: u64,
+) -> Result<()> {
+    let max_wait_time = Duration::from_secs(ROUND_COMPLETION_TIMEOUT_SECS);
+    let start_time = std::time::Instant::now();
+    let mut end_slot = current_board(ore_client, boards, config).await?.end_slot;
+    let mut last_rpc_poll: Option<std::time::Instant> = None;
+    let mut stale_logged = false;
+
+    loop {
+        tokio::time::sleep(Duration::from_millis(SLOT_WAIT_POLL_INTERVAL_MS)).await;
+
+        // Check timeout
+        if start_time.elapsed() > max_wait_time {
+            log::error!("⏰ Timeout waiting for round to complete ({} seconds)", ROUND_COMPLETION_TIMEOUT_SECS);
+            anyhow::bail!("Round completion timeout");
+        }
+
+        let stream = slots.current_slot().await;
+        let fresh = stream.filter(|(_, age)| *age < Duration::from_secs(SLOT_STREAM_STALE_SECS));
+
+        match fresh {
+            Some((slot, _)) if slot < end_slot.saturating_add(ROUND_END_SLOT_BUFFER) => {
+                stale_logged = false;
+                continue;
+            }
+            Some(_) => stale_logged = false,
+            None => {
+                if !stale_logged {
+                    match stream {
+                        Some((_, age)) => log::warn!("📡 Slot stream stale for {:.1}s, falling back to RPC polling", age.as_secs_f64()),
+                        None => log::warn!("📡 No slot updates yet, falling back to RPC polling"),
+                    }
+                    stale_logged = true;
+                }
+                if last_rpc_poll.is_some_and(|t| t.elapsed() < Duration::from_secs(ROUND_COMPLETION_POLL_INTERVAL_SECS)) {
+                    continue;
+                }
+                last_rpc_poll = Some(std::time::Instant::now());
+            }
+        }
+
+        // Confirm the round ended (end_slot may also have moved since the bet)
+        match current_board(ore_client, boards, config).await {
+            Ok(board_check) => {
+                if ore_client.is_round_complete(&board_check).await.unwrap_or(false) {
+                    log::debug!("🏁 Round #{} completed! (slot stream lag: {})",
+                        round_id,
+                        stream.map_or("n/a".to_string(), |(_, age)| format!("{}ms", age.as_millis())));
+                    return Ok(());
+                }
+                end_slot = board_check.end_slot;
+            }
+            Err(e) => {
+                log::warn!("⚠️ RPC error checking round status: {}. Retrying...", e);
+            }
+        }
+    }
+}
+
+/// Wait for the miner account to reflect a win (WebSocket first, RPC fallback with retries)
+/// Returns (sol_earned, ore_earned, rewards_sol_after)
+async fn wait_for_rewards(
+    subscription: &MinerSubscription,
+    ore_client: &OreClient,
+    signer_pubkey: &Pubkey,
+    rewards_sol_before: u64,
+    rewards_ore_before: u64,
+) -> (u64, u64, u64) {
+    // ore-app pattern: Try WebSocket first (fast), fallback to RPC
+    log::debug!("⏳ Waiting for rewards update...");
+    let (mut rewards_sol_after, mut rewards_ore_after) = if let Some(miner) = subscription
+        .wait_for_wss_update(rewards_sol_before, Duration::from_secs(WSS_UPDATE_TIMEOUT_SECS))
+        .await
+    {
+        log::debug!("✅ Rewards updated via WebSocket! {:.6} → {:.6} SOL",
+            rewards_sol_before as f64 / 1e9,
+            miner.rewards_sol as f64 / 1e9);
+        (miner.rewards_sol, miner.rewards_ore)
+    } else {
+        // WebSocket didn't update quickly, fetch via RPC
+        log::debug!("📡 WebSocket timeout, fetching via RPC...");
+        if let Ok(Some(miner)) = ore_client.get_miner(signer_pubkey).await {
+            log::debug!("✅ Rewards fetched via RPC! {:.6} → {:.6} SOL",
+                rewards_sol_before as f64 / 1e9,
+                miner.rewards_sol as f64 / 1e9);
+            (miner.rewards_sol, miner.rewards_ore)
+        } else {
+            log::warn!("⚠️ Failed to fetch miner account");
+            (0, 0)
+        }
+    };
+
+    // Calculate actual rewards earned this round
+    let mut sol_earned_actual = rewards_sol_after.saturating_sub(rewards_sol_before);
+    let mut ore_earned_actual = rewards_ore_after.saturating_sub(rewards_ore_before);
+
+    // If rewards haven't updated yet (equal or less than before), retry up to 10 times
+    let mut retry_count = 0;
+    while rewards_sol_after <= rewards_sol_before && retry_count < MAX_REWARDS_RETRIES {
+        retry_count += 1;
+        log::debug!("⚠️ Rewards not updated yet (before: {:.6}, after: {:.6}), retrying {}/{}...",
+            rewards_sol_before as f64 / 1e9,
+            rewards_sol_after as f64 / 1e9,
+            retry_count,
+            MAX_REWARDS_RETRIES);
+        tokio::time::sleep(Duration::from_secs(REWARDS_RETRY_INTERVAL_SECS)).await;
+
+        if let Ok(Some(miner)) = ore_client.get_miner(signer_pubkey).await {
+            rewards_sol_after = miner.rewards_sol;
+            rewards_ore_after = miner.rewards_ore;
+            sol_earned_actual = rewards_sol_after.saturating_sub(rewards_sol_before);
+            ore_earned_actual = rewards_ore_after.saturating_sub(rewards_ore_before);
+
+            if rewards_sol_after > rewards_sol_before {
+                log::debug!("✅ Rewards updated after {} retries: {:.6} SOL, {:.6} ORE",
+                    retry_count,
+                    sol_earned_actual as f64 / 1e9,
+                    ore_earned_actual as f64 / 1e11);
+                break;
+            }
+        }
+    }
+
+    if rewards_sol_after <= rewards_sol_before {
+        log::warn!("⚠️ Rewards still not updated after {} retries (before: {:.6}, after: {:.6})",
+            retry_count,
+            rewards_sol_before as f64 / 1e9,
+            rewards_sol_after as f64 / 1e9);
+    }
+
+    (sol_earned_actual, ore_earned_actual, rewards_sol_after)
 }
@@ -0,0 +1,148 @@
+use crate::mining::grid::TOTAL_BLOCKS;
+use crate::ore::state::Round;
+
+/// Chance that any given square wins a round
+pub const SQUARE_WIN_PROBABILITY: f64 = 1.0 / TOTAL_BLOCKS as f64;
+
+/// Chance that the motherlode pays out in a given round
+pub const MOTHERLODE_HIT_PROBABILITY: f64 = 1.0 / 625.0;
+
+/// SOL we'd get back if `square` wins after we deploy `bet` on each of `our_squares`
+///
+/// Everything deployed outside the winning square (including our own other bets) is split
+/// among the winning square's depositors in proportion to their stake; our stake is returned.
+/// Protocol fees and ORE rewards are not modelled.
+pub fn payout_if_wins(deployed: &[u64; TOTAL_BLOCKS], our_squares: &[u8], square: u8, bet: u64) -> f64 {
+    if bet == 0 {
+        return 0.0;
+    }
+
+    let pot_after: u128 = deployed.iter().map(|&d| d as u128).sum::<u128>()
+        + bet as u128 * our_squares.len() as u128;
+    let square_after = deployed[square as usize] as u128 + bet as u128;
+    let losers_pot = pot_after - square_after;
+
+    // An empty square means we're the only depositor and take the whole losers' pot
+    bet as f64 + losers_pot as f64 * bet as f64 / square_after as f64
+}
+
+/// Expected return per lamport staked when betting `bet` on each of `squares`
+/// 1.0 is break-even; below 1.0 the current deployment makes the bet unfavorable
+pub fn ev_ratio(deployed: &[u64; TOTAL_BLOCKS], squares: &[u8], bet: u64) -> f64 {
+    let cost = bet as f64 * squares.len() as f64;
+    if cost == 0.0 {
+        return 0.0;
+    }
+
+    let expected: f64 = squares.iter()
+        .map(|&square| SQUARE_WIN_PROBABILITY * payout_if_wins(deployed, squares, square, bet))
+        .sum();
+
+    expected / cost
+}
+
+/// Expected lamports from the motherlode when betting `bet` on each of `squares`, valuing ORE at `ore_price_sol`
+///
+/// On a hit the motherlode is split among the winning square's depositors like the losers' pot.
+pub fn motherlode_ev(round: &Round, squares: &[u8], bet: u64, ore_price_sol: f64) -> f64 {
+    if bet == 0 {
+        return 0.0;
+    }
+    let motherlode_lamports = round.motherlode as f64 / 1e11 * ore_price_sol * 1e9;
+    squares.iter()
+        .map(|&square| {
+            let share = bet as f64 / (round.deployed[square as usize] as f64 + bet as f64);
+            SQUARE_WIN_PROBABILITY * MOTHERLODE_HIT_PROBABILITY * motherlode_lamports * share
+        })
+        .sum()
+}
+
+/// Expected net SOL result (lamports, negative = expected loss) of betting `bet_per_block`
+/// on each of `blocks` given the round's current deployment
+/// The motherlode is counted only when an ORE price is given, since it pays in ORE
+pub fn estimate_ev(round: &Round, blocks: &[u8], bet_per_block: u64, ore_price_sol: Option<f64>) -> f64 {
+    let cost = bet_per_block as f64 * blocks.len() as f64;
+    let motherlode = ore_price_sol.map_or(0.0, |price| motherlode_ev(round, blocks, bet_per_block, price));
+    ev_ratio(&round.deployed, blocks, bet_per_block) * cost - cost + motherlode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    const BET: u64 = 1_000_000;
+
+    /// Every square holds `BET` except the listed ones, which are empty
+    fn board_with_empty(empty: &[u8]) -> [u64; TOTAL_BLOCKS] {
+        let mut deployed = [BET; TOTAL_BLOCKS];
+        for &square in empty {
+            deployed[square as usize] = 0;
+        }
+        deployed
+    }
+
+    #[test]
+    fn uniform_board() {
+        // 25 bets deployed, ours makes 26: we hold half of the winning square's 2,
+        // so we get our stake back plus half of the other 24
+        let deployed = [BET; TOTAL_BLOCKS];
+        assert_eq!(payout_if_wins(&deployed, &[0], 0, BET), 13.0 * BET as f64);
+        assert!((ev_ratio(&deployed, &[0], BET) - 0.52).abs() < 1e-12);
+        let round = Round { deployed, ..Round::zeroed() };
+        assert!((estimate_ev(&round, &[0], BET, None) + 480_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn empty_square_takes_the_whole_losers_pot() {
+        let deployed = board_with_empty(&[5]);
+        // Alone on square 5: our stake plus the other 24 squares
+        assert_eq!(payout_if_wins(&deployed, &[5], 5, BET), 25.0 * BET as f64);
+        // 1/25 * 25 = break-even
+        assert!((ev_ratio(&deployed, &[5], BET) - 1.0).abs() < 1e-12);
+        let round = Round { deployed, ..Round::zeroed() };
+        assert!(estimate_ev(&round, &[5], BET, None).abs() < 1e-6);
+    }
+
+    #[test]
+    fn our_other_squares_feed_the_winner() {
+        // Squares 3 and 4 empty, we take both: whichever wins collects the other 23 plus our bet on the loser
+        let deployed = board_with_empty(&[3, 4]);
+        assert_eq!(payout_if_wins(&deployed, &[3, 4], 3, BET), 25.0 * BET as f64);
+        assert_eq!(payout_if_wins(&deployed, &[3, 4], 4, BET), 25.0 * BET as f64);
+        assert!((ev_ratio(&deployed, &[3, 4], BET) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn empty_board_only_returns_our_stake() {
+        let deployed = [0; TOTAL_BLOCKS];
+        assert_eq!(payout_if_wins(&deployed, &[7], 7, BET), BET as f64);
+        // We only get paid when our square wins: 1/25 of the stake back
+        assert!((ev_ratio(&deployed, &[7], BET) - 0.04).abs() < 1e-12);
+        let round = Round::zeroed();
+        assert!((estimate_ev(&round, &[7], BET, None) + 960_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn zero_bet_or_no_squares() {
+        let deployed = [BET; TOTAL_BLOCKS];
+        assert_eq!(payout_if_wins(&deployed, &[0], 0, 0), 0.0);
+        assert_eq!(ev_ratio(&deployed, &[0], 0), 0.0);
+        assert_eq!(ev_ratio(&deployed, &[], BET), 0.0);
+        assert_eq!(estimate_ev(&Round::zeroed(), &[], BET, Some(1.0)), 0.0);
+    }
+
+    #[test]
+    fn motherlode_adds_its_expected_share() {
+        // 625 ORE at 0.01 SOL is 6.25 SOL; alone on an empty square we'd take all of it on a hit
+        let deployed = board_with_empty(&[5]);
+        let round = Round { deployed, motherlode: 625 * 100_000_000_000, ..Round::zeroed() };
+        let expected = 6.25e9 / 25.0 / 625.0; // 400_000 lamports
+        assert!((motherlode_ev(&round, &[5], BET, 0.01) - expected).abs() < 1e-6);
+        assert!((estimate_ev(&round, &[5], BET, Some(0.01)) - expected).abs() < 1e-6);
+        // Without a price it's left out
+        assert!(estimate_ev(&round, &[5], BET, None).abs() < 1e-6);
+        // Sharing square 0 with an equal stake halves our cut
+        assert!((motherlode_ev(&round, &[0], BET, 0.01) - expected / 2.0).abs() < 1e-6);
+    }
+}
@@ -1,35 +1,404 @@
 use anyhow::Result;
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_commitment_config::CommitmentConfig;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
 use solana_sdk::{
-    signature::Signer,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
     signer::keypair::Keypair,
     transaction::Transaction,
 };
+use solana_transaction_status_client_types::TransactionStatus;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use crate::client::SolanaClient;
+use crate::config::{PriorityFeeConfig, PriorityFeeMode, TransactionConfig};
 use crate::mining::grid::BlockPosition;
-use crate::ore::instruction::{build_deploy_instruction, build_claim_sol_instruction, build_checkpoint_instruction};
+use crate::ore::OreClient;
+use crate::ore::instruction::{
+    build_checkpoint_instruction, build_claim_ore_instruction, build_claim_sol_instruction,
+    build_create_ata_idempotent_instruction, build_deploy_instruction, build_transfer_instruction,
+};
+use crate::ore::error::OreError;
+use crate::ore::pda::ORE_MINT_ADDRESS;
+use crate::ore::state::Miner;
+use crate::subscription::SignatureSubscription;
+
+/// How a failed send attempt should be handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxErrorKind {
+    /// Blockhash expired or RPC node lagging: retry right away with a fresh blockhash
+    RetryImmediately,
+    /// Transient failure (network, timeout): retry with backoff
+    Retry,
+    /// Program/simulation error (e.g. round already ended): retrying won't help
+    Fatal,
+}
+
+/// Classify a send/confirm error message from `solana_client`
+pub fn classify_tx_error(message: &str) -> TxErrorKind {
+    const IMMEDIATE: &[&str] = &[
+        "BlockhashNotFound",
+        "Blockhash not found",
+        "block height exceeded",
+        "has expired",
+        "Node is behind",
+        "NodeUnhealthy",
+        "Node is unhealthy",
+        "MinContextSlotNotReached",
+        "Minimum context slot has not been reached",
+    ];
+    const FATAL: &[&str] = &[
+        "custom program error",
+        "InstructionError",
+        "insufficient funds",
+        "Insufficient funds",
+        "InsufficientFundsForFee",
+        "InsufficientFundsForRent",
+        "AccountNotFound",
+        "no record of a prior credit", // AccountNotFound's message: the fee payer was never funded
+    ];
+
+    if IMMEDIATE.iter().any(|pattern| message.contains(pattern)) {
+        TxErrorKind::RetryImmediately
+    } else if FATAL.iter().any(|pattern| message.contains(pattern)) {
+        TxErrorKind::Fatal
+    } else {
+        TxErrorKind::Retry
+    }
+}
+
+/// Wrap a non-retryable error, naming the Ore program error when there is one
+/// (the `OreError` stays available via `downcast_ref`)
+fn fatal_error(e: anyhow::Error) -> anyhow::Error {
+    match OreError::from_message(&format!("{:#}", e)) {
+        Some(ore_error) => {
+            log::error!("❌ {}", ore_error);
+            e.context(ore_error)
+        }
+        None => e.context("Transaction failed with a non-retryable error"),
+    }
+}
+
+/// Wait before the next attempt according to the error classification
+async fn retry_delay(attempt: u8, kind: TxErrorKind) {
+    if kind == TxErrorKind::RetryImmediately {
+        log::info!("🔄 Retrying immediately with a fresh blockhash...");
+        return;
+    }
+
+    // Exponential backoff
+    let delay = Duration::from_millis(100 * (2_u64.pow(attempt as u32)));
+    log::info!("⏳ Retrying in {:?}...", delay);
+    tokio::time::sleep(delay).await;
+}
+
+/// How often a submitted signature's status is polled
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Longest the send is held back waiting for `signatureSubscribe` to be acknowledged
+const SIGNATURE_SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;         // Base fee, charged per signature
+const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u64 = 200_000; // CU limit per instruction when none is requested
+const MAX_COMPUTE_UNITS: u64 = 1_400_000;          // Transaction-wide CU cap
+
+/// Fee (lamports) charged for a transaction that lands, whether it succeeds or fails:
+/// the base fee per signature plus the compute unit price times the requested CU limit
+pub fn transaction_fee(signatures: usize, priority_fee: Option<u64>, compute_units: u64) -> u64 {
+    let priority = priority_fee
+        .map(|price| (price as u128 * compute_units as u128).div_ceil(1_000_000) as u64)
+        .unwrap_or(0);
+    signatures as u64 * LAMPORTS_PER_SIGNATURE + priority
+}
+
+/// Fee of one bet (Checkpoint + Deploy) for bankroll planning, at the highest compute unit price
+/// the config allows: the cap in dynamic mode if set, otherwise the static price
+pub fn estimated_bet_fee(fee_config: &PriorityFeeConfig) -> u64 {
+    let price = match fee_config.priority_fee_mode {
+        PriorityFeeMode::Static => fee_config.priority_fee_microlamports,
+        PriorityFeeMode::Dynamic => fee_config.max_priority_fee_microlamports.or(fee_config.priority_fee_microlamports),
+    };
+    let compute_units = fee_config.compute_unit_limit
+        .map_or((2 * DEFAULT_INSTRUCTION_COMPUTE_UNITS).min(MAX_COMPUTE_UNITS), |units| units as u64);
+    transaction_fee(1, price, compute_units)
+}
+
+/// Where a submitted transaction stands, from one getSignatureStatuses result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmationState {
+    /// Not seen yet, or seen below the requested commitment
+    Pending,
+    /// Reached the requested commitment without error
+    Confirmed,
+    /// Landed but failed (message is the TransactionError)
+    Failed(String),
+}
+
+/// Where signature statuses come from (the RPC node; a stub in tests)
+#[async_trait]
+pub trait SignatureStatusSource: Send + Sync {
+    async fn signature_status(&self, signature: &Signature) -> Result<Option<TransactionStatus>>;
+}
+
+#[async_trait]
+impl SignatureStatusSource for RpcClient {
+    async fn signature_status(&self, signature: &Signature) -> Result<Option<TransactionStatus>> {
+        Ok(self.get_signature_statuses(&[*signature]).await?.value.into_iter().next().flatten())
+    }
+}
+
+/// Classify a signature status against the commitment we're waiting for
+pub fn confirmation_state(status: Option<&TransactionStatus>, commitment: CommitmentConfig) -> ConfirmationState {
+    match status {
+        None => ConfirmationState::Pending,
+        Some(status) => match &status.err {
+            Some(err) => ConfirmationState::Failed(err.to_string()),
+            None if status.satisfies_commitment(commitment) => ConfirmationState::Confirmed,
+            None => ConfirmationState::Pending,
+        },
+    }
+}
+
+/// Whether the Miner account already shows a Deploy on `round_id` for every selected square
+/// Used after an ambiguous failure (e.g. confirmation timeout) to avoid betting twice
+pub fn deploy_landed(miner: &Miner, round_id: u64, squares: &[bool; 25]) -> bool {
+    miner.round_id == round_id
+        && squares.iter()
+            .zip(miner.deployed.iter())
+            .all(|(&selected, &deployed)| !selected || deployed > 0)
+}
+
+/// Deploy being sent, so retries can check whether an earlier attempt already landed
+#[derive(Clone, Copy)]
+struct DeployCheck {
+    round_id: u64,
+    squares: [bool; 25],
+}
+
+/// Where Miner accounts come from (the RPC node; a stub in tests)
+#[async_trait]
+pub trait MinerSource: Send + Sync {
+    async fn miner(&self, authority: &Pubkey) -> Result<Option<Miner>>;
+}
+
+#[async_trait]
+impl MinerSource for OreClient {
+    async fn miner(&self, authority: &Pubkey) -> Result<Option<Miner>> {
+        self.get_miner(authority).await
+    }
+}
+
+/// A Deploy found on chain after the attempt that sent it reported a failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LandedDeploy {
+    signature: Option<Signature>, // None if no attempt's status shows it landed
+    fee: u64,
+}
+
+/// After a failed attempt, whether one of the `sent` Deploys (signature, fee) is on chain anyway
+/// The Miner account decides whether it landed; signature statuses tell which attempt it was.
+/// If no status says so (e.g. the node dropped it), the signature is left out and the latest
+/// attempt's fee is counted, since every attempt costs about the same.
+async fn find_landed_deploy(
+    miners: &dyn MinerSource,
+    statuses: &dyn SignatureStatusSource,
+    authority: &Pubkey,
+    check: DeployCheck,
+    sent: &[(Signature, u64)],
+) -> Option<LandedDeploy> {
+    let &(_, latest_fee) = sent.last()?;
+    match miners.miner(authority).await {
+        Ok(Some(miner)) if deploy_landed(&miner, check.round_id, &check.squares) => {}
+        Ok(_) => return None,
+        Err(e) => {
+            log::warn!("⚠️ Failed to fetch miner for idempotency check: {}", e);
+            return None;
+        }
+    }
+
+    for &(signature, fee) in sent.iter().rev() {
+        match statuses.signature_status(&signature).await {
+            Ok(Some(status)) if status.err.is_none() => return Some(LandedDeploy { signature: Some(signature), fee }),
+            Ok(_) => {}
+            Err(e) => log::warn!("⚠️ Failed to look up the status of {}: {}", signature, e),
+        }
+    }
+    Some(LandedDeploy { signature: None, fee: latest_fee })
+}
 
 #[derive(Clone)]
 pub struct TransactionExecutor {
     solana: SolanaClient,
+    ore: OreClient,
     max_retries: u8,
+    dry_run: bool,
+    fee_config: PriorityFeeConfig,
+    fee_cache: Arc<Mutex<Option<u64>>>, // Dynamic estimate for the current round
+    commitment: CommitmentConfig,       // Level a transaction must reach to count as confirmed
+    skip_preflight: bool,
+    confirm_timeout: Duration,          // Per-attempt limit on signature status polling
+    signature_subscription: Option<SignatureSubscription>, // Push confirmation, when enabled
+    websocket_confirm_timeout: Duration, // How long to wait on the WebSocket before polling
+    fees_paid: Arc<AtomicU64>,          // Fees of landed transactions not yet collected by `take_fees_paid`
 }
 
 impl TransactionExecutor {
-    pub fn new(solana: SolanaClient, max_retries: u8) -> Self {
+    /// `ws_url` is used for `signatureSubscribe` when `confirm_via_websocket` is set
+    pub fn new(solana: SolanaClient, ore: OreClient, dry_run: bool, tx_config: TransactionConfig, ws_url: String) -> Self {
         Self {
             solana,
-            max_retries,
+            ore,
+            max_retries: tx_config.max_retries,
+            dry_run,
+            fee_config: tx_config.priority_fee,
+            fee_cache: Arc::new(Mutex::new(None)),
+            commitment: tx_config.commitment.config(),
+            skip_preflight: tx_config.skip_preflight,
+            confirm_timeout: Duration::from_secs(tx_config.confirm_timeout_secs),
+            signature_subscription: tx_config.confirm_via_websocket.then(|| SignatureSubscription::new(ws_url)),
+            websocket_confirm_timeout: Duration::from_secs(tx_config.websocket_confirm_timeout_secs),
+            fees_paid: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Fees (lamports) of every transaction that landed since the last call, including failed ones
+    /// Shared by all clones, so fees of claims sent from background tasks are collected here too
+    pub fn take_fees_paid(&self) -> u64 {
+        self.fees_paid.swap(0, Ordering::Relaxed)
+    }
+
+    /// Fee this transaction will be charged if it lands
+    fn fee_for(&self, transaction: &Transaction, priority_fee: Option<u64>) -> u64 {
+        let compute_units = match self.fee_config.compute_unit_limit {
+            Some(units) => units as u64,
+            None => {
+                let budget_program = solana_compute_budget_interface::id();
+                let instructions = transaction.message.instructions.iter()
+                    .filter(|ix| transaction.message.account_keys.get(ix.program_id_index as usize) != Some(&budget_program))
+                    .count() as u64;
+                (instructions * DEFAULT_INSTRUCTION_COMPUTE_UNITS).min(MAX_COMPUTE_UNITS)
+            }
+        };
+        transaction_fee(transaction.signatures.len(), priority_fee, compute_units)
+    }
+
+    fn add_fee(&self, fee: u64) {
+        self.fees_paid.fetch_add(fee, Ordering::Relaxed);
+    }
+
+    /// Compute unit price to attach: the static value, or the latest dynamic estimate
+    /// (static value as fallback), clamped to the configured min/max
+    pub fn priority_fee(&self) -> Option<u64> {
+        let fee = match self.fee_config.priority_fee_mode {
+            PriorityFeeMode::Static => self.fee_config.priority_fee_microlamports,
+            PriorityFeeMode::Dynamic => {
+                (*self.fee_cache.lock().unwrap()).or(self.fee_config.priority_fee_microlamports)
+            }
+        };
+
+        fee.map(|fee| self.clamp_priority_fee(fee))
+    }
+
+    /// Clamp a compute unit price to `min_priority_fee_microlamports`/`max_priority_fee_microlamports`
+    fn clamp_priority_fee(&self, fee: u64) -> u64 {
+        if let Some(max) = self.fee_config.max_priority_fee_microlamports {
+            if fee > max {
+                log::warn!("⚠️ Priority fee {} capped to max {} micro-lamports/CU", fee, max);
+                return max;
+            }
+        }
+        if let Some(min) = self.fee_config.min_priority_fee_microlamports {
+            if fee < min {
+                log::debug!("Priority fee {} raised to min {} micro-lamports/CU", fee, min);
+                return min;
+            }
+        }
+        fee
+    }
+
+    /// Percentile of recent prioritization fees for the accounts the Deploy instruction write-locks
+    pub async fn estimate_priority_fee(&self, authority: &Pubkey, round_id: u64) -> Result<u64> {
+        let deploy_ix = build_deploy_instruction(*authority, *authority, 0, round_id, [false; 25]);
+        let accounts: Vec<Pubkey> = deploy_ix.accounts.iter()
+            .filter(|meta| meta.is_writable && !meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+
+        let percentile = self.fee_config.priority_fee_percentile;
+        let fee = self.solana.get_priority_fee_estimate(&accounts, percentile).await?;
+        Ok(self.clamp_priority_fee(fee))
+    }
+
+    /// Re-estimate the dynamic priority fee for this round; returns the fee that will be attached
+    /// On RPC failure the previous estimate is dropped so the static fee is used as fallback
+    pub async fn refresh_priority_fee(&self, authority: &Pubkey, round_id: u64) -> Option<u64> {
+        if self.fee_config.priority_fee_mode == PriorityFeeMode::Dynamic {
+            match self.estimate_priority_fee(authority, round_id).await {
+                Ok(fee) => {
+                    log::info!("💸 Dynamic priority fee estimate (p{}): {} micro-lamports/CU",
+                        self.fee_config.priority_fee_percentile, fee);
+                    *self.fee_cache.lock().unwrap() = Some(fee);
+                }
+                Err(e) => {
+                    log::warn!("⚠️ Failed to estimate priority fee: {}. Using static fallback", e);
+                    *self.fee_cache.lock().unwrap() = None;
+                }
+            }
+        }
+
+        self.priority_fee()
+    }
+
+    /// Prepend ComputeBudget instructions (priority fee / CU limit) when configured
+    fn with_compute_budget(&self, instructions: &[Instruction], priority_fee: Option<u64>) -> Vec<Instruction> {
+        let mut all = Vec::with_capacity(instructions.len() + 2);
+
+        if let Some(units) = self.fee_config.compute_unit_limit {
+            all.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(price) = priority_fee {
+            all.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+
+        if !all.is_empty() {
+            let price = priority_fee.unwrap_or(0);
+            log::info!("💸 Priority fee: {} micro-lamports/CU, CU limit: {}, effective: {}",
+                price,
+                self.fee_config.compute_unit_limit.map_or("default".to_string(), |u| u.to_string()),
+                self.fee_config.fee_lamports(price).map_or("unknown (no CU limit)".to_string(), |f| format!("{} lamports", f)));
+        }
+
+        all.extend_from_slice(instructions);
+        all
+    }
+
+    /// Log the instructions that would have been sent and return a synthetic signature
+    fn simulate_dry_run(&self, instructions: &[Instruction]) -> String {
+        log::info!("🧪 DRY RUN: skipping submission of {} instruction(s)", instructions.len());
+        for instruction in instructions {
+            log::debug!("   - program {} ({} accounts, data: {:?})",
+                instruction.program_id,
+                instruction.accounts.len(),
+                instruction.data);
+        }
+        format!("dry-run-{}", chrono::Utc::now().timestamp_millis())
+    }
+
     /// Execute bet transaction with retry logic
+    /// `deadline` (the round's end) bounds confirmation polling
     pub async fn execute_bet(
         &self,
         signer: &dyn Signer,
         round_id: u64,
         blocks: &[BlockPosition],
         bet_per_block: u64,
-    ) -> Result<String> {
+        deadline: Option<Instant>,
+    ) -> Result<Option<String>> {
         // Convert BlockPosition to boolean array
         let mut squares = [false; 25];
         for block in blocks {
@@ -50,7 +419,8 @@ impl TransactionExecutor {
             log::debug!("   - Block {} (row: {}, col: {})", block.index, block.row, block.col);
         }
 
-        self.send_transaction_with_retry(signer, vec![instruction]).await
+        let check = DeployCheck { round_id, squares };
+        self.send_deploy_with_retry(signer, vec![instruction], check, deadline).await
     }
 
     /// Execute checkpoint + bet in single transaction
@@ -61,7 +431,8 @@ impl TransactionExecutor {
         bet_round_id: u64,
         blocks: &[BlockPosition],
         bet_per_block: u64,
-    ) -> Result<String> {
+        deadline: Option<Instant>,
+    ) -> Result<Option<String>> {
         // Convert BlockPosition to boolean array
         let mut squares = [false; 25];
         for block in blocks {
@@ -92,7 +463,8 @@ impl TransactionExecutor {
         }
 
         // Send both instructions in single transaction
-        self.send_transaction_with_retry(signer, vec![checkpoint_ix, deploy_ix]).await
+        let check = DeployCheck { round_id: bet_round_id, squares };
+        self.send_deploy_with_retry(signer, vec![checkpoint_ix, deploy_ix], check, deadline).await
     }
 
     /// Execute claim SOL transaction (takes owned Keypair for Send + 'static compatibility)
@@ -108,12 +480,49 @@ impl TransactionExecutor {
         self.send_transaction_with_retry_keypair(signer, vec![instruction]).await
     }
 
+    /// Execute claim ORE transaction, creating the ORE token account first if it doesn't exist
+    pub async fn execute_claim_ore(
+        &self,
+        signer: Keypair,
+    ) -> Result<String> {
+        let authority = signer.pubkey();
+        let mut instructions = Vec::with_capacity(2);
+
+        if self.ore.get_ore_token_balance(&authority).await?.is_none() {
+            log::info!("🪙 ORE token account not found, creating it in the claim transaction");
+            instructions.push(build_create_ata_idempotent_instruction(authority, authority, ORE_MINT_ADDRESS));
+        }
+        instructions.push(build_claim_ore_instruction(authority));
+
+        log::debug!("🔨 Building Claim ORE instruction");
+
+        self.send_transaction_with_retry_keypair(signer, instructions).await
+    }
+
+    /// Execute a SOL transfer of `lamports` to `destination` (takes owned Keypair for Send + 'static compatibility)
+    pub async fn execute_transfer(
+        &self,
+        signer: Keypair,
+        destination: Pubkey,
+        lamports: u64,
+    ) -> Result<String> {
+        let instruction = build_transfer_instruction(signer.pubkey(), destination, lamports);
+
+        log::debug!("🔨 Building Transfer instruction: {:.6} SOL to {}", lamports as f64 / 1e9, destination);
+
+        self.send_transaction_with_retry_keypair(signer, vec![instruction]).await
+    }
+
     /// Send transaction with retry logic (for Keypair)
     async fn send_transaction_with_retry_keypair(
         &self,
         signer: Keypair,
-        instructions: Vec<solana_sdk::instruction::Instruction>,
+        instructions: Vec<Instruction>,
     ) -> Result<String> {
+        if self.dry_run {
+            return Ok(self.simulate_dry_run(&instructions));
+        }
+
         let mut last_error = None;
 
         for attempt in 1..=self.max_retries {
@@ -123,14 +532,16 @@ impl TransactionExecutor {
                     return Ok(signature);
                 }
                 Err(e) => {
-                    log::warn!("❌ Transaction attempt {} failed: {}", attempt, e);
+                    let kind = classify_tx_error(&format!("{:#}", e));
+                    log::warn!("❌ Transaction attempt {} failed ({:?}): {}", attempt, kind, e);
+
+                    if kind == TxErrorKind::Fatal {
+                        return Err(fatal_error(e));
+                    }
                     last_error = Some(e);
 
                     if attempt < self.max_retries {
-                        // Exponential backoff
-                        let delay = std::time::Duration::from_millis(100 * (2_u64.pow(attempt as u32)));
-                        log::info!("⏳ Retrying in {:?}...", delay);
-                        tokio::time::sleep(delay).await;
+                        retry_delay(attempt, kind).await;
                     }
                 }
             }
@@ -143,72 +554,436 @@ impl TransactionExecutor {
     async fn send_transaction_keypair(
         &self,
         signer: &Keypair,
-        instructions: &[solana_sdk::instruction::Instruction],
+        instructions: &[Instruction],
     ) -> Result<String> {
-        // Get recent blockhash
+        // Fresh blockhash every attempt so a retry never reuses an expiring one
         let recent_blockhash = self.solana.rpc.get_latest_blockhash().await?;
 
         // Create and sign transaction
-        let mut transaction = Transaction::new_with_payer(instructions, Some(&signer.pubkey()));
+        let priority_fee = self.priority_fee();
+        let instructions = self.with_compute_budget(instructions, priority_fee);
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&signer.pubkey()));
         transaction.sign(&[signer], recent_blockhash);
+        let fee = self.fee_for(&transaction, priority_fee);
 
         // Send and confirm transaction
-        let signature = self.solana.rpc
-            .send_and_confirm_transaction(&transaction)
-            .await?;
+        let signature = self.submit_and_confirm(&transaction, fee, None).await?;
 
         Ok(signature.to_string())
     }
 
-    /// Send transaction with retry logic
-    async fn send_transaction_with_retry(
+    /// Send a Deploy with retry logic
+    /// Before each retry, and after the last failure, the Miner account is checked so an attempt
+    /// that landed despite its error isn't sent again. The signature is None when the landed
+    /// attempt can't be told apart from the others.
+    async fn send_deploy_with_retry(
         &self,
         signer: &dyn Signer,
-        instructions: Vec<solana_sdk::instruction::Instruction>,
-    ) -> Result<String> {
+        instructions: Vec<Instruction>,
+        check: DeployCheck,
+        deadline: Option<Instant>,
+    ) -> Result<Option<String>> {
+        if self.dry_run {
+            return Ok(Some(self.simulate_dry_run(&instructions)));
+        }
+
         let mut last_error = None;
+        let mut sent = Vec::new();
 
         for attempt in 1..=self.max_retries {
-            match self.send_transaction(signer, &instructions).await {
+            if let Some(landed) = self.earlier_deploy_landed(&signer.pubkey(), check, &sent).await {
+                return Ok(landed);
+            }
+
+            match self.send_transaction(signer, &instructions, &mut sent, deadline).await {
                 Ok(signature) => {
                     log::info!("✅ Transaction confirmed: {}", signature);
-                    return Ok(signature);
+                    return Ok(Some(signature));
                 }
                 Err(e) => {
-                    log::warn!("❌ Transaction attempt {} failed: {}", attempt, e);
+                    let kind = classify_tx_error(&format!("{:#}", e));
+                    log::warn!("❌ Transaction attempt {} failed ({:?}): {}", attempt, kind, e);
+
+                    if kind == TxErrorKind::Fatal {
+                        return Err(fatal_error(e));
+                    }
                     last_error = Some(e);
 
                     if attempt < self.max_retries {
-                        // Exponential backoff
-                        let delay = std::time::Duration::from_millis(100 * (2_u64.pow(attempt as u32)));
-                        log::info!("⏳ Retrying in {:?}...", delay);
-                        tokio::time::sleep(delay).await;
+                        retry_delay(attempt, kind).await;
                     }
                 }
             }
         }
 
+        // The last attempt may have landed too
+        if let Some(landed) = self.earlier_deploy_landed(&signer.pubkey(), check, &sent).await {
+            return Ok(landed);
+        }
+
         Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Transaction failed after {} retries", self.max_retries)))
     }
 
+    /// Whether an earlier Deploy attempt is on chain; counts its fee and returns its signature if known
+    async fn earlier_deploy_landed(&self, authority: &Pubkey, check: DeployCheck, sent: &[(Signature, u64)]) -> Option<Option<String>> {
+        let landed = find_landed_deploy(&self.ore, self.solana.rpc.as_ref(), authority, check, sent).await?;
+        log::warn!("⚠️ An earlier attempt landed despite the error, not re-sending Deploy");
+        self.add_fee(landed.fee);
+        match landed.signature {
+            Some(signature) => log::info!("✅ Transaction confirmed: {}", signature),
+            None => log::warn!("⚠️ Couldn't tell which of {} attempts landed, recording the bet without a signature", sent.len()),
+        }
+        Some(landed.signature.map(|signature| signature.to_string()))
+    }
+
     /// Send transaction and wait for confirmation
+    /// `sent` collects every signature (and its fee) before submission so an ambiguous failure can be matched later
     async fn send_transaction(
         &self,
         signer: &dyn Signer,
-        instructions: &[solana_sdk::instruction::Instruction],
+        instructions: &[Instruction],
+        sent: &mut Vec<(Signature, u64)>,
+        deadline: Option<Instant>,
     ) -> Result<String> {
-        // Get recent blockhash
+        // Fresh blockhash every attempt so a retry never reuses an expiring one
         let recent_blockhash = self.solana.rpc.get_latest_blockhash().await?;
 
         // Create and sign transaction
-        let mut transaction = Transaction::new_with_payer(instructions, Some(&signer.pubkey()));
+        let priority_fee = self.priority_fee();
+        let instructions = self.with_compute_budget(instructions, priority_fee);
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&signer.pubkey()));
         transaction.sign(&[signer], recent_blockhash);
+        let fee = self.fee_for(&transaction, priority_fee);
+        sent.push((transaction.signatures[0], fee));
 
         // Send and confirm transaction
+        let signature = self.submit_and_confirm(&transaction, fee, deadline).await?;
+
+        Ok(signature.to_string())
+    }
+
+    /// Submit once, then wait for the signature to reach the configured commitment, fail,
+    /// or hit `confirm_timeout` / `deadline` (whichever is first)
+    /// With `confirm_via_websocket`, a `signatureSubscribe` notification is awaited first;
+    /// if it doesn't arrive in time, the signature status is polled for the remaining time
+    /// `fee` is counted as paid once the transaction lands, even if it fails on chain
+    async fn submit_and_confirm(&self, transaction: &Transaction, fee: u64, deadline: Option<Instant>) -> Result<Signature> {
+        let started = Instant::now();
+        let timeout_at = started + self.confirm_timeout;
+        let give_up_at = deadline.map_or(timeout_at, |deadline| deadline.min(timeout_at));
+
+        // Subscribe before sending so a fast landing can't slip past the subscription
+        let waiter = match &self.signature_subscription {
+            Some(subscription) => match subscription
+                .subscribe(&transaction.signatures[0], self.commitment, SIGNATURE_SUBSCRIBE_TIMEOUT)
+                .await
+            {
+                Ok(waiter) => Some(waiter),
+                Err(e) => {
+                    log::warn!("⚠️ signatureSubscribe failed, confirming by polling: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let send_config = RpcSendTransactionConfig {
+            skip_preflight: self.skip_preflight,
+            preflight_commitment: Some(self.commitment.commitment),
+            ..RpcSendTransactionConfig::default()
+        };
         let signature = self.solana.rpc
-            .send_and_confirm_transaction(&transaction)
+            .send_transaction_with_config(transaction, send_config)
             .await?;
+        log::debug!("📨 Submitted {}, waiting for {:?}", signature, self.commitment.commitment);
 
-        Ok(signature.to_string())
+        if let Some(waiter) = waiter {
+            let ws_deadline = give_up_at.min(Instant::now() + self.websocket_confirm_timeout);
+            match waiter.wait(ws_deadline.into()).await {
+                Ok(Ok(())) => {
+                    log::debug!("📬 {} confirmed via WebSocket in {:.1}s", signature, started.elapsed().as_secs_f64());
+                    self.add_fee(fee);
+                    return Ok(signature);
+                }
+                Ok(Err(err)) => {
+                    self.add_fee(fee);
+                    anyhow::bail!("Transaction {} failed: {}", signature, err);
+                }
+                Err(e) => log::warn!("⚠️ WebSocket confirmation for {} gave up ({}), polling instead", signature, e),
+            }
+        }
+
+        match poll_confirmation(self.solana.rpc.as_ref(), &signature, self.commitment, give_up_at, STATUS_POLL_INTERVAL).await? {
+            ConfirmationState::Confirmed => {
+                self.add_fee(fee);
+                Ok(signature)
+            }
+            ConfirmationState::Failed(err) => {
+                self.add_fee(fee);
+                anyhow::bail!("Transaction {} failed: {}", signature, err);
+            }
+            ConfirmationState::Pending => {
+                anyhow::bail!("Transaction {} not confirmed within {:.1}s (confirmation timeout)",
+                    signature,
+                    started.elapsed().as_secs_f64());
+            }
+        }
+    }
+}
+
+/// Poll the signature status every `interval` until it's confirmed or failed
+/// Returns Pending if `give_up_at` passes first; RPC errors end the wait
+async fn poll_confirmation(
+    source: &dyn SignatureStatusSource,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+    give_up_at: Instant,
+    interval: Duration,
+) -> Result<ConfirmationState> {
+    loop {
+        let status = source.signature_status(signature).await?;
+        let state = confirmation_state(status.as_ref(), commitment);
+        if state != ConfirmationState::Pending || Instant::now() >= give_up_at {
+            return Ok(state);
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Commitment;
+    use bytemuck::Zeroable;
+    use solana_sdk::transaction::TransactionError;
+    use solana_transaction_status_client_types::TransactionConfirmationStatus;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn classify_representative_client_errors() {
+        let table = [
+            // Blockhash not found / expired: resend right away with a fresh one
+            ("RPC response error -32002: Transaction simulation failed: Blockhash not found; ", TxErrorKind::RetryImmediately),
+            ("Transaction simulation failed: BlockhashNotFound", TxErrorKind::RetryImmediately),
+            ("Signature 5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW has expired: block height exceeded", TxErrorKind::RetryImmediately),
+            ("RPC response error -32005: Node is behind by 154 slots [NodeUnhealthy]", TxErrorKind::RetryImmediately),
+            ("RPC response error -32016: Minimum context slot has not been reached", TxErrorKind::RetryImmediately),
+            // Rate limits and network trouble: back off and retry
+            ("HTTP status client error (429 Too Many Requests) for url (https://api.mainnet-beta.solana.com/)", TxErrorKind::Retry),
+            ("error sending request for url (https://api.mainnet-beta.solana.com/): operation timed out", TxErrorKind::Retry),
+            ("Transaction 3xRkq… not confirmed within 30.0s (confirmation timeout)", TxErrorKind::Retry),
+            // Program and funding errors: the same transaction will fail again
+            ("RPC response error -32002: Transaction simulation failed: Error processing Instruction 1: custom program error: 0x1; 3 log messages:", TxErrorKind::Fatal),
+            ("Transaction failed: InstructionError(1, Custom(6))", TxErrorKind::Fatal),
+            ("RPC response error -32002: Transaction simulation failed: Insufficient funds for fee", TxErrorKind::Fatal),
+            ("Transaction results in an account (0) with insufficient funds for rent", TxErrorKind::Fatal),
+            ("RPC response error -32002: Transaction simulation failed: Attempt to debit an account but found no record of a prior credit.", TxErrorKind::Fatal),
+        ];
+        for (message, expected) in table {
+            assert_eq!(classify_tx_error(message), expected, "{}", message);
+        }
+    }
+
+    fn squares(selected: &[usize]) -> [bool; 25] {
+        let mut squares = [false; 25];
+        for &square in selected {
+            squares[square] = true;
+        }
+        squares
+    }
+
+    fn miner_on(round_id: u64, deployed: &[(usize, u64)]) -> Miner {
+        let mut miner = Miner::zeroed();
+        miner.round_id = round_id;
+        for &(square, lamports) in deployed {
+            miner.deployed[square] = lamports;
+        }
+        miner
+    }
+
+    #[test]
+    fn deploy_landed_on_every_selected_square() {
+        let miner = miner_on(42, &[(3, 1_000_000), (7, 1_000_000)]);
+        assert!(deploy_landed(&miner, 42, &squares(&[3, 7])));
+        assert!(deploy_landed(&miner, 42, &squares(&[7])));
+    }
+
+    #[test]
+    fn deploy_not_landed() {
+        assert!(!deploy_landed(&miner_on(42, &[]), 42, &squares(&[3, 7])));
+        // Only part of the selection is on chain: not our Deploy
+        assert!(!deploy_landed(&miner_on(42, &[(3, 1_000_000)]), 42, &squares(&[3, 7])));
+    }
+
+    #[test]
+    fn deploy_on_a_stale_round_does_not_count() {
+        let miner = miner_on(41, &[(3, 1_000_000), (7, 1_000_000)]);
+        assert!(!deploy_landed(&miner, 42, &squares(&[3, 7])));
+    }
+
+    /// Status as getSignatureStatuses reports it at `level` (finalized = rooted, no confirmation count)
+    fn status(level: TransactionConfirmationStatus, err: Option<TransactionError>) -> TransactionStatus {
+        let confirmations = match level {
+            TransactionConfirmationStatus::Processed => Some(0),
+            TransactionConfirmationStatus::Confirmed => Some(5),
+            TransactionConfirmationStatus::Finalized => None,
+        };
+        TransactionStatus {
+            slot: 100,
+            confirmations,
+            status: err.clone().map_or(Ok(()), Err),
+            err,
+            confirmation_status: Some(level),
+        }
+    }
+
+    fn commitment(json: &str) -> CommitmentConfig {
+        serde_json::from_str::<TransactionConfig>(json).unwrap().commitment.config()
+    }
+
+    #[test]
+    fn commitment_parses_from_config() {
+        assert_eq!(commitment("{}"), CommitmentConfig::confirmed());
+        assert_eq!(commitment(r#"{"commitment": "processed"}"#), CommitmentConfig::processed());
+        assert_eq!(commitment(r#"{"commitment": "finalized"}"#), CommitmentConfig::finalized());
+        assert!(serde_json::from_str::<TransactionConfig>(r#"{"commitment": "max"}"#).is_err());
+        assert_eq!(Commitment::default(), Commitment::Confirmed);
+    }
+
+    #[test]
+    fn confirmation_state_against_each_commitment() {
+        use TransactionConfirmationStatus::*;
+        let confirmed = commitment(r#"{"commitment": "confirmed"}"#);
+        let finalized = commitment(r#"{"commitment": "finalized"}"#);
+
+        assert_eq!(confirmation_state(None, confirmed), ConfirmationState::Pending);
+        assert_eq!(confirmation_state(Some(&status(Processed, None)), confirmed), ConfirmationState::Pending);
+        assert_eq!(confirmation_state(Some(&status(Confirmed, None)), confirmed), ConfirmationState::Confirmed);
+        assert_eq!(confirmation_state(Some(&status(Confirmed, None)), finalized), ConfirmationState::Pending);
+        assert_eq!(confirmation_state(Some(&status(Finalized, None)), finalized), ConfirmationState::Confirmed);
+        assert_eq!(confirmation_state(Some(&status(Processed, None)), CommitmentConfig::processed()), ConfirmationState::Confirmed);
+
+        // A landed failure is reported whatever the commitment
+        let failed = status(Processed, Some(TransactionError::InsufficientFundsForFee));
+        assert!(matches!(confirmation_state(Some(&failed), finalized), ConfirmationState::Failed(_)));
+    }
+
+    /// Hands out queued statuses one poll at a time, then "not found"
+    struct ScriptedStatuses {
+        replies: Mutex<VecDeque<Result<Option<TransactionStatus>>>>,
+        polls: AtomicU64,
+    }
+
+    impl ScriptedStatuses {
+        fn new(replies: Vec<Result<Option<TransactionStatus>>>) -> Self {
+            Self { replies: Mutex::new(replies.into()), polls: AtomicU64::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl SignatureStatusSource for ScriptedStatuses {
+        async fn signature_status(&self, _signature: &Signature) -> Result<Option<TransactionStatus>> {
+            self.polls.fetch_add(1, Ordering::Relaxed);
+            self.replies.lock().unwrap().pop_front().unwrap_or(Ok(None))
+        }
+    }
+
+    async fn poll(source: &ScriptedStatuses, give_up_after: Duration) -> Result<ConfirmationState> {
+        let give_up_at = Instant::now() + give_up_after;
+        poll_confirmation(source, &Signature::default(), CommitmentConfig::confirmed(), give_up_at, Duration::from_millis(1)).await
+    }
+
+    #[tokio::test]
+    async fn polling_waits_through_pending_until_confirmed() {
+        use TransactionConfirmationStatus::*;
+        let source = ScriptedStatuses::new(vec![Ok(None), Ok(Some(status(Processed, None))), Ok(Some(status(Confirmed, None)))]);
+        assert_eq!(poll(&source, Duration::from_secs(5)).await.unwrap(), ConfirmationState::Confirmed);
+        assert_eq!(source.polls.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn polling_stops_on_a_failed_transaction() {
+        let failed = status(TransactionConfirmationStatus::Processed, Some(TransactionError::AccountInUse));
+        let source = ScriptedStatuses::new(vec![Ok(None), Ok(Some(failed))]);
+        let state = poll(&source, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(state, ConfirmationState::Failed(TransactionError::AccountInUse.to_string()));
+        assert_eq!(source.polls.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn polling_gives_up_while_pending() {
+        let source = ScriptedStatuses::new(vec![]);
+        assert_eq!(poll(&source, Duration::from_millis(20)).await.unwrap(), ConfirmationState::Pending);
+        assert!(source.polls.load(Ordering::Relaxed) >= 2);
+        // Past the deadline already: one look, then give up
+        let source = ScriptedStatuses::new(vec![]);
+        assert_eq!(poll(&source, Duration::ZERO).await.unwrap(), ConfirmationState::Pending);
+        assert_eq!(source.polls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn polling_surfaces_rpc_errors() {
+        let source = ScriptedStatuses::new(vec![Ok(None), Err(anyhow::anyhow!("connection reset"))]);
+        assert!(poll(&source, Duration::from_secs(5)).await.unwrap_err().to_string().contains("connection reset"));
+    }
+
+    /// Returns the same Miner account (or error) on every lookup
+    struct StubMiner(std::result::Result<Option<Miner>, String>);
+
+    #[async_trait]
+    impl MinerSource for StubMiner {
+        async fn miner(&self, _authority: &Pubkey) -> Result<Option<Miner>> {
+            self.0.clone().map_err(|e| anyhow::anyhow!(e))
+        }
+    }
+
+    const CHECK: DeployCheck = DeployCheck {
+        round_id: 42,
+        squares: {
+            let mut squares = [false; 25];
+            squares[3] = true;
+            squares[7] = true;
+            squares
+        },
+    };
+
+    /// Three attempts, oldest first, each with a different fee
+    fn three_attempts() -> Vec<(Signature, u64)> {
+        vec![(Signature::from([1; 64]), 5_000), (Signature::from([2; 64]), 6_000), (Signature::from([3; 64]), 7_000)]
+    }
+
+    fn landed_miner() -> StubMiner {
+        StubMiner(Ok(Some(miner_on(42, &[(3, 1_000_000), (7, 1_000_000)]))))
+    }
+
+    #[tokio::test]
+    async fn earlier_attempt_that_landed_is_identified() {
+        use TransactionConfirmationStatus::*;
+        // Newest first: the last attempt was never seen, the second failed on chain, the first landed
+        let failed = status(Processed, Some(TransactionError::AccountInUse));
+        let statuses = ScriptedStatuses::new(vec![Ok(None), Ok(Some(failed)), Ok(Some(status(Confirmed, None)))]);
+        let landed = find_landed_deploy(&landed_miner(), &statuses, &Pubkey::default(), CHECK, &three_attempts()).await;
+        assert_eq!(landed, Some(LandedDeploy { signature: Some(Signature::from([1; 64])), fee: 5_000 }));
+        assert_eq!(statuses.polls.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn landed_deploy_without_a_known_signature() {
+        // The Miner shows the Deploy but no status does: keep the latest fee, drop the signature
+        let statuses = ScriptedStatuses::new(vec![Ok(None), Err(anyhow::anyhow!("connection reset")), Ok(None)]);
+        let landed = find_landed_deploy(&landed_miner(), &statuses, &Pubkey::default(), CHECK, &three_attempts()).await;
+        assert_eq!(landed, Some(LandedDeploy { signature: None, fee: 7_000 }));
+    }
+
+    #[tokio::test]
+    async fn deploy_not_on_the_miner_is_sent_again() {
+        let statuses = ScriptedStatuses::new(vec![]);
+        let attempts = three_attempts();
+        for miners in [StubMiner(Ok(None)), StubMiner(Ok(Some(miner_on(42, &[(3, 1_000_000)])))), StubMiner(Err("timed out".into()))] {
+            assert_eq!(find_landed_deploy(&miners, &statuses, &Pubkey::default(), CHECK, &attempts).await, None);
+        }
+        // Nothing sent yet: no lookup needed
+        assert_eq!(find_landed_deploy(&landed_miner(), &statuses, &Pubkey::default(), CHECK, &[]).await, None);
+        assert_eq!(statuses.polls.load(Ordering::Relaxed), 0);
     }
 }
@@ -1,5 +1,7 @@
 use rand::seq::SliceRandom;
-use rand::rng;
+use rand::{rng, RngCore};
+use crate::config::BlockSelection;
+use crate::ore::state::Round;
 
 pub const GRID_SIZE: usize = 5;
 pub const TOTAL_BLOCKS: usize = GRID_SIZE * GRID_SIZE; // 25
@@ -22,17 +24,221 @@ impl BlockPosition {
     }
 }
 
-/// Select blocks to bet on randomly
-pub fn select_blocks(count: u8) -> Vec<BlockPosition> {
-    let count = (count as usize).min(TOTAL_BLOCKS);
-    
-    let mut rng = rng();
-    let mut indices: Vec<u8> = (0..TOTAL_BLOCKS as u8).collect();
+/// Inputs available to a selector when picking squares for a round
+pub struct SelectionContext<'a> {
+    pub round: &'a Round,              // Current round (deployed totals per square)
+    pub previous_winner: Option<u8>,   // Winning square of the last round we played
+    pub rng: &'a mut dyn RngCore,
+}
+
+/// Strategy for picking which squares to bet on each round
+pub trait BlockSelector {
+    fn select(&self, count: u8, ctx: &mut SelectionContext) -> Vec<BlockPosition>;
+}
+
+/// Pick uniformly at random
+pub struct RandomSelector;
+
+impl BlockSelector for RandomSelector {
+    fn select(&self, count: u8, ctx: &mut SelectionContext) -> Vec<BlockPosition> {
+        pick_random((0..TOTAL_BLOCKS as u8).collect(), count, ctx.rng)
+    }
+}
+
+/// Pick the squares with the least SOL deployed this round (ties broken randomly)
+/// A win on a thin square pays more since the pot is split by deployment
+pub struct LeastDeployedSelector;
+
+impl BlockSelector for LeastDeployedSelector {
+    fn select(&self, count: u8, ctx: &mut SelectionContext) -> Vec<BlockPosition> {
+        let count = (count as usize).min(TOTAL_BLOCKS);
+        let mut indices: Vec<u8> = (0..TOTAL_BLOCKS as u8).collect();
+
+        // Shuffle first so the stable sort leaves equal squares in random order
+        indices.shuffle(ctx.rng);
+        indices.sort_by_key(|&i| ctx.round.deployed[i as usize]);
+
+        indices.into_iter()
+            .take(count)
+            .map(BlockPosition::from_index)
+            .collect()
+    }
+}
+
+/// Always bet on the same squares (validated to match `blocks_per_bet`)
+/// A smaller count (from `blocks_per_loss_level`) bets the first squares of the list
+pub struct FixedSelector {
+    pub squares: Vec<u8>,
+}
+
+impl BlockSelector for FixedSelector {
+    fn select(&self, count: u8, _ctx: &mut SelectionContext) -> Vec<BlockPosition> {
+        self.squares.iter().copied().take(count as usize).map(BlockPosition::from_index).collect()
+    }
+}
+
+/// Pick at random, never choosing the configured squares
+pub struct ExcludeSelector {
+    pub excluded: Vec<u8>,
+}
+
+impl BlockSelector for ExcludeSelector {
+    fn select(&self, count: u8, ctx: &mut SelectionContext) -> Vec<BlockPosition> {
+        let candidates = (0..TOTAL_BLOCKS as u8)
+            .filter(|i| !self.excluded.contains(i))
+            .collect();
+        pick_random(candidates, count, ctx.rng)
+    }
+}
+
+/// Pick at random, skipping the square that won the previous round
+pub struct AvoidLastWinnerSelector;
+
+impl BlockSelector for AvoidLastWinnerSelector {
+    fn select(&self, count: u8, ctx: &mut SelectionContext) -> Vec<BlockPosition> {
+        let candidates = (0..TOTAL_BLOCKS as u8)
+            .filter(|&i| Some(i) != ctx.previous_winner)
+            .collect();
+        pick_random(candidates, count, ctx.rng)
+    }
+}
+
+/// Pick at random among the corner and edge squares
+pub struct CornersEdgesSelector;
 
-    indices.shuffle(&mut rng);
+impl BlockSelector for CornersEdgesSelector {
+    fn select(&self, count: u8, ctx: &mut SelectionContext) -> Vec<BlockPosition> {
+        pick_random(perimeter_squares(), count, ctx.rng)
+    }
+}
 
-    indices.into_iter()
+/// Indices of the squares on the outer ring of the grid (4 corners + 12 edges)
+pub fn perimeter_squares() -> Vec<u8> {
+    (0..TOTAL_BLOCKS as u8)
+        .filter(|&i| {
+            let block = BlockPosition::from_index(i);
+            let last = GRID_SIZE as u8 - 1;
+            block.row == 0 || block.row == last || block.col == 0 || block.col == last
+        })
+        .collect()
+}
+
+/// Build the selector configured in `martingale.block_selection`
+pub fn build_selector(mode: &BlockSelection) -> Box<dyn BlockSelector + Send + Sync> {
+    match mode {
+        BlockSelection::Random => Box::new(RandomSelector),
+        BlockSelection::LeastDeployed => Box::new(LeastDeployedSelector),
+        BlockSelection::AvoidLastWinner => Box::new(AvoidLastWinnerSelector),
+        BlockSelection::CornersEdges => Box::new(CornersEdgesSelector),
+        BlockSelection::Fixed(squares) => Box::new(FixedSelector { squares: squares.clone() }),
+        BlockSelection::Exclude(squares) => Box::new(ExcludeSelector { excluded: squares.clone() }),
+    }
+}
+
+/// Shuffle the candidate indices and take up to `count` of them
+fn pick_random(mut candidates: Vec<u8>, count: u8, rng: &mut dyn RngCore) -> Vec<BlockPosition> {
+    let count = (count as usize).min(candidates.len());
+
+    candidates.shuffle(rng);
+
+    candidates.into_iter()
         .take(count)
         .map(BlockPosition::from_index)
         .collect()
 }
+
+/// Draw the grid as 5 lines of emoji: ⬜ empty, 🟦 our bet, 🟩 our winning bet, 🟥 winner we missed
+/// At most 25 emoji + 4 newlines, well inside Discord's 1024-character field limit
+pub fn render_grid(bet_indices: &[u8], winning: Option<u8>) -> String {
+    (0..GRID_SIZE as u8)
+        .map(|row| {
+            (0..GRID_SIZE as u8)
+                .map(|col| {
+                    let index = row * GRID_SIZE as u8 + col;
+                    match (bet_indices.contains(&index), winning == Some(index)) {
+                        (true, true) => "🟩",
+                        (true, false) => "🟦",
+                        (false, true) => "🟥",
+                        (false, false) => "⬜",
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Select blocks to bet on randomly
+pub fn select_blocks(count: u8) -> Vec<BlockPosition> {
+    pick_random((0..TOTAL_BLOCKS as u8).collect(), count, &mut rng())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn least_deployed(deployed: [u64; TOTAL_BLOCKS], count: u8, seed: u64) -> Vec<u8> {
+        let round = Round { deployed, ..Round::zeroed() };
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut ctx = SelectionContext { round: &round, previous_winner: None, rng: &mut rng };
+        LeastDeployedSelector.select(count, &mut ctx).iter().map(|b| b.index).collect()
+    }
+
+    #[test]
+    fn render_grid_winner_we_bet_on() {
+        let grid = render_grid(&[0, 6], Some(6));
+        assert_eq!(grid, "🟦⬜⬜⬜⬜\n⬜🟩⬜⬜⬜\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜⬜");
+    }
+
+    #[test]
+    fn render_grid_winner_we_missed() {
+        let grid = render_grid(&[0, 6], Some(24));
+        assert_eq!(grid, "🟦⬜⬜⬜⬜\n⬜🟦⬜⬜⬜\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜⬜\n⬜⬜⬜⬜🟥");
+        // Not settled yet: no winner drawn
+        assert!(!render_grid(&[0, 6], None).contains('🟥'));
+    }
+
+    #[test]
+    fn render_grid_fits_a_discord_field() {
+        let all: Vec<u8> = (0..TOTAL_BLOCKS as u8).collect();
+        let grid = render_grid(&all, Some(12));
+        assert_eq!(grid.lines().count(), GRID_SIZE);
+        assert_eq!(grid.chars().count(), TOTAL_BLOCKS + GRID_SIZE - 1);
+        assert!(grid.len() <= 1024);
+    }
+
+    #[test]
+    fn least_deployed_picks_the_thinnest_squares() {
+        let mut deployed = [0; TOTAL_BLOCKS];
+        for (square, amount) in deployed.iter_mut().enumerate() {
+            *amount = 1_000_000 + (TOTAL_BLOCKS - square) as u64 * 1_000; // Square 24 is the thinnest
+        }
+        assert_eq!(least_deployed(deployed, 3, 1), [24, 23, 22]);
+        // Asking for more than the grid caps at 25, thinnest first
+        let all = least_deployed(deployed, 30, 1);
+        assert_eq!(all, (0..TOTAL_BLOCKS as u8).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn least_deployed_breaks_ties_at_random() {
+        // Squares 4, 9 and 14 tie for the minimum; we only take two of them
+        let mut deployed = [5_000_000; TOTAL_BLOCKS];
+        for square in [4, 9, 14] {
+            deployed[square] = 1_000;
+        }
+        let mut seen = std::collections::HashSet::new();
+        for seed in 0..50 {
+            let picked = least_deployed(deployed, 2, seed);
+            assert_eq!(picked.len(), 2);
+            assert!(picked.iter().all(|square| [4, 9, 14].contains(square)), "{:?}", picked);
+            assert_ne!(picked[0], picked[1]);
+            seen.insert(picked);
+        }
+        // Ties aren't always settled by index: the highest tied square gets picked too
+        assert!(seen.len() > 1);
+        assert!(seen.iter().any(|picked| picked.contains(&14)));
+    }
+}
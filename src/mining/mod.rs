@@ -1,3 +1,4 @@
+pub mod ev;
 pub mod executor;
 pub mod grid;
 pub mod strategy;
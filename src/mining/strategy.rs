@@ -1,5 +1,146 @@
-use crate::config::MartingaleConfig;
-use serde::{Serialize, Deserialize};
+use crate::config::{scale_lamports, BaseBetMode, MartingaleConfig, StrategyKind, WinRateGuardConfig};
+use crate::mining::grid::TOTAL_BLOCKS;
+use serde::{de::DeserializeOwned, Serialize, Deserialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Betting progression driven by the main loop
+///
+/// Every strategy keeps its round/cycle bookkeeping in a `MartingaleState`
+/// (exposed via `state()`); implementations only decide how the bet per block
+/// moves after a win or a loss.
+pub trait BettingStrategy {
+    /// Bet per block (lamports) for the next round
+    fn next_bet(&self) -> u64 {
+        self.state().current_bet_per_block
+    }
+
+    /// Called immediately when a round is won
+    fn on_win(&mut self, config: &MartingaleConfig);
+
+    /// Called when a round is lost
+    /// Returns (should_continue, should_warn)
+    fn on_loss(&mut self, config: &MartingaleConfig) -> (bool, bool);
+
+    /// Back to the base bet with a fresh streak, as after max losses
+    /// Strategies with their own progression position clear it too
+    fn reset(&mut self, config: &MartingaleConfig) {
+        self.state_mut().reset(config);
+        self.persist();
+    }
+
+    /// Shared round/cycle bookkeeping
+    fn state(&self) -> &MartingaleState;
+
+    fn state_mut(&mut self) -> &mut MartingaleState;
+
+    /// Serialize the full strategy state (including any strategy-specific fields)
+    fn to_json(&self) -> serde_json::Result<String>;
+
+    /// Record bet placement
+    fn record_bet(&mut self, round_id: u64, total_bet: u64) {
+        self.state_mut().record_bet(round_id, total_bet);
+        self.persist();
+    }
+
+    /// Update earnings after rewards are confirmed (called asynchronously)
+    fn update_earnings(&mut self, ore_reward: u64, sol_reward: u64) {
+        self.state_mut().update_earnings(ore_reward, sol_reward);
+        self.persist();
+    }
+
+    /// Record transaction fees paid (bets, claims, and transactions that landed but failed)
+    fn record_fees(&mut self, fees: u64) {
+        if fees > 0 {
+            self.state_mut().record_fees(fees);
+            self.persist();
+        }
+    }
+
+    /// Write the state to `path` atomically (temp file + rename) so a crash mid-write can't corrupt it
+    fn save_to_file(&self, path: &Path) -> anyhow::Result<()> {
+        let json = self.to_json()?;
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Write the state to the state file (no-op when persistence is disabled)
+    fn persist(&self) {
+        if let Some(path) = &self.state().state_file {
+            if let Err(e) = self.save_to_file(path) {
+                log::warn!("⚠️ Failed to write state file {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Build the betting strategy selected in config, restoring persisted state from `state_file` if present
+pub fn build_strategy(config: &MartingaleConfig, state_file: &Path) -> Box<dyn BettingStrategy + Send> {
+    let mut strategy: Box<dyn BettingStrategy + Send> = match config.strategy {
+        StrategyKind::Martingale => Box::new(
+            load_state::<MartingaleState>(state_file)
+                .unwrap_or_else(|| MartingaleState::new(config.base_bet_lamports())),
+        ),
+        StrategyKind::Fibonacci => Box::new(
+            load_state::<FibonacciState>(state_file)
+                .unwrap_or_else(|| FibonacciState::new(config.base_bet_lamports())),
+        ),
+        StrategyKind::Paroli => Box::new(
+            load_state::<ParoliState>(state_file)
+                .unwrap_or_else(|| ParoliState::new(config.base_bet_lamports())),
+        ),
+        StrategyKind::Custom => Box::new(
+            load_state::<CustomState>(state_file)
+                .unwrap_or_else(|| CustomState::new(config.base_bet_lamports())),
+        ),
+        StrategyKind::Flat => Box::new(
+            load_state::<FlatState>(state_file)
+                .unwrap_or_else(|| FlatState::new(config.base_bet_lamports())),
+        ),
+    };
+
+    strategy.state_mut().state_file = Some(state_file.to_path_buf());
+    strategy
+}
+
+/// Build a fresh strategy with no state file (nothing is restored or persisted)
+pub fn new_strategy(config: &MartingaleConfig) -> Box<dyn BettingStrategy + Send> {
+    let base_bet = config.base_bet_lamports();
+    match config.strategy {
+        StrategyKind::Martingale => Box::new(MartingaleState::new(base_bet)),
+        StrategyKind::Fibonacci => Box::new(FibonacciState::new(base_bet)),
+        StrategyKind::Paroli => Box::new(ParoliState::new(base_bet)),
+        StrategyKind::Custom => Box::new(CustomState::new(base_bet)),
+        StrategyKind::Flat => Box::new(FlatState::new(base_bet)),
+    }
+}
+
+/// Read a persisted strategy state from `path`
+pub fn load_from_file<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Load persisted state, returning None if the file is missing, corrupt, or from an incompatible schema
+fn load_state<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    if !path.exists() {
+        log::info!("📂 No state file at {}, starting fresh", path.display());
+        return None;
+    }
+
+    match load_from_file(path) {
+        Ok(state) => {
+            log::info!("📂 Restored strategy state from {}", path.display());
+            Some(state)
+        }
+        Err(e) => {
+            log::warn!("⚠️ State file {} is unreadable, corrupt or incompatible: {}. Starting fresh", path.display(), e);
+            None
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MartingaleState {
@@ -13,6 +154,58 @@ pub struct MartingaleState {
     pub last_win_time: Option<i64>,
     pub win_count: u32,
     pub loss_count: u32,
+    #[serde(default)]
+    pub total_fees_lamports: u64,        // Transaction fees paid (base + priority), including failed transactions
+    #[serde(default)]
+    pub current_cycle_fees_lamports: u64, // Fees paid in the current cycle (resets on win)
+    #[serde(default)]
+    pub uncapped_bet_per_block: Option<u64>, // Progression bet before max_bet_per_block_sol was applied (None = not capped)
+    #[serde(default)]
+    pub cycle_base_bet: Option<u64>,     // Base bet fixed at the start of the current cycle (percent sizing)
+    #[serde(default)]
+    pub balance_lamports: Option<u64>,   // Latest wallet balance snapshot, used to size the next cycle
+    #[serde(default)]
+    pub last_winning_square: Option<u8>, // Winning square of the last settled round (for block selection)
+    #[serde(default)]
+    pub day_start_timestamp: i64,        // Start (00:00 UTC) of the day `day_net_lamports` covers
+    #[serde(default)]
+    pub day_net_lamports: i64,           // Net SOL result for the current UTC day (earned - bet)
+    #[serde(default)]
+    pub daily_limit_notified: bool,      // Betting paused by a daily limit (notice already sent today)
+    #[serde(default)]
+    pub daily_stats: DailyStats,         // Counters for the daily digest (reset when it is sent)
+    #[serde(default)]
+    pub last_bet_round: Option<u64>,     // Round of the last bet counted here (a resumed bet not counted yet is added)
+    #[serde(default)]
+    pub last_bet_time: Option<i64>,      // When the last bet was placed (for the heartbeat)
+    #[serde(default)]
+    pub square_stats: SquareStats,       // Winning square distribution and how our picks overlap it
+    #[serde(default)]
+    pub round_history: RoundHistory,     // Last `rolling_window_rounds` settled rounds, oldest first
+    #[serde(default)]
+    pub max_consecutive_losses_ever: u8,
+    #[serde(default)]
+    pub top_miner_wins: u32,             // Rounds where we were the round's top miner
+    #[serde(default)]
+    pub top_miner_ore: u64,              // ORE from those top miner rewards (already part of total_earned_ore)
+    #[serde(default)]
+    pub win_streak: u32,                 // Wins in a row so far (reset by a loss)
+    #[serde(default)]
+    pub max_win_streak_ever: u32,
+    #[serde(default)]
+    pub peak_net_lamports: i64,          // Highest net profit reached (starts at 0)
+    #[serde(default)]
+    pub max_drawdown_lamports: u64,      // Largest drop from a net profit peak
+    #[serde(default)]
+    pub cooldown_until_round: Option<u64>, // Round-based cooldown: no bets up to and including this round
+    #[serde(default)]
+    pub win_rate_guard_tripped: bool,    // Win-rate guard active (notice already sent)
+    #[serde(default)]
+    pub win_rate_guard_until_round: Option<u64>, // Guard skip: no bets up to and including this round, and only later rounds count
+    #[serde(skip)]
+    pub ev_skipped_round: Option<u64>,   // Round skipped by the EV guard (not re-evaluated until the next round)
+    #[serde(skip)]
+    pub state_file: Option<PathBuf>,   // Where the state is persisted (None = persistence off)
 }
 
 impl MartingaleState {
@@ -28,6 +221,32 @@ impl MartingaleState {
             last_win_time: None,
             win_count: 0,
             loss_count: 0,
+            total_fees_lamports: 0,
+            current_cycle_fees_lamports: 0,
+            uncapped_bet_per_block: None,
+            cycle_base_bet: None,
+            balance_lamports: None,
+            last_winning_square: None,
+            day_start_timestamp: 0,
+            day_net_lamports: 0,
+            daily_limit_notified: false,
+            daily_stats: DailyStats::default(),
+            last_bet_round: None,
+            last_bet_time: None,
+            square_stats: SquareStats::default(),
+            round_history: RoundHistory::default(),
+            max_consecutive_losses_ever: 0,
+            top_miner_wins: 0,
+            top_miner_ore: 0,
+            win_streak: 0,
+            max_win_streak_ever: 0,
+            peak_net_lamports: 0,
+            max_drawdown_lamports: 0,
+            cooldown_until_round: None,
+            win_rate_guard_tripped: false,
+            win_rate_guard_until_round: None,
+            ev_skipped_round: None,
+            state_file: None,
         }
     }
 
@@ -36,30 +255,138 @@ impl MartingaleState {
         log::info!("📊 Updating earnings: ORE: {}, SOL: {}", ore_reward, sol_reward);
         self.total_earned_ore += ore_reward;
         self.total_earned_sol += sol_reward;
+        self.daily_stats.sol_earned += sol_reward;
+        self.daily_stats.ore_earned += ore_reward;
+        self.update_drawdown();
+
+        self.roll_day(chrono::Utc::now().timestamp());
+        self.day_net_lamports += sol_reward as i64;
+    }
+
+    /// Start a fresh daily counter once the UTC day changes
+    /// Only the daily figures reset; the martingale cycle carries over midnight
+    pub fn roll_day(&mut self, now: i64) {
+        let today = utc_day_start(now);
+        if today != self.day_start_timestamp {
+            if self.day_start_timestamp != 0 {
+                log::info!("📅 New UTC day: yesterday's net {:+.6} SOL", self.day_net_lamports as f64 / 1e9);
+            }
+            self.day_start_timestamp = today;
+            self.day_net_lamports = 0;
+            self.daily_limit_notified = false;
+        }
+    }
+
+    /// Which daily limit (if any) today's net result has reached
+    pub fn daily_limit_hit(&mut self, loss_limit: Option<u64>, profit_target: Option<u64>) -> Option<DailyLimit> {
+        self.roll_day(chrono::Utc::now().timestamp());
+        match (loss_limit, profit_target) {
+            (Some(limit), _) if self.day_net_lamports <= -(limit as i64) => Some(DailyLimit::Loss(limit)),
+            (_, Some(target)) if self.day_net_lamports >= target as i64 => Some(DailyLimit::ProfitTarget(target)),
+            _ => None,
+        }
     }
 
     /// Reset martingale cycle (called immediately on win)
     pub fn reset_after_win(&mut self, config: &MartingaleConfig) {
         self.consecutive_losses = 0;
         self.current_cycle_bet_lamports = 0;
+        self.current_cycle_fees_lamports = 0;
         self.last_win_time = Some(chrono::Utc::now().timestamp());
         self.win_count += 1;
-        self.current_bet_per_block = config.base_bet_lamports();
+        self.daily_stats.wins += 1;
+        self.win_streak += 1;
+        self.max_win_streak_ever = self.max_win_streak_ever.max(self.win_streak);
+        self.start_cycle(config);
     }
 
-    /// Called when losing a round
-    /// Returns (should_continue, should_warn)
-    pub fn on_loss(&mut self, config: &MartingaleConfig) -> (bool, bool) {
+    /// Base bet of the current cycle
+    pub fn base_bet(&self, config: &MartingaleConfig) -> u64 {
+        self.cycle_base_bet.unwrap_or_else(|| config.base_bet_lamports())
+    }
+
+    /// Fix the base bet for a new cycle from the latest balance snapshot and go back to it
+    fn start_cycle(&mut self, config: &MartingaleConfig) {
+        let base = config.base_bet_for_balance(self.balance_lamports);
+        if config.base_bet_mode == BaseBetMode::Percent {
+            log::info!("📏 New cycle base bet: {:.6} SOL per block ({}% of {:.6} SOL)",
+                base as f64 / 1e9,
+                config.base_bet_percent,
+                self.balance_lamports.unwrap_or(0) as f64 / 1e9);
+        }
+        self.cycle_base_bet = Some(base);
+        self.current_bet_per_block = base;
+        self.uncapped_bet_per_block = None;
+    }
+
+    /// Store the wallet balance fetched before a bet
+    /// In percent mode the very first cycle is sized here, since no reset has happened yet
+    pub fn update_balance(&mut self, config: &MartingaleConfig, balance: u64) {
+        self.balance_lamports = Some(balance);
+        if config.base_bet_mode == BaseBetMode::Percent
+            && self.cycle_base_bet.is_none()
+            && self.consecutive_losses == 0
+        {
+            self.start_cycle(config);
+        }
+    }
+
+    /// Set the next bet, raised to the minimum bet and clamped to `max_bet_per_block_sol` if configured
+    /// The uncapped value is kept so the progression continues from it
+    pub fn set_bet(&mut self, config: &MartingaleConfig, bet: u64) {
+        let floor = config.min_bet_per_block_lamports();
+        if bet < floor {
+            log::debug!("Bet {:.9} SOL is below the minimum, raising to {:.9} SOL per block",
+                bet as f64 / 1e9,
+                floor as f64 / 1e9);
+        }
+        let bet = bet.max(floor);
+        match config.max_bet_per_block_lamports() {
+            Some(cap) if bet > cap => {
+                log::warn!("🧢 Bet {:.6} SOL exceeds cap, clamping to {:.6} SOL per block",
+                    bet as f64 / 1e9,
+                    cap as f64 / 1e9);
+                self.current_bet_per_block = cap;
+                self.uncapped_bet_per_block = Some(bet);
+            }
+            _ => {
+                self.current_bet_per_block = bet;
+                self.uncapped_bet_per_block = None;
+            }
+        }
+    }
+
+    /// Bet the progression calls for, ignoring the cap
+    pub fn theoretical_bet(&self) -> u64 {
+        self.uncapped_bet_per_block.unwrap_or(self.current_bet_per_block)
+    }
+
+    /// Count a lost round without touching the bet size
+    /// Returns (max_losses_reached, should_warn)
+    pub fn register_loss(&mut self, config: &MartingaleConfig) -> (bool, bool) {
         log::warn!("❌ LOST Round #{}", self.consecutive_losses + 1);
 
-        self.consecutive_losses += 1;
+        self.consecutive_losses = self.consecutive_losses.saturating_add(1); // Flat betting can run past max
         self.loss_count += 1;
+        self.daily_stats.losses += 1;
+        self.daily_stats.max_loss_streak = self.daily_stats.max_loss_streak.max(self.consecutive_losses);
+        self.max_consecutive_losses_ever = self.max_consecutive_losses_ever.max(self.consecutive_losses);
+        self.win_streak = 0;
+        self.update_drawdown();
 
         // Check if warning threshold reached or exceeded
         let should_warn = self.consecutive_losses >= config.warn_consecutive_losses;
 
+        (self.consecutive_losses >= config.max_consecutive_losses, should_warn)
+    }
+
+    /// Called when losing a round
+    /// Returns (should_continue, should_warn)
+    pub fn on_loss(&mut self, config: &MartingaleConfig) -> (bool, bool) {
+        let (max_reached, should_warn) = self.register_loss(config);
+
         // Check if max consecutive losses reached
-        if self.consecutive_losses >= config.max_consecutive_losses {
+        if max_reached {
             log::error!("🛑 Max consecutive losses reached. Resetting bet.");
             self.reset(config);
             return (false, should_warn); // Don't continue, signal warning
@@ -67,13 +394,12 @@ impl MartingaleState {
 
         // Apply martingale: multiply bet by configured multiplier
         let multiplier = config.multiplier;
-        let old_bet = self.current_bet_per_block;
-        
-        // Use f64 for precise calculation, then round to nearest lamport
-        let new_bet_f64 = (old_bet as f64) * multiplier;
-        let new_bet = new_bet_f64.round() as u64;
-                
-        self.current_bet_per_block = new_bet;
+        let old_bet = self.theoretical_bet();
+
+        // Integer lamport math, rounded half up (see scale_lamports)
+        let new_bet = scale_lamports(old_bet, multiplier);
+
+        self.set_bet(config, new_bet);
 
         log::info!(
             "📈 Martingale: Multiplying bet by {:.2}x: {:.6} → {:.6} SOL",
@@ -86,19 +412,114 @@ impl MartingaleState {
     }
 
     /// Record bet placement
-    pub fn record_bet(&mut self, total_bet: u64) {
+    pub fn record_bet(&mut self, round_id: u64, total_bet: u64) {
+        self.last_bet_round = Some(round_id);
         self.total_bet_lamports += total_bet;
         self.current_cycle_bet_lamports += total_bet;
+        self.last_bet_time = Some(chrono::Utc::now().timestamp());
+        self.daily_stats.rounds += 1;
+        self.daily_stats.sol_bet += total_bet;
+        self.daily_stats.largest_bet_per_block = self.daily_stats.largest_bet_per_block.max(self.current_bet_per_block);
+
+        self.roll_day(chrono::Utc::now().timestamp());
+        self.day_net_lamports -= total_bet as i64;
+    }
+
+    /// Record transaction fees; they count against today's net like a bet
+    pub fn record_fees(&mut self, fees: u64) {
+        self.total_fees_lamports += fees;
+        self.current_cycle_fees_lamports += fees;
+        self.daily_stats.fees_paid += fees;
+
+        self.roll_day(chrono::Utc::now().timestamp());
+        self.day_net_lamports -= fees as i64;
     }
 
     pub fn reset(&mut self, config: &MartingaleConfig) {
         self.consecutive_losses = 0;
-        self.current_bet_per_block = config.base_bet_lamports();
+        self.start_cycle(config);
         self.current_cycle_bet_lamports = 0; // Reset cycle bet on reset
+        self.current_cycle_fees_lamports = 0;
     }
 
+    /// SOL earned minus SOL bet and transaction fees
     pub fn net_profit_sol(&self) -> i64 {
-        (self.total_earned_sol as i64) - (self.total_bet_lamports as i64)
+        (self.total_earned_sol as i64) - (self.total_bet_lamports as i64) - (self.total_fees_lamports as i64)
+    }
+
+    /// Net profit (after fees) as a percentage of all SOL staked; None before the first bet
+    pub fn roi_percent(&self) -> Option<f64> {
+        (self.total_bet_lamports > 0)
+            .then(|| self.net_profit_sol() as f64 / self.total_bet_lamports as f64 * 100.0)
+    }
+
+    /// Realized edge of the game itself: SOL returned per SOL staked, minus 1, in percent
+    /// Fees and ORE rewards are left out, so a negative value reads as the house edge we've paid
+    pub fn edge_percent(&self) -> Option<f64> {
+        (self.total_bet_lamports > 0)
+            .then(|| (self.total_earned_sol as f64 / self.total_bet_lamports as f64 - 1.0) * 100.0)
+    }
+
+    /// Change the rolling window size, trimming older rounds if it shrank
+    pub fn set_rolling_window(&mut self, window: usize) {
+        self.round_history.set_capacity(window);
+    }
+
+    /// Win rate (%) over the rolling window and the number of rounds it covers
+    pub fn rolling_win_rate(&self) -> Option<(f64, usize)> {
+        self.round_history.win_rate(usize::MAX)
+    }
+
+    /// Blocks to bet at the current loss level
+    pub fn blocks_per_bet(&self, config: &MartingaleConfig) -> u8 {
+        config.blocks_for_losses(self.consecutive_losses)
+    }
+
+    /// Win rate (%) over the guard window if it's below the floor
+    /// None while above it or until a full window has been played since the last guard skip
+    pub fn win_rate_below_floor(&self, guard: &WinRateGuardConfig) -> Option<f64> {
+        let (rate, rounds) = self.round_history.win_rate_after(guard.window_rounds, self.win_rate_guard_until_round)?;
+        (rounds == guard.window_rounds && rate < guard.min_win_rate).then_some(rate)
+    }
+
+    /// Track the net profit peak and the largest drop from it
+    /// Called when a round settles (loss counted, or win credited) so an open bet isn't counted as a drop
+    fn update_drawdown(&mut self) {
+        let net = self.net_profit_sol();
+        self.peak_net_lamports = self.peak_net_lamports.max(net);
+        self.max_drawdown_lamports = self.max_drawdown_lamports.max(self.current_drawdown());
+    }
+
+    /// Distance (lamports) of the current net profit below its peak
+    pub fn current_drawdown(&self) -> u64 {
+        (self.peak_net_lamports - self.net_profit_sol()).max(0) as u64
+    }
+
+    /// Totals across several wallets' states, for the all-wallets stats
+    /// Counts and amounts add up; streaks and the worst drawdown are the largest of any wallet.
+    /// The peak is the sum of the wallets' peaks, so the current drawdown is the sum of theirs.
+    pub fn combined<'a>(states: impl IntoIterator<Item = &'a MartingaleState>) -> MartingaleState {
+        let mut total = MartingaleState::new(0);
+        for state in states {
+            total.win_count += state.win_count;
+            total.loss_count += state.loss_count;
+            total.total_bet_lamports += state.total_bet_lamports;
+            total.total_earned_sol += state.total_earned_sol;
+            total.total_earned_ore += state.total_earned_ore;
+            total.total_fees_lamports += state.total_fees_lamports;
+            total.top_miner_wins += state.top_miner_wins;
+            total.top_miner_ore += state.top_miner_ore;
+            total.peak_net_lamports += state.peak_net_lamports;
+            total.max_drawdown_lamports = total.max_drawdown_lamports.max(state.max_drawdown_lamports);
+            total.max_win_streak_ever = total.max_win_streak_ever.max(state.max_win_streak_ever);
+            total.max_consecutive_losses_ever = total.max_consecutive_losses_ever.max(state.max_consecutive_losses_ever);
+            for square in 0..TOTAL_BLOCKS {
+                total.square_stats.wins[square] += state.square_stats.wins[square];
+                total.square_stats.bets[square] += state.square_stats.bets[square];
+                total.square_stats.our_wins[square] += state.square_stats.our_wins[square];
+            }
+        }
+        total
     }
 
     pub fn win_rate(&self) -> f64 {
@@ -109,3 +530,945 @@ impl MartingaleState {
         (self.win_count as f64 / total_rounds as f64) * 100.0
     }
 }
+
+/// Classic martingale: multiply the bet on every loss, back to base on a win
+impl BettingStrategy for MartingaleState {
+    fn on_win(&mut self, config: &MartingaleConfig) {
+        self.reset_after_win(config);
+        self.persist();
+    }
+
+    fn on_loss(&mut self, config: &MartingaleConfig) -> (bool, bool) {
+        let result = MartingaleState::on_loss(self, config);
+        self.persist();
+        result
+    }
+
+    fn state(&self) -> &MartingaleState {
+        self
+    }
+
+    fn state_mut(&mut self) -> &mut MartingaleState {
+        self
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Activity since the last daily digest
+/// Kept in the state file so a restart doesn't lose the day's figures
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyStats {
+    pub since: i64,                     // When these counters started (0 = never reset)
+    pub rounds: u32,                    // Rounds we placed a bet in
+    pub wins: u32,
+    pub losses: u32,
+    pub sol_bet: u64,                   // Lamports deployed
+    pub sol_earned: u64,                // Lamports credited from wins
+    pub ore_earned: u64,
+    pub fees_paid: u64,                 // Transaction fees (lamports)
+    pub largest_bet_per_block: u64,     // Highest bet per block reached (lamports)
+    pub max_loss_streak: u8,
+    pub last_summary_at: Option<i64>,   // When the last digest was sent
+}
+
+impl DailyStats {
+    /// Net SOL result (lamports) over the period, after fees
+    pub fn net_lamports(&self) -> i64 {
+        self.sol_earned as i64 - self.sol_bet as i64 - self.fees_paid as i64
+    }
+
+    /// Start a new period after a digest was sent at `now`
+    pub fn reset(&mut self, now: i64) {
+        *self = Self {
+            since: now,
+            last_summary_at: Some(now),
+            ..Default::default()
+        };
+    }
+}
+
+/// One settled round we bet in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RoundEntry {
+    pub round_id: u64,
+    pub winning_square: u8,
+    pub won: bool,
+    pub net: i64,   // SOL earned - bet - fee, in lamports
+}
+
+/// The last `capacity` settled rounds, oldest first, for rolling stats and adaptive strategies
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoundHistory {
+    entries: VecDeque<RoundEntry>,
+    #[serde(skip)]
+    capacity: usize, // Set from config (0 = not tracked)
+}
+
+impl RoundHistory {
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.trim();
+    }
+
+    pub fn push(&mut self, entry: RoundEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.push_back(entry);
+        self.trim();
+    }
+
+    /// Credit a win's SOL once the rewards are known (the entry is pushed with just the stake and fee)
+    pub fn add_earnings(&mut self, round_id: u64, sol_earned: u64) {
+        if let Some(entry) = self.entries.iter_mut().rev().find(|e| e.round_id == round_id) {
+            entry.net += sol_earned as i64;
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &RoundEntry> {
+        self.entries.iter()
+    }
+
+    /// Win rate (%) over the last `n` rounds and how many rounds that actually covered
+    pub fn win_rate(&self, n: usize) -> Option<(f64, usize)> {
+        self.win_rate_after(n, None)
+    }
+
+    /// Same as `win_rate`, counting only rounds after `after_round`
+    pub fn win_rate_after(&self, n: usize, after_round: Option<u64>) -> Option<(f64, usize)> {
+        let recent = || self.entries.iter().rev()
+            .take_while(|e| after_round.is_none_or(|after| e.round_id > after))
+            .take(n);
+        let rounds = recent().count();
+        if rounds == 0 {
+            return None;
+        }
+        let wins = recent().filter(|e| e.won).count();
+        Some((wins as f64 / rounds as f64 * 100.0, rounds))
+    }
+
+    /// Net result (lamports) of the last `n` rounds
+    pub fn net(&self, n: usize) -> i64 {
+        self.entries.iter().rev().take(n).map(|e| e.net).sum()
+    }
+}
+
+/// Per-square counts over every round we bet in
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SquareStats {
+    pub wins: [u32; TOTAL_BLOCKS],      // Times each square was the winning square
+    pub bets: [u32; TOTAL_BLOCKS],      // Times we bet on each square
+    pub our_wins: [u32; TOTAL_BLOCKS],  // Times a square we bet on won
+}
+
+impl SquareStats {
+    /// Count one settled round (`won` is the verified result, which may come from the on-chain Miner)
+    pub fn record(&mut self, bet_squares: &[u8], winning_square: u8, won: bool) {
+        let winning = winning_square as usize;
+        if winning >= TOTAL_BLOCKS {
+            return;
+        }
+        self.wins[winning] += 1;
+        for &square in bet_squares.iter().filter(|&&s| (s as usize) < TOTAL_BLOCKS) {
+            self.bets[square as usize] += 1;
+        }
+        if won {
+            self.our_wins[winning] += 1;
+        }
+    }
+
+    /// Rounds recorded
+    pub fn rounds(&self) -> u32 {
+        self.wins.iter().sum()
+    }
+
+    /// The `n` squares that won most often as (square, wins), most frequent first
+    pub fn top_winners(&self, n: usize) -> Vec<(u8, u32)> {
+        let mut squares: Vec<(u8, u32)> = self.wins.iter()
+            .enumerate()
+            .map(|(square, &wins)| (square as u8, wins))
+            .filter(|&(_, wins)| wins > 0)
+            .collect();
+        squares.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        squares.truncate(n);
+        squares
+    }
+
+    /// Pearson chi-square statistic of the winning squares against a uniform distribution
+    /// (24 degrees of freedom; values above ~36.4 happen by chance only 5% of the time)
+    pub fn chi_square(&self) -> Option<f64> {
+        let rounds = self.rounds();
+        if rounds == 0 {
+            return None;
+        }
+        let expected = rounds as f64 / TOTAL_BLOCKS as f64;
+        Some(self.wins.iter()
+            .map(|&observed| (observed as f64 - expected).powi(2) / expected)
+            .sum())
+    }
+}
+
+/// Daily limit that pauses betting until the next UTC day (value in lamports)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DailyLimit {
+    Loss(u64),
+    ProfitTarget(u64),
+}
+
+/// Timestamp of 00:00 UTC on the day containing `timestamp`
+pub fn utc_day_start(timestamp: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(86_400)
+}
+
+/// Next `hour`:00 UTC strictly after `now`
+pub fn next_utc_hour(now: i64, hour: u8) -> i64 {
+    let today = utc_day_start(now) + hour as i64 * 3600;
+    if today > now { today } else { today + 86_400 }
+}
+
+/// n-th Fibonacci number of the betting sequence (1, 1, 2, 3, 5, 8, ...)
+pub fn fibonacci(n: usize) -> u64 {
+    let (mut a, mut b) = (1u64, 1u64);
+    for _ in 0..n {
+        let next = a.saturating_add(b);
+        a = b;
+        b = next;
+    }
+    a
+}
+
+/// Custom progression: base bet times the sequence entry for `losses` losses in a row,
+/// staying on the last entry once the streak outruns the sequence
+pub fn custom_bet(config: &MartingaleConfig, base_bet: u64, losses: usize) -> u64 {
+    let Some(last) = config.custom_sequence.len().checked_sub(1) else {
+        return base_bet;
+    };
+    if losses == 0 {
+        return base_bet;
+    }
+    scale_lamports(base_bet, config.custom_sequence[(losses - 1).min(last)])
+}
+
+/// Bet per block at each step of a losing streak that starts at `base_bet`
+/// Entry `i` is the bet placed after `i` losses in a row, following the configured strategy
+/// between the minimum bet and `max_bet_per_block_sol`; the last entry is the bet that reaches `max_consecutive_losses`
+pub fn loss_ladder(config: &MartingaleConfig, base_bet: u64) -> Vec<u64> {
+    let cap = config.max_bet_per_block_lamports();
+    let floor = config.min_bet_per_block_lamports();
+    let mut uncapped = base_bet;
+    let mut ladder = Vec::with_capacity(config.max_consecutive_losses as usize);
+
+    for losses in 0..config.max_consecutive_losses as usize {
+        if losses > 0 {
+            uncapped = match config.strategy {
+                StrategyKind::Martingale => scale_lamports(uncapped, config.multiplier),
+                StrategyKind::Fibonacci => base_bet.saturating_mul(fibonacci(losses)),
+                StrategyKind::Paroli | StrategyKind::Flat => base_bet, // Losses never escalate
+                StrategyKind::Custom => custom_bet(config, base_bet, losses),
+            };
+        }
+        let bet = uncapped.max(floor);
+        ladder.push(cap.map_or(bet, |cap| bet.min(cap)));
+    }
+    ladder
+}
+
+/// Total SOL (lamports) staked over a full losing streak of `max_consecutive_losses` rounds
+pub fn worst_case_stake(config: &MartingaleConfig, base_bet: u64) -> u64 {
+    loss_ladder(config, base_bet)
+        .iter()
+        .enumerate()
+        .fold(0u64, |total, (losses, bet)| {
+            let blocks = config.blocks_for_losses(losses as u8) as u64;
+            total.saturating_add(bet.saturating_mul(blocks))
+        })
+}
+
+/// What a full losing streak of `max_consecutive_losses` rounds takes out of the wallet
+#[derive(Debug, Clone)]
+pub struct BankrollRequirement {
+    pub ladder: Vec<u64>,  // Bet per block at each loss level (see `loss_ladder`)
+    pub stake: u64,        // All bets of the streak
+    pub fees: u64,         // Estimated transaction fees of those bets
+    pub reserve: u64,      // Balance kept untouched (min_balance_sol)
+}
+
+impl BankrollRequirement {
+    pub fn total(&self) -> u64 {
+        self.stake.saturating_add(self.fees).saturating_add(self.reserve)
+    }
+}
+
+/// Bankroll needed to survive the worst case: the full ladder, one fee per bet, and the reserve
+pub fn bankroll_requirement(config: &MartingaleConfig, base_bet: u64, fee_per_bet: u64, reserve: u64) -> BankrollRequirement {
+    let ladder = loss_ladder(config, base_bet);
+    BankrollRequirement {
+        stake: worst_case_stake(config, base_bet),
+        fees: fee_per_bet.saturating_mul(ladder.len() as u64),
+        reserve,
+        ladder,
+    }
+}
+
+/// Fibonacci progression: one step forward on a loss, two steps back on a win
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FibonacciState {
+    #[serde(flatten)]
+    pub state: MartingaleState,
+    #[serde(default)]
+    pub fib_index: usize,  // Current position in the Fibonacci sequence
+}
+
+impl FibonacciState {
+    pub fn new(base_bet: u64) -> Self {
+        Self {
+            state: MartingaleState::new(base_bet),
+            fib_index: 0,
+        }
+    }
+
+    /// Recompute the bet from the base bet and the current sequence position
+    fn apply_index(&mut self, config: &MartingaleConfig) {
+        let bet = self.state.base_bet(config).saturating_mul(fibonacci(self.fib_index));
+        self.state.set_bet(config, bet);
+    }
+}
+
+impl BettingStrategy for FibonacciState {
+    fn on_win(&mut self, config: &MartingaleConfig) {
+        self.state.reset_after_win(config);
+        self.fib_index = self.fib_index.saturating_sub(2);
+        self.apply_index(config);
+
+        log::info!(
+            "📉 Fibonacci: stepping back to index {}: {:.6} SOL",
+            self.fib_index,
+            self.state.current_bet_per_block as f64 / 1e9
+        );
+        self.persist();
+    }
+
+    fn on_loss(&mut self, config: &MartingaleConfig) -> (bool, bool) {
+        let (max_reached, should_warn) = self.state.register_loss(config);
+
+        if max_reached {
+            log::error!("🛑 Max consecutive losses reached. Resetting bet.");
+            BettingStrategy::reset(self, config);
+            return (false, should_warn);
+        }
+
+        let old_bet = self.state.current_bet_per_block;
+        self.fib_index += 1;
+        self.apply_index(config);
+
+        log::info!(
+            "📈 Fibonacci: advancing to index {}: {:.6} → {:.6} SOL",
+            self.fib_index,
+            old_bet as f64 / 1e9,
+            self.state.current_bet_per_block as f64 / 1e9
+        );
+        self.persist();
+
+        (true, should_warn)
+    }
+
+    fn reset(&mut self, config: &MartingaleConfig) {
+        self.state.reset(config);
+        self.fib_index = 0;
+        self.persist();
+    }
+
+    fn state(&self) -> &MartingaleState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut MartingaleState {
+        &mut self.state
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Anti-martingale (Paroli): multiply the bet on each consecutive win up to
+/// `win_streak_cap`, back to base after a loss or once the cap is reached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParoliState {
+    #[serde(flatten)]
+    pub state: MartingaleState,
+    #[serde(default)]
+    pub consecutive_wins: u8,
+}
+
+impl ParoliState {
+    pub fn new(base_bet: u64) -> Self {
+        Self {
+            state: MartingaleState::new(base_bet),
+            consecutive_wins: 0,
+        }
+    }
+}
+
+impl BettingStrategy for ParoliState {
+    fn on_win(&mut self, config: &MartingaleConfig) {
+        let old_bet = self.state.theoretical_bet();
+        self.state.reset_after_win(config);
+        self.consecutive_wins += 1;
+
+        if self.consecutive_wins >= config.win_streak_cap {
+            log::info!("🏁 Paroli: win streak cap ({}) reached, back to base bet", config.win_streak_cap);
+            self.consecutive_wins = 0;
+        } else {
+            let new_bet = scale_lamports(old_bet, config.multiplier);
+            self.state.set_bet(config, new_bet);
+
+            log::info!(
+                "📈 Paroli: win #{} - multiplying bet by {:.2}x: {:.6} → {:.6} SOL",
+                self.consecutive_wins,
+                config.multiplier,
+                old_bet as f64 / 1e9,
+                new_bet as f64 / 1e9
+            );
+        }
+        self.persist();
+    }
+
+    fn on_loss(&mut self, config: &MartingaleConfig) -> (bool, bool) {
+        let (max_reached, should_warn) = self.state.register_loss(config);
+
+        // Losses never escalate the bet: back to base and start a new win streak
+        self.state.set_bet(config, self.state.base_bet(config));
+        self.consecutive_wins = 0;
+
+        if max_reached {
+            log::error!("🛑 Max consecutive losses reached. Resetting bet.");
+            self.state.reset(config);
+            self.persist();
+            return (false, should_warn);
+        }
+
+        self.persist();
+        (true, should_warn)
+    }
+
+    fn reset(&mut self, config: &MartingaleConfig) {
+        self.state.reset(config);
+        self.consecutive_wins = 0;
+        self.persist();
+    }
+
+    fn state(&self) -> &MartingaleState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut MartingaleState {
+        &mut self.state
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Custom progression: `custom_sequence` gives the bet after each loss as a multiple of the base bet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomState {
+    #[serde(flatten)]
+    pub state: MartingaleState,
+}
+
+impl CustomState {
+    pub fn new(base_bet: u64) -> Self {
+        Self {
+            state: MartingaleState::new(base_bet),
+        }
+    }
+}
+
+impl BettingStrategy for CustomState {
+    fn on_win(&mut self, config: &MartingaleConfig) {
+        self.state.reset_after_win(config);
+        self.persist();
+    }
+
+    fn on_loss(&mut self, config: &MartingaleConfig) -> (bool, bool) {
+        let (max_reached, should_warn) = self.state.register_loss(config);
+
+        if max_reached {
+            log::error!("🛑 Max consecutive losses reached. Resetting bet.");
+            self.state.reset(config);
+            self.persist();
+            return (false, should_warn);
+        }
+
+        let old_bet = self.state.theoretical_bet();
+        let losses = self.state.consecutive_losses as usize;
+        let new_bet = custom_bet(config, self.state.base_bet(config), losses);
+        self.state.set_bet(config, new_bet);
+
+        log::info!(
+            "📈 Custom: step {} of {}: {:.6} → {:.6} SOL",
+            losses.min(config.custom_sequence.len()),
+            config.custom_sequence.len(),
+            old_bet as f64 / 1e9,
+            new_bet as f64 / 1e9
+        );
+        self.persist();
+
+        (true, should_warn)
+    }
+
+    fn state(&self) -> &MartingaleState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut MartingaleState {
+        &mut self.state
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Flat betting: the base bet every round, whatever the streak
+///
+/// A "cycle" still runs from one win to the next for reporting. `max_consecutive_losses`
+/// is only a notice unless `stop_on_max_losses` is set, in which case `on_max_losses` applies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlatState {
+    #[serde(flatten)]
+    pub state: MartingaleState,
+}
+
+impl FlatState {
+    pub fn new(base_bet: u64) -> Self {
+        Self {
+            state: MartingaleState::new(base_bet),
+        }
+    }
+}
+
+impl BettingStrategy for FlatState {
+    fn on_win(&mut self, config: &MartingaleConfig) {
+        self.state.reset_after_win(config);
+        self.persist();
+    }
+
+    fn on_loss(&mut self, config: &MartingaleConfig) -> (bool, bool) {
+        let (max_reached, past_warn) = self.state.register_loss(config);
+
+        if max_reached && config.stop_on_max_losses {
+            log::error!("🛑 Max consecutive losses reached. Resetting streak.");
+            self.state.reset(config);
+            self.persist();
+            return (false, past_warn);
+        }
+
+        // The bet never moves, so warn once at each threshold instead of on every loss past it
+        let losses = self.state.consecutive_losses;
+        let should_warn = losses == config.warn_consecutive_losses || losses == config.max_consecutive_losses;
+        self.persist();
+        (true, should_warn)
+    }
+
+    fn state(&self) -> &MartingaleState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut MartingaleState {
+        &mut self.state
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Martingale settings with `overrides` applied on top of a 0.001 SOL base bet
+    fn config(overrides: serde_json::Value) -> MartingaleConfig {
+        let mut json = serde_json::json!({
+            "base_bet_amount": 0.001,
+            "max_consecutive_losses": 10,
+            "warn_consecutive_losses": 5,
+            "blocks_per_bet": 1,
+        });
+        json.as_object_mut().unwrap().extend(overrides.as_object().unwrap().clone());
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn custom_progression_over_eight_losses() {
+        let config = config(serde_json::json!({
+            "strategy": "custom",
+            "base_bet_amount": 0.001000003,
+            "custom_sequence": [1.5, 2, 3.5, 5, 8],
+        }));
+        let mut strategy = CustomState::new(config.base_bet_lamports());
+        assert_eq!(strategy.next_bet(), 1_000_003);
+
+        let mut bets = Vec::new();
+        for _ in 0..8 {
+            assert!(strategy.on_loss(&config).0);
+            bets.push(strategy.next_bet());
+        }
+        // Uneven products round half up; past the fifth loss the streak stays on the last entry
+        assert_eq!(bets, [1_500_005, 2_000_006, 3_500_011, 5_000_015, 8_000_024, 8_000_024, 8_000_024, 8_000_024]);
+
+        strategy.on_win(&config);
+        assert_eq!(strategy.next_bet(), 1_000_003);
+        assert_eq!(strategy.state().consecutive_losses, 0);
+    }
+
+    #[test]
+    fn custom_progression_resets_at_max_losses() {
+        let config = config(serde_json::json!({
+            "strategy": "custom",
+            "max_consecutive_losses": 3,
+            "warn_consecutive_losses": 2,
+            "custom_sequence": [2, 3],
+        }));
+        let mut strategy = CustomState::new(config.base_bet_lamports());
+        assert_eq!(strategy.on_loss(&config), (true, false));
+        assert_eq!(strategy.next_bet(), 2_000_000);
+        assert_eq!(strategy.on_loss(&config), (true, true));
+        assert_eq!(strategy.next_bet(), 3_000_000);
+        assert_eq!(strategy.on_loss(&config), (false, true));
+        assert_eq!(strategy.next_bet(), 1_000_000);
+    }
+
+    /// Bets after each of `losses` losses in a row
+    fn loss_walk(strategy: &mut dyn BettingStrategy, config: &MartingaleConfig, losses: usize) -> Vec<u64> {
+        (0..losses)
+            .map(|_| {
+                assert!(strategy.on_loss(config).0);
+                strategy.next_bet()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn martingale_progression_over_eight_losses() {
+        let doubling = config(serde_json::json!({}));
+        let mut strategy = MartingaleState::new(doubling.base_bet_lamports());
+        assert_eq!(loss_walk(&mut strategy, &doubling, 8),
+            [2_000_000, 4_000_000, 8_000_000, 16_000_000, 32_000_000, 64_000_000, 128_000_000, 256_000_000]);
+
+        // 1.5x: each step scales the previous bet, rounded half up to the lamport
+        let one_and_a_half = config(serde_json::json!({ "multiplier": 1.5 }));
+        let mut strategy = MartingaleState::new(one_and_a_half.base_bet_lamports());
+        assert_eq!(loss_walk(&mut strategy, &one_and_a_half, 8),
+            [1_500_000, 2_250_000, 3_375_000, 5_062_500, 7_593_750, 11_390_625, 17_085_938, 25_628_907]);
+    }
+
+    #[test]
+    fn fibonacci_progression_over_eight_losses() {
+        let config = config(serde_json::json!({ "strategy": "fibonacci", "base_bet_amount": 0.000001234 }));
+        let mut strategy = FibonacciState::new(config.base_bet_lamports());
+        assert_eq!(loss_walk(&mut strategy, &config, 8),
+            [1_234, 2_468, 3_702, 6_170, 9_872, 16_042, 25_914, 41_956]);
+        assert_eq!(strategy.fib_index, 8);
+    }
+
+    #[test]
+    fn fibonacci_steps_back_two_on_a_win() {
+        let config = config(serde_json::json!({ "strategy": "fibonacci" }));
+
+        // At index 0 a win has nowhere to go
+        let mut strategy = FibonacciState::new(config.base_bet_lamports());
+        strategy.on_win(&config);
+        assert_eq!((strategy.fib_index, strategy.next_bet()), (0, 1_000_000));
+
+        // At index 1 the step back stops at 0
+        strategy.on_loss(&config);
+        assert_eq!((strategy.fib_index, strategy.next_bet()), (1, 1_000_000));
+        strategy.on_win(&config);
+        assert_eq!((strategy.fib_index, strategy.next_bet()), (0, 1_000_000));
+
+        // From index 6 (13x) each win drops two places: 5x, 2x, then base
+        loss_walk(&mut strategy, &config, 6);
+        assert_eq!((strategy.fib_index, strategy.next_bet()), (6, 13_000_000));
+        let mut steps = Vec::new();
+        for _ in 0..3 {
+            strategy.on_win(&config);
+            steps.push((strategy.fib_index, strategy.next_bet()));
+        }
+        assert_eq!(steps, [(4, 5_000_000), (2, 2_000_000), (0, 1_000_000)]);
+        assert_eq!(strategy.state().consecutive_losses, 0);
+    }
+
+    #[test]
+    fn paroli_win_streak_up_to_the_cap() {
+        let config = config(serde_json::json!({ "strategy": "paroli", "multiplier": 1.5, "win_streak_cap": 4 }));
+        let mut strategy = ParoliState::new(config.base_bet_lamports());
+
+        // Three wins scale the bet, the fourth hits the cap and goes back to base
+        let mut steps = Vec::new();
+        for _ in 0..4 {
+            strategy.on_win(&config);
+            steps.push((strategy.consecutive_wins, strategy.next_bet()));
+        }
+        assert_eq!(steps, [(1, 1_500_000), (2, 2_250_000), (3, 3_375_000), (0, 1_000_000)]);
+
+        // A fresh streak after the cap starts from base again
+        strategy.on_win(&config);
+        assert_eq!((strategy.consecutive_wins, strategy.next_bet()), (1, 1_500_000));
+    }
+
+    #[test]
+    fn paroli_loss_drops_back_to_base() {
+        let config = config(serde_json::json!({ "strategy": "paroli" }));
+        let mut strategy = ParoliState::new(config.base_bet_lamports());
+        strategy.on_win(&config);
+        strategy.on_win(&config);
+        assert_eq!((strategy.consecutive_wins, strategy.next_bet()), (2, 4_000_000));
+
+        assert_eq!(strategy.on_loss(&config), (true, false));
+        assert_eq!((strategy.consecutive_wins, strategy.next_bet()), (0, 1_000_000));
+        // Further losses never escalate
+        assert_eq!(loss_walk(&mut strategy, &config, 3), [1_000_000; 3]);
+        // And the next win streak starts over
+        strategy.on_win(&config);
+        assert_eq!((strategy.consecutive_wins, strategy.next_bet()), (1, 2_000_000));
+    }
+
+    #[test]
+    fn flat_bet_never_changes() {
+        let config = config(serde_json::json!({
+            "strategy": "flat",
+            "base_bet_amount": 0.002,
+            "max_consecutive_losses": 3,
+            "warn_consecutive_losses": 2,
+        }));
+        let mut strategy = FlatState::new(config.base_bet_lamports());
+
+        // Win, lose past max_consecutive_losses (only a notice for flat), win again, lose
+        let outcomes = [true, false, false, false, false, false, true, true, false, true];
+        for won in outcomes {
+            if won {
+                strategy.on_win(&config);
+            } else {
+                assert!(strategy.on_loss(&config).0);
+            }
+            assert_eq!(strategy.next_bet(), 2_000_000);
+            assert_eq!(strategy.state().uncapped_bet_per_block, None);
+        }
+        assert_eq!(strategy.state().max_consecutive_losses_ever, 5);
+    }
+
+    #[test]
+    fn flat_bet_with_stop_on_max_losses() {
+        let config = config(serde_json::json!({
+            "strategy": "flat",
+            "base_bet_amount": 0.002,
+            "max_consecutive_losses": 2,
+            "warn_consecutive_losses": 1,
+            "stop_on_max_losses": true,
+        }));
+        let mut strategy = FlatState::new(config.base_bet_lamports());
+        assert_eq!(strategy.on_loss(&config), (true, true));
+        assert_eq!(strategy.on_loss(&config), (false, true));
+        assert_eq!(strategy.next_bet(), 2_000_000);
+        assert_eq!(strategy.state().consecutive_losses, 0);
+    }
+
+    #[test]
+    fn bankroll_sums_the_martingale_ladder() {
+        let config = config(serde_json::json!({
+            "max_consecutive_losses": 4,
+            "warn_consecutive_losses": 3,
+            "blocks_per_bet": 2,
+        }));
+        assert_eq!(loss_ladder(&config, 1_000_000), [1_000_000, 2_000_000, 4_000_000, 8_000_000]);
+
+        // 2 blocks × (1 + 2 + 4 + 8) × 0.001 SOL, one fee per bet, 0.05 SOL reserve
+        let required = bankroll_requirement(&config, 1_000_000, 10_000, 50_000_000);
+        assert_eq!(required.stake, 30_000_000);
+        assert_eq!(required.fees, 40_000);
+        assert_eq!(required.reserve, 50_000_000);
+        assert_eq!(required.total(), 80_040_000);
+    }
+
+    #[test]
+    fn bankroll_follows_blocks_per_loss_level() {
+        let config = config(serde_json::json!({
+            "max_consecutive_losses": 4,
+            "warn_consecutive_losses": 3,
+            "blocks_per_bet": 4,
+            "blocks_per_loss_level": [1, 2, 3, 4],
+        }));
+        // (1×1 + 2×2 + 3×4 + 4×8) × 0.001 SOL
+        assert_eq!(worst_case_stake(&config, 1_000_000), 49_000_000);
+        assert_eq!(bankroll_requirement(&config, 1_000_000, 0, 0).total(), 49_000_000);
+    }
+
+    #[test]
+    fn bankroll_ladder_is_clamped_to_cap_and_floor() {
+        let capped = config(serde_json::json!({
+            "max_consecutive_losses": 5,
+            "max_bet_per_block_sol": 0.005,
+        }));
+        assert_eq!(loss_ladder(&capped, 1_000_000), [1_000_000, 2_000_000, 4_000_000, 5_000_000, 5_000_000]);
+        let required = bankroll_requirement(&capped, 1_000_000, 5_000, 1_000_000);
+        assert_eq!(required.stake, 17_000_000);
+        assert_eq!(required.total(), 17_000_000 + 25_000 + 1_000_000);
+
+        // A percent-mode base below the floor is raised to it, the progression continues from the raw base
+        let floored = config(serde_json::json!({
+            "max_consecutive_losses": 4,
+            "warn_consecutive_losses": 3,
+            "min_bet_per_block_sol": 0.001,
+        }));
+        assert_eq!(loss_ladder(&floored, 400_000), [1_000_000, 1_000_000, 1_600_000, 3_200_000]);
+    }
+
+    #[test]
+    fn bankroll_for_other_strategies() {
+        let fibonacci = config(serde_json::json!({"strategy": "fibonacci", "max_consecutive_losses": 6}));
+        assert_eq!(loss_ladder(&fibonacci, 1_000_000), [1_000_000, 1_000_000, 2_000_000, 3_000_000, 5_000_000, 8_000_000]);
+        let flat = config(serde_json::json!({"strategy": "flat", "max_consecutive_losses": 3, "warn_consecutive_losses": 2}));
+        assert_eq!(worst_case_stake(&flat, 1_000_000), 3_000_000);
+    }
+
+    #[test]
+    fn drawdown_tracks_peak_drop_and_recovery() {
+        let config = config(serde_json::json!({}));
+        let mut state = MartingaleState::new(config.base_bet_lamports());
+
+        // Win to a new peak of +2M
+        state.record_bet(1, 1_000_000);
+        state.update_earnings(0, 3_000_000);
+        assert_eq!(state.peak_net_lamports, 2_000_000);
+        assert_eq!(state.current_drawdown(), 0);
+
+        // An open bet isn't a drop until the round settles
+        state.record_bet(2, 1_000_000);
+        assert_eq!(state.max_drawdown_lamports, 0);
+        state.register_loss(&config);
+        assert_eq!(state.current_drawdown(), 1_000_000);
+
+        // Down to -1M: 3M below the peak
+        state.record_bet(3, 2_000_000);
+        state.register_loss(&config);
+        assert_eq!(state.net_profit_sol(), -1_000_000);
+        assert_eq!(state.current_drawdown(), 3_000_000);
+        assert_eq!(state.max_drawdown_lamports, 3_000_000);
+
+        // Recovery past the old peak: new peak, no current drawdown, the worst drop is kept
+        state.record_bet(4, 1_000_000);
+        state.update_earnings(0, 6_000_000);
+        assert_eq!(state.peak_net_lamports, 4_000_000);
+        assert_eq!(state.current_drawdown(), 0);
+        assert_eq!(state.max_drawdown_lamports, 3_000_000);
+    }
+
+    #[test]
+    fn square_stats_accumulate() {
+        let mut stats = SquareStats::default();
+        stats.record(&[1, 2], 2, true);
+        stats.record(&[1, 2], 7, false);
+        stats.record(&[2], 7, false);
+        stats.record(&[30], 30, false); // Out of range: ignored
+        assert_eq!(stats.rounds(), 3);
+        assert_eq!((stats.wins[2], stats.wins[7]), (1, 2));
+        assert_eq!((stats.bets[1], stats.bets[2]), (2, 3));
+        assert_eq!(stats.our_wins[2], 1);
+        assert_eq!(stats.our_wins.iter().sum::<u32>(), 1);
+        assert_eq!(stats.top_winners(5), [(7, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn chi_square_uniform_and_skewed() {
+        assert_eq!(SquareStats::default().chi_square(), None);
+
+        // Every square wins 4 times: a perfect fit
+        let mut uniform = SquareStats::default();
+        for _ in 0..4 {
+            for square in 0..TOTAL_BLOCKS as u8 {
+                uniform.record(&[], square, false);
+            }
+        }
+        assert_eq!(uniform.chi_square(), Some(0.0));
+
+        // 25 rounds all won by square 3: expected 1 each, so (25-1)^2 + 24 * (0-1)^2 = 600
+        let mut skewed = SquareStats::default();
+        for _ in 0..25 {
+            skewed.record(&[3], 3, true);
+        }
+        assert!((skewed.chi_square().unwrap() - 600.0).abs() < 1e-9);
+        assert_eq!(skewed.top_winners(3), [(3, 25)]);
+    }
+
+    #[test]
+    fn guard_reset_returns_fibonacci_and_paroli_to_base() {
+        let config = config(serde_json::json!({}));
+
+        let mut fibonacci = FibonacciState::new(config.base_bet_lamports());
+        for _ in 0..4 {
+            fibonacci.on_loss(&config);
+        }
+        assert_eq!((fibonacci.fib_index, fibonacci.next_bet()), (4, 5_000_000));
+        let strategy: &mut dyn BettingStrategy = &mut fibonacci;
+        strategy.reset(&config);
+        assert_eq!((fibonacci.fib_index, fibonacci.next_bet()), (0, 1_000_000));
+        // The progression starts over rather than from the stale index
+        fibonacci.on_loss(&config);
+        fibonacci.on_loss(&config);
+        assert_eq!((fibonacci.fib_index, fibonacci.next_bet()), (2, 2_000_000));
+
+        let mut paroli = ParoliState::new(config.base_bet_lamports());
+        paroli.on_win(&config);
+        paroli.on_win(&config);
+        assert_eq!((paroli.consecutive_wins, paroli.next_bet()), (2, 4_000_000));
+        let strategy: &mut dyn BettingStrategy = &mut paroli;
+        strategy.reset(&config);
+        assert_eq!((paroli.consecutive_wins, paroli.next_bet()), (0, 1_000_000));
+        paroli.on_win(&config);
+        assert_eq!((paroli.consecutive_wins, paroli.next_bet()), (1, 2_000_000));
+    }
+
+    #[test]
+    fn combined_stats_across_wallets() {
+        let mut first = MartingaleState::new(1_000_000);
+        first.record_bet(1, 2_000_000);
+        first.update_earnings(5, 6_000_000);
+        first.win_count = 1;
+        first.max_win_streak_ever = 1;
+        first.square_stats.record(&[3, 4], 3, true);
+
+        let mut second = MartingaleState::new(1_000_000);
+        second.record_bet(1, 1_000_000);
+        second.register_loss(&config(serde_json::json!({})));
+        second.record_fees(5_000);
+        second.square_stats.record(&[3], 9, false);
+
+        let total = MartingaleState::combined([&first, &second]);
+        assert_eq!((total.win_count, total.loss_count), (1, 1));
+        assert_eq!(total.total_bet_lamports, 3_000_000);
+        assert_eq!(total.total_earned_ore, 5);
+        assert_eq!(total.net_profit_sol(), 6_000_000 - 3_000_000 - 5_000);
+        // +4M peak on the first wallet; the second is 1_005_000 below its zero peak
+        assert_eq!(total.peak_net_lamports, 4_000_000);
+        assert_eq!(total.current_drawdown(), 1_005_000);
+        assert_eq!(total.max_drawdown_lamports, 1_000_000);
+        assert_eq!(total.max_consecutive_losses_ever, 1);
+        assert_eq!((total.square_stats.wins[3], total.square_stats.wins[9], total.square_stats.bets[3]), (1, 1, 2));
+        assert_eq!(total.square_stats.rounds(), 2);
+    }
+}
@@ -0,0 +1,1320 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::{Client, StatusCode};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::Instant;
+use crate::config::{BotConfig, DiscordConfig, NotificationMode, NotifierKind};
+use crate::discord::DiscordNotifier;
+use crate::mining::grid::render_grid;
+use crate::mining::strategy::{DailyLimit, DailyStats, MartingaleState};
+use crate::ore::pda::get_round_pda;
+use crate::ore::state::Round;
+use crate::telegram::TelegramNotifier;
+
+const MAX_RATE_LIMIT_RETRIES: u8 = 3; // Resend attempts after a 429
+const MAX_RETRY_AFTER_SECS: f64 = 60.0; // Longer waits drop the message instead of backing up the queue
+const QUEUE_CAPACITY: usize = 100; // Messages waiting for the sender task; more are dropped
+const FLUSH_TIMEOUT_SECS: u64 = 30; // Max wait for queued messages at shutdown
+const MAX_SUMMARY_LINES: usize = 40; // Rounds listed in one summary embed (description limit is 4096 chars)
+const REQUEST_TIMEOUT_SECS: u64 = 10; // Per-request timeout so a hung connection counts as a failed attempt
+const RETRY_BASE_MS: u64 = 1000; // Backoff before the 2nd attempt, doubling after each failure
+const TOP_SQUARES: usize = 5; // Most frequent winning squares listed in the stats embed
+
+/// Which audience a message is for; backends with a single destination ignore it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// Bets, results, claims, errors
+    Main,
+    /// Periodic stats and summaries
+    Stats,
+    /// Loss-streak and infrastructure warnings
+    Warn,
+}
+
+/// A notification backend (Discord, Telegram, log)
+///
+/// Messages use Discord's embed shape as the common format:
+/// `{"embeds": [{"title", "description", "color", "fields": [{"name", "value", "inline"}]}]}`.
+/// Field values may contain markdown links and ``` code blocks.
+/// New backends implement this and are registered in `build_backends`; the bot only talks to `Notifications`.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Queue or emit a message; must not block the betting loop
+    fn send(&self, channel: Channel, message: &serde_json::Value);
+
+    /// Wait for queued messages to go out (called at shutdown)
+    async fn flush(&self) {}
+}
+
+/// One settled round, listed in the summary embed
+#[derive(Debug, Clone, Copy)]
+pub struct RoundResult {
+    pub round_id: u64,
+    pub won: bool,
+    pub winning_square: u8,
+    pub total_bet: u64,
+    pub sol_earned: u64,
+}
+
+/// Backends configured in `discord` / `telegram`, or the log if there are none (or `notifier` is "stdout")
+pub fn build_backends(config: &BotConfig) -> Vec<Box<dyn Notifier>> {
+    if config.notifier == NotifierKind::Stdout {
+        log::info!("ℹ️ notifier is \"stdout\", notifications go to the log");
+        return vec![Box::new(LogNotifier)];
+    }
+
+    let mut backends: Vec<Box<dyn Notifier>> = Vec::new();
+
+    if let Some(discord) = DiscordNotifier::from_config(&config.discord) {
+        backends.push(Box::new(discord));
+    }
+    if let Some(telegram) = &config.telegram {
+        backends.push(Box::new(TelegramNotifier::new(telegram)));
+    }
+    if backends.is_empty() {
+        log::info!("ℹ️ No Discord or Telegram configured, notifications go to the log");
+        backends.push(Box::new(LogNotifier));
+    }
+
+    backends
+}
+
+/// Discord ping attached to warnings (`content` + `allowed_mentions` so it actually fires)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mention {
+    Role(String),
+    Here,
+    Everyone,
+}
+
+impl Mention {
+    /// "here", "everyone" (with or without @), or a numeric role id
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().trim_start_matches('@') {
+            "here" => Some(Mention::Here),
+            "everyone" => Some(Mention::Everyone),
+            id if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) => Some(Mention::Role(id.to_string())),
+            _ => None,
+        }
+    }
+
+    fn apply(&self, payload: &mut serde_json::Value) {
+        let (content, allowed) = match self {
+            Mention::Role(id) => (format!("<@&{}>", id), json!({ "roles": [id] })),
+            Mention::Here => ("@here".to_string(), json!({ "parse": ["everyone"] })),
+            Mention::Everyone => ("@everyone".to_string(), json!({ "parse": ["everyone"] })),
+        };
+        payload["content"] = json!(content);
+        payload["allowed_mentions"] = allowed;
+    }
+}
+
+/// Builds the bot's notifications and fans them out to every configured backend
+#[derive(Clone)]
+pub struct Notifications {
+    backends: Arc<Vec<Box<dyn Notifier>>>,
+    dry_run: bool,
+    explorer_url: String,
+    explorer_cluster: Option<String>,
+    show_grid: bool,
+    mode: NotificationMode,
+    summary_win_threshold: i64,
+    pending_rounds: Arc<Mutex<Vec<RoundResult>>>, // Summary mode: rounds since the last roll-up
+    warn_mention: Option<Mention>,     // Pinged on loss-streak warnings
+    critical_mention: Option<Mention>, // Pinged one loss from the limit and when it's hit (falls back to warn_mention)
+    wallet: Option<String>,            // Multi-wallet mode: prefixed to every title
+}
+
+impl Notifications {
+    /// Presentation options (explorer links, grid, summary mode) come from the `discord` section
+    /// and apply to every backend
+    pub fn new(backends: Vec<Box<dyn Notifier>>, config: &DiscordConfig, dry_run: bool) -> Self {
+        Self {
+            backends: Arc::new(backends),
+            dry_run,
+            explorer_url: config.explorer_url.trim_end_matches('/').to_string(),
+            explorer_cluster: config.explorer_cluster.clone(),
+            show_grid: config.show_grid,
+            mode: config.notification_mode,
+            summary_win_threshold: (config.summary_win_threshold_sol * 1e9).round() as i64,
+            pending_rounds: Arc::new(Mutex::new(Vec::new())),
+            warn_mention: config.warn_mention_role_id.as_deref().and_then(Mention::parse),
+            critical_mention: config.critical_mention.as_deref().and_then(Mention::parse),
+            wallet: None,
+        }
+    }
+
+    /// Wait until every backend has delivered (or given up on) its queued messages
+    pub async fn flush(&self) {
+        for backend in self.backends.iter() {
+            backend.flush().await;
+        }
+    }
+
+    /// Add the configured ping to a warning; `critical` escalates to `critical_mention`
+    fn push_mention(&self, payload: &mut serde_json::Value, critical: bool) {
+        let mention = if critical {
+            self.critical_mention.as_ref().or(self.warn_mention.as_ref())
+        } else {
+            self.warn_mention.as_ref()
+        };
+        if let Some(mention) = mention {
+            mention.apply(payload);
+        }
+    }
+
+    /// Per-bet and per-loss embeds are suppressed in summary mode
+    pub fn is_summary_mode(&self) -> bool {
+        self.mode == NotificationMode::Summary
+    }
+
+    /// Whether a win gets its own embed: always in verbose mode, above the threshold in summary mode
+    pub fn should_notify_win(&self, net_profit: i64) -> bool {
+        !self.is_summary_mode() || net_profit >= self.summary_win_threshold
+    }
+
+    /// Queue a settled round for the next summary (no-op in verbose mode)
+    pub fn record_round(&self, result: RoundResult) {
+        if self.is_summary_mode() {
+            self.pending_rounds.lock().unwrap().push(result);
+        }
+    }
+
+    /// Send the queued rounds as one embed and clear the queue
+    pub async fn notify_round_summary(&self) -> Result<()> {
+        let rounds: Vec<RoundResult> = std::mem::take(&mut *self.pending_rounds.lock().unwrap());
+        if rounds.is_empty() {
+            return Ok(());
+        }
+
+        let wins = rounds.iter().filter(|r| r.won).count();
+        let net: i64 = rounds.iter().map(|r| r.sol_earned as i64 - r.total_bet as i64).sum();
+        // Keep the newest rounds if there are too many for one embed description
+        let lines: Vec<String> = rounds.iter()
+            .skip(rounds.len().saturating_sub(MAX_SUMMARY_LINES))
+            .map(|r| format!("{} #{} · square {} · bet {:.6} · {:+.6} SOL",
+                if r.won { "✅" } else { "❌" },
+                r.round_id,
+                r.winning_square,
+                r.total_bet as f64 / 1e9,
+                (r.sol_earned as i64 - r.total_bet as i64) as f64 / 1e9))
+            .collect();
+
+        let embed = json!({
+            "embeds": [{
+                "title": format!("🗒️ Last {} Rounds", rounds.len()),
+                "description": lines.join("\n"),
+                "color": 3447003, // Blue
+                "fields": [
+                    {
+                        "name": "Record",
+                        "value": format!("{}W / {}L", wins, rounds.len() - wins),
+                        "inline": true
+                    },
+                    {
+                        "name": "Net",
+                        "value": format!("{:+.6} SOL", net as f64 / 1e9),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook(embed).await
+    }
+
+    /// Explorer URL for `path` (e.g. "tx/<signature>"), on the configured cluster
+    fn explorer_link(&self, path: &str) -> String {
+        match &self.explorer_cluster {
+            Some(cluster) => format!("{}/{}?cluster={}", self.explorer_url, path, cluster),
+            None => format!("{}/{}", self.explorer_url, path),
+        }
+    }
+
+    /// Add a "Transaction" field linking to `signature` (dry-run signatures aren't on chain, so no link)
+    fn push_transaction_link(&self, embed: &mut serde_json::Value, signature: Option<&str>) {
+        if let Some(signature) = signature.filter(|_| !self.dry_run) {
+            let url = self.explorer_link(&format!("tx/{}", signature));
+            push_field(embed, "Transaction", format!("[View on explorer]({})", url), false);
+        }
+    }
+
+    /// Add a "Grid" field showing our squares and the winner (when `show_grid` is on)
+    fn push_grid(&self, embed: &mut serde_json::Value, bet_blocks: &[u8], winning_block: Option<u8>) {
+        if self.show_grid {
+            push_field(embed, "Grid", format!("```\n{}\n```", render_grid(bet_blocks, winning_block)), false);
+        }
+    }
+
+    /// Add a "Round Account" field linking to the round PDA
+    fn push_round_link(&self, embed: &mut serde_json::Value, round_id: u64) {
+        let (round_address, _) = get_round_pda(round_id);
+        let url = self.explorer_link(&format!("account/{}", round_address));
+        push_field(embed, "Round Account", format!("[{}]({})", round_address, url), false);
+    }
+
+    /// Prefix embed titles with the wallet name (multi-wallet mode) and a DRY RUN tag
+    /// so paper results aren't mistaken for real ones
+    fn tag_titles(&self, mut payload: serde_json::Value) -> serde_json::Value {
+        if let Some(wallet) = &self.wallet {
+            if let Some(embeds) = payload["embeds"].as_array_mut() {
+                for embed in embeds {
+                    let title = embed["title"].as_str().unwrap_or_default();
+                    embed["title"] = json!(format!("[{}] {}", wallet, title));
+                }
+            }
+        }
+        if self.dry_run {
+            if let Some(embeds) = payload["embeds"].as_array_mut() {
+                for embed in embeds {
+                    let title = embed["title"].as_str().unwrap_or_default();
+                    embed["title"] = json!(format!("🧪 [DRY RUN] {}", title));
+                }
+            }
+        }
+        payload
+    }
+
+    /// Same backends, with `wallet` prefixed to every title and its own summary-mode queue
+    pub fn for_wallet(&self, wallet: &str) -> Self {
+        Self {
+            wallet: Some(wallet.to_string()),
+            pending_rounds: Arc::new(Mutex::new(Vec::new())),
+            ..self.clone()
+        }
+    }
+
+    /// Send a bet notification
+    #[allow(clippy::too_many_arguments)]
+    pub async fn notify_bet(
+        &self,
+        round_id: u64,
+        blocks: &[u8],
+        bet_per_block: u64,
+        total_bet: u64,
+        consecutive_losses: u8,
+        priority_fee: Option<u64>,
+        signature: Option<&str>,
+    ) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "🎲 New Bet Placed",
+                "color": 3447003, // Blue
+                "fields": [
+                    {
+                        "name": "Round",
+                        "value": format!("#{}", round_id),
+                        "inline": true
+                    },
+                    {
+                        "name": format!("Blocks ({})", blocks.len()),
+                        "value": format!("{:?}", blocks),
+                        "inline": true
+                    },
+                    {
+                        "name": "Bet per Block",
+                        "value": format!("{:.6} SOL", bet_per_block as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Total Bet",
+                        "value": format!("{:.6} SOL", total_bet as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Consecutive Losses",
+                        "value": consecutive_losses.to_string(),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        if let Some(fee) = priority_fee {
+            push_field(&mut embed, "Priority Fee", format!("{} µlamports/CU", fee), true);
+        }
+
+        self.push_grid(&mut embed, blocks, None);
+        self.push_transaction_link(&mut embed, signature);
+        self.send_webhook(embed).await
+    }
+
+    /// Send a win notification
+    #[allow(clippy::too_many_arguments)]
+    pub async fn notify_win(
+        &self,
+        round: &Round,
+        bet_blocks: &[u8],
+        winning_block: u8,
+        ore_reward: u64,
+        sol_reward: u64,
+        net_profit_sol: i64,
+        top_miner_reward: Option<u64>,
+        bet_signature: Option<&str>,
+    ) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": if top_miner_reward.is_some() { "✅ WIN! 🏆 Top Miner" } else { "✅ WIN!" },
+                "color": 3066993, // Green
+                "fields": [
+                    {
+                        "name": "Round",
+                        "value": format!("#{}", round.id),
+                        "inline": true
+                    },
+                    {
+                        "name": "Winning Block",
+                        "value": winning_block.to_string(),
+                        "inline": true
+                    },
+                    {
+                        "name": "ORE Reward",
+                        "value": format!("{:.6} ORE", ore_reward as f64 / 1e11),
+                        "inline": true
+                    },
+                    {
+                        "name": "SOL Reward",
+                        "value": format!("{:.6} SOL", sol_reward as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Net Profit",
+                        "value": format!("{:.6} SOL", net_profit_sol as f64 / 1e9),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        push_field(&mut embed, "Square Share", square_share(round.deployed[winning_block as usize], round.total_deployed), true);
+        if let Some(reward) = top_miner_reward {
+            push_field(&mut embed, "🏆 Top Miner Reward", format!("{:.6} ORE (in ORE Reward)", reward as f64 / 1e11), true);
+        }
+        if round.motherlode > 0 {
+            push_field(&mut embed, "💎 Motherlode", format!("{:.6} ORE split among the winning square", round.motherlode as f64 / 1e11), true);
+        }
+        self.push_grid(&mut embed, bet_blocks, Some(winning_block));
+        self.push_transaction_link(&mut embed, bet_signature);
+        self.push_round_link(&mut embed, round.id);
+        self.send_webhook(embed).await
+    }
+
+    /// Send a loss notification
+    pub async fn notify_loss(
+        &self,
+        round: &Round,
+        bet_blocks: &[u8],
+        winning_block: u8,
+        consecutive_losses: u8,
+        next_bet: u64,
+        bet_signature: Option<&str>,
+    ) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "❌ Loss",
+                "color": 15158332, // Red
+                "fields": [
+                    {
+                        "name": "Round",
+                        "value": format!("#{}", round.id),
+                        "inline": true
+                    },
+                    {
+                        "name": "Winning Block",
+                        "value": winning_block.to_string(),
+                        "inline": true
+                    },
+                    {
+                        "name": "Consecutive Losses",
+                        "value": consecutive_losses.to_string(),
+                        "inline": true
+                    },
+                    {
+                        "name": "Next Bet",
+                        "value": format!("{:.6} SOL per block", next_bet as f64 / 1e9),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        push_field(&mut embed, "Square Share", square_share(round.deployed[winning_block as usize], round.total_deployed), true);
+        self.push_grid(&mut embed, bet_blocks, Some(winning_block));
+        self.push_transaction_link(&mut embed, bet_signature);
+        self.push_round_link(&mut embed, round.id);
+        self.send_webhook(embed).await
+    }
+
+    /// Send a warning notification (to stats channel)
+    pub async fn notify_warning(
+        &self,
+        consecutive_losses: u8,
+        max_losses: u8,
+        current_bet: u64,
+        theoretical_bet: u64,
+    ) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "⚠️ Warning: High Consecutive Losses",
+                "color": 15105570, // Orange
+                "fields": [
+                    {
+                        "name": "Consecutive Losses",
+                        "value": format!("{}/{}", consecutive_losses, max_losses),
+                        "inline": true
+                    },
+                    {
+                        "name": "Current Bet",
+                        "value": format!("{:.6} SOL per block", current_bet as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Status",
+                        "value": if consecutive_losses >= max_losses { "Max loss limit reached!" } else { "Approaching max loss limit!" },
+                        "inline": false
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        if theoretical_bet != current_bet {
+            push_field(&mut embed, "Uncapped Bet",
+                format!("{:.6} SOL per block (capped by max_bet_per_block_sol)", theoretical_bet as f64 / 1e9),
+                true);
+        }
+
+        // One more loss hits the limit
+        self.push_mention(&mut embed, consecutive_losses.saturating_add(1) >= max_losses);
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a one-time warning when the progression first runs into `max_bet_per_block_sol`
+    pub async fn notify_bet_ceiling(&self, consecutive_losses: u8, cap: u64, theoretical_bet: u64) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "🧢 Bet Ceiling Reached",
+                "color": 15105570, // Orange
+                "fields": [
+                    {
+                        "name": "Consecutive Losses",
+                        "value": consecutive_losses.to_string(),
+                        "inline": true
+                    },
+                    {
+                        "name": "Bet Ceiling",
+                        "value": format!("{:.6} SOL per block", cap as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Uncapped Bet",
+                        "value": format!("{:.6} SOL per block", theoretical_bet as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Status",
+                        "value": "Bet stays flat at the ceiling until the next win; a win no longer recovers the whole cycle",
+                        "inline": false
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a one-time notice when a daily limit pauses betting
+    pub async fn notify_daily_limit(&self, limit: DailyLimit, day_net_lamports: i64) -> Result<()> {
+        let (title, limit_name, limit_lamports) = match limit {
+            DailyLimit::Loss(limit) => ("🛑 Daily Loss Limit Reached", "Daily Loss Limit", limit),
+            DailyLimit::ProfitTarget(target) => ("🎯 Daily Profit Target Reached", "Daily Profit Target", target),
+        };
+
+        let embed = json!({
+            "embeds": [{
+                "title": title,
+                "color": 15105570, // Orange
+                "fields": [
+                    {
+                        "name": "Today's Net",
+                        "value": format!("{:+.6} SOL", day_net_lamports as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": limit_name,
+                        "value": format!("{:.6} SOL", limit_lamports as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Status",
+                        "value": "Betting paused until 00:00 UTC",
+                        "inline": false
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a notice when a daily-limit pause is lifted
+    pub async fn notify_daily_resume(&self) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "▶️ Betting Resumed",
+                "color": 3066993, // Green
+                "description": "Daily limit lifted (new UTC day), betting resumed.",
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a notice when betting is paused or resumed by hand; `reason` says what paused it
+    pub async fn notify_pause_changed(&self, paused: bool, reason: Option<&str>) -> Result<()> {
+        let (title, color, description) = if paused {
+            ("⏸️ Betting Paused", 15105570, "No new bets until resumed. The round in progress was allowed to finish.") // Orange
+        } else {
+            ("▶️ Betting Resumed", 3066993, "Betting resumed where the cycle left off.") // Green
+        };
+
+        let mut embed = json!({
+            "embeds": [{
+                "title": title,
+                "color": color,
+                "description": description,
+                "fields": [],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+        if let Some(reason) = reason {
+            push_field(&mut embed, "Via", reason.to_string(), true);
+        }
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a one-time notice when the bot leaves its betting windows
+    pub async fn notify_outside_schedule(&self, next_start: chrono::DateTime<Utc>) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "😴 Outside Schedule",
+                "color": 9807270, // Purple
+                "description": format!("No betting until the next window opens at {} UTC.", next_start.format("%a %Y-%m-%d %H:%M")),
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook(embed).await
+    }
+
+    /// Send a warning that the WebSocket stream has been unhealthy (updates fall back to RPC)
+    pub async fn notify_infra_warning(&self, status: &str, minutes: u64, reconnects: u32) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "📡 WebSocket Unhealthy",
+                "color": 15105570, // Orange
+                "description": format!("WebSocket {} for {}+ minutes. Falling back to RPC polling.", status, minutes),
+                "fields": [
+                    {
+                        "name": "Reconnects",
+                        "value": reconnects.to_string(),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a notice that the WebSocket stream recovered
+    pub async fn notify_infra_recovered(&self, downtime_minutes: u64, reconnects: u32) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "📡 WebSocket Recovered",
+                "color": 3066993, // Green
+                "description": format!("WebSocket updates are flowing again after ~{} minutes.", downtime_minutes),
+                "fields": [
+                    {
+                        "name": "Reconnects",
+                        "value": reconnects.to_string(),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a notice that a bet already on chain was picked up instead of placing a new one
+    pub async fn notify_resumed_bet(&self, round_id: u64, blocks: &[u8], total_bet: u64) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": format!("♻️ Resumed existing bet on round #{}", round_id),
+                "color": 3447003, // Blue
+                "fields": [
+                    {
+                        "name": "Blocks",
+                        "value": format!("{:?}", blocks),
+                        "inline": true
+                    },
+                    {
+                        "name": "Total Bet",
+                        "value": format!("{:.6} SOL", total_bet as f64 / 1e9),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook(embed).await
+    }
+
+    /// Send a notice that the EV guard skipped a round
+    pub async fn notify_ev_skip(&self, round_id: u64, reason: &str) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": format!("⏭️ Skipped round #{}", round_id),
+                "color": 9807270, // Purple
+                "description": reason,
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook(embed).await
+    }
+
+    /// Send a warning that the win-rate guard tripped; `action` says what the bot does about it
+    pub async fn notify_win_rate_guard(&self, rate: f64, window: usize, floor: f64, action: &str) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "📉 Win Rate Below Floor",
+                "color": 15105570, // Orange
+                "description": action,
+                "fields": [],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        push_field(&mut embed, "Win Rate", format!("{:.1}% over {} rounds", rate, window), true);
+        push_field(&mut embed, "Floor", format!("{}%", floor), true);
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a notice that the win-rate guard no longer holds betting back
+    pub async fn notify_win_rate_guard_lifted(&self, detail: &str) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "📈 Win Rate Guard Lifted",
+                "color": 3066993, // Green
+                "description": detail,
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a notice that max consecutive losses triggered a cooldown
+    /// `duration` reads like "30 minutes" or "5 rounds"
+    pub async fn notify_cooldown(&self, duration: &str) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": format!("⏸️ Cooling down for {}", duration),
+                "color": 15105570, // Orange
+                "description": "Max consecutive losses reached. Betting resumes at the base bet after the cooldown.",
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.push_mention(&mut embed, true);
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Send a notice when a cooldown ends and betting resumes at the base bet
+    pub async fn notify_cooldown_over(&self) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "▶️ Cooldown Over",
+                "color": 3066993, // Green
+                "description": "Betting resumed at the base bet.",
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook_to_warn(embed).await
+    }
+
+    /// Config reloaded on SIGHUP: what was applied, and what still needs a restart
+    pub async fn notify_config_reloaded(&self, changed: &[String], ignored: &[String]) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "🔄 Config Reloaded",
+                "color": 3447003, // Blue
+                "description": if changed.is_empty() { "No runtime settings changed.".to_string() } else { changed.join("\n") },
+                "fields": [],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        if !ignored.is_empty() {
+            push_field(&mut embed, "Needs a restart (ignored)", ignored.join(", "), false);
+        }
+
+        self.send_webhook(embed).await
+    }
+
+    /// Send an error notification
+    pub async fn notify_error(&self, error_msg: &str) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "🚨 Error",
+                "color": 10038562, // Dark Red
+                "description": error_msg,
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook(embed).await
+    }
+
+    /// Send the error raised when max consecutive losses stops the bot, with the critical ping
+    pub async fn notify_max_losses(&self, error_msg: &str) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "🚨 Error",
+                "color": 10038562, // Dark Red
+                "description": error_msg,
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.push_mention(&mut embed, true);
+        self.send_webhook(embed).await
+    }
+
+    /// Send SOL claim notification
+    pub async fn notify_claim_sol(
+        &self,
+        claimed_amount: u64,
+        new_balance: u64,
+        signature: Option<&str>,
+    ) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "💰 SOL Claimed",
+                "color": 15844367, // Gold
+                "fields": [
+                    {
+                        "name": "Claimed Amount",
+                        "value": format!("{:.6} SOL", claimed_amount as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "New Balance",
+                        "value": format!("{:.6} SOL", new_balance as f64 / 1e9),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.push_transaction_link(&mut embed, signature);
+        self.send_webhook(embed).await
+    }
+
+    /// Send a notification when ORE rewards are claimed
+    pub async fn notify_claim_ore(
+        &self,
+        claimed_amount: u64,
+        token_balance: u64,
+        signature: Option<&str>,
+    ) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "🪙 ORE Claimed",
+                "color": 15844367, // Gold
+                "fields": [
+                    {
+                        "name": "Claimed Amount",
+                        "value": format!("{:.6} ORE", claimed_amount as f64 / 1e11),
+                        "inline": true
+                    },
+                    {
+                        "name": "Token Balance",
+                        "value": format!("{:.6} ORE", token_balance as f64 / 1e11),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.push_transaction_link(&mut embed, signature);
+        self.send_webhook(embed).await
+    }
+
+    /// Send a notification when profits above `sweep_above_sol` were moved to the cold wallet
+    pub async fn notify_sweep(
+        &self,
+        swept_amount: u64,
+        destination: &str,
+        new_balance: u64,
+        signature: Option<&str>,
+    ) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": format!("🏦 Swept {:.6} SOL to cold wallet", swept_amount as f64 / 1e9),
+                "color": 15844367, // Gold
+                "fields": [
+                    {
+                        "name": "Destination",
+                        "value": format!("`{}`", destination),
+                        "inline": false
+                    },
+                    {
+                        "name": "New Balance",
+                        "value": format!("{:.6} SOL", new_balance as f64 / 1e9),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.push_transaction_link(&mut embed, signature);
+        self.send_webhook(embed).await
+    }
+
+    /// Send a shutdown notification with the final stats
+    pub async fn notify_shutdown(
+        &self,
+        win_count: u32,
+        loss_count: u32,
+        win_rate: f64,
+        net_profit_sol: i64,
+        max_drawdown: u64,
+    ) -> Result<()> {
+        let embed = json!({
+            "embeds": [{
+                "title": "👋 Shutting down",
+                "color": 9807270, // Purple
+                "fields": [
+                    {
+                        "name": "Record",
+                        "value": format!("{}W / {}L ({:.2}%)", win_count, loss_count, win_rate),
+                        "inline": true
+                    },
+                    {
+                        "name": "Net Profit",
+                        "value": format!("{:.6} SOL", net_profit_sol as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Max Drawdown",
+                        "value": format!("{:.6} SOL", max_drawdown as f64 / 1e9),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook(embed).await
+    }
+
+    /// Send statistics summary
+    pub async fn notify_stats(&self, state: &MartingaleState) -> Result<()> {
+        let mut embed = json!({
+            "embeds": [{
+                "title": "📊 Bot Statistics",
+                "color": 9807270, // Purple
+                "fields": [
+                    {
+                        "name": "Total Rounds",
+                        "value": (state.win_count + state.loss_count).to_string(),
+                        "inline": true
+                    },
+                    {
+                        "name": "Wins",
+                        "value": state.win_count.to_string(),
+                        "inline": true
+                    },
+                    {
+                        "name": "Losses",
+                        "value": state.loss_count.to_string(),
+                        "inline": true
+                    },
+                    {
+                        "name": "Win Rate",
+                        "value": format!("{:.2}%", state.win_rate()),
+                        "inline": true
+                    },
+                    {
+                        "name": "Total ORE Earned",
+                        "value": format!("{:.6} ORE", state.total_earned_ore as f64 / 1e11),
+                        "inline": true
+                    },
+                    {
+                        "name": "Net Profit",
+                        "value": format!("{:.6} SOL", state.net_profit_sol() as f64 / 1e9),
+                        "inline": true
+                    },
+                    {
+                        "name": "Fees Paid",
+                        "value": format!("{:.6} SOL", state.total_fees_lamports as f64 / 1e9),
+                        "inline": true
+                    }
+                ],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        if let (Some(roi), Some(edge)) = (state.roi_percent(), state.edge_percent()) {
+            push_field(&mut embed, "ROI", format!("{:+.2}%", roi), true);
+            push_field(&mut embed, "Edge (SOL, before fees)", format!("{:+.2}%", edge), true);
+        }
+        if let Some((rate, rounds)) = state.rolling_win_rate() {
+            push_field(&mut embed, &format!("Win Rate (last {})", rounds), format!("{:.2}%", rate), true);
+        }
+        push_field(&mut embed, "Longest Win Streak", state.max_win_streak_ever.to_string(), true);
+        if state.top_miner_wins > 0 {
+            push_field(&mut embed, "Top Miner", format!("{}x, {:.6} ORE", state.top_miner_wins, state.top_miner_ore as f64 / 1e11), true);
+        }
+        push_field(&mut embed, "Longest Loss Streak", state.max_consecutive_losses_ever.to_string(), true);
+        push_field(&mut embed, "Drawdown", format!("{:.6} SOL (max {:.6} SOL)",
+            state.current_drawdown() as f64 / 1e9,
+            state.max_drawdown_lamports as f64 / 1e9), true);
+
+        let squares = &state.square_stats;
+        let top: Vec<String> = squares.top_winners(TOP_SQUARES)
+            .iter()
+            .map(|&(square, wins)| format!("{}: {}× won · we bet {} · hit {}",
+                square,
+                wins,
+                squares.bets[square as usize],
+                squares.our_wins[square as usize]))
+            .collect();
+        if !top.is_empty() {
+            push_field(&mut embed, &format!("Top Squares ({} rounds)", squares.rounds()), top.join("\n"), false);
+        }
+
+        self.send_webhook_to_stats(embed).await
+    }
+
+    /// Daily digest: activity since the previous digest plus where the progression stands now
+    pub async fn notify_daily_summary(&self, day: &DailyStats, state: &MartingaleState) -> Result<()> {
+        let settled = day.wins + day.losses;
+        let win_rate = if settled == 0 { 0.0 } else { day.wins as f64 / settled as f64 * 100.0 };
+        let net = day.net_lamports();
+
+        let mut embed = json!({
+            "embeds": [{
+                "title": "📅 Daily Summary",
+                "color": if net >= 0 { 3066993 } else { 15158332 }, // Green / Red
+                "fields": [],
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        if let Some(since) = chrono::DateTime::from_timestamp(day.since, 0).filter(|_| day.since != 0) {
+            embed["embeds"][0]["description"] = json!(format!("Since {}", since.format("%Y-%m-%d %H:%M UTC")));
+        }
+        push_field(&mut embed, "Rounds", day.rounds.to_string(), true);
+        push_field(&mut embed, "Record", format!("{}W / {}L ({:.1}%)", day.wins, day.losses, win_rate), true);
+        push_field(&mut embed, "Net", format!("{:+.6} SOL", net as f64 / 1e9), true);
+        push_field(&mut embed, "SOL Bet", format!("{:.6} SOL", day.sol_bet as f64 / 1e9), true);
+        push_field(&mut embed, "SOL Earned", format!("{:.6} SOL", day.sol_earned as f64 / 1e9), true);
+        push_field(&mut embed, "ORE Earned", format!("{:.6} ORE", day.ore_earned as f64 / 1e11), true);
+        push_field(&mut embed, "Fees Paid", format!("{:.6} SOL", day.fees_paid as f64 / 1e9), true);
+        push_field(&mut embed, "Largest Bet", format!("{:.6} SOL per block", day.largest_bet_per_block as f64 / 1e9), true);
+        push_field(&mut embed, "Longest Loss Streak", day.max_loss_streak.to_string(), true);
+        push_field(&mut embed, "Current Bet", format!("{:.6} SOL per block ({} losses in a row)",
+            state.current_bet_per_block as f64 / 1e9,
+            state.consecutive_losses), false);
+        push_field(&mut embed, "All-Time Net", format!("{:+.6} SOL ({}W / {}L)",
+            state.net_profit_sol() as f64 / 1e9,
+            state.win_count,
+            state.loss_count), false);
+
+        self.send_webhook_to_stats(embed).await
+    }
+
+    /// Compact liveness ping to the stats channel
+    pub async fn notify_heartbeat(
+        &self,
+        round_id: u64,
+        balance: Option<u64>,
+        consecutive_losses: u8,
+        ws_status: &str,
+        last_bet_age_secs: Option<i64>,
+    ) -> Result<()> {
+        let balance = balance.map_or("unknown".to_string(), |b| format!("{:.6} SOL", b as f64 / 1e9));
+        let last_bet = last_bet_age_secs.map_or("no bet yet".to_string(), |age| format!("last bet {} ago", format_age(age)));
+
+        let embed = json!({
+            "embeds": [{
+                "title": "💓 Alive",
+                "description": format!("Round #{} · balance {} · {} consecutive losses · WSS: {} · {}",
+                    round_id, balance, consecutive_losses, ws_status, last_bet),
+                "color": 10070709, // Grey
+                "timestamp": Utc::now().to_rfc3339()
+            }]
+        });
+
+        self.send_webhook_to_stats(embed).await
+    }
+
+    async fn send_webhook(&self, payload: serde_json::Value) -> Result<()> {
+        self.dispatch(Channel::Main, payload)
+    }
+
+    async fn send_webhook_to_stats(&self, payload: serde_json::Value) -> Result<()> {
+        self.dispatch(Channel::Stats, payload)
+    }
+
+    async fn send_webhook_to_warn(&self, payload: serde_json::Value) -> Result<()> {
+        self.dispatch(Channel::Warn, payload)
+    }
+
+    /// Hand the message to every backend; delivery happens in the background
+    fn dispatch(&self, channel: Channel, payload: serde_json::Value) -> Result<()> {
+        let payload = self.tag_titles(payload);
+        for backend in self.backends.iter() {
+            backend.send(channel, &payload);
+        }
+        Ok(())
+    }
+}
+
+/// Append a field to the first embed of a webhook payload
+fn push_field(payload: &mut serde_json::Value, name: &str, value: String, inline: bool) {
+    if let Some(fields) = payload["embeds"][0]["fields"].as_array_mut() {
+        fields.push(json!({
+            "name": name,
+            "value": value,
+            "inline": inline
+        }));
+    }
+}
+
+/// "42s", "17m", "3h 5m"
+fn format_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s => format!("{}h {}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+/// Backend used when no Discord or Telegram destination is configured: each message becomes one info log line
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    /// "📣 ✅ WIN! | Round: #12 | Winning Block: 7 | ..."
+    fn send(&self, _channel: Channel, payload: &serde_json::Value) {
+        let Some(embed) = payload["embeds"].get(0) else {
+            return;
+        };
+
+        let mut line = format!("📣 {}", embed["title"].as_str().unwrap_or_default());
+        if let Some(description) = embed["description"].as_str() {
+            line.push_str(&format!(" | {}", description));
+        }
+        for field in embed["fields"].as_array().into_iter().flatten() {
+            let value = field["value"].as_str().unwrap_or_default()
+                .replace("```", "")
+                .trim()
+                .replace('\n', " ");
+            line.push_str(&format!(" | {}: {}", field["name"].as_str().unwrap_or_default(), value));
+        }
+
+        log::info!("{}", line);
+    }
+}
+
+/// Work for a `QueuedSender` task
+enum Outgoing {
+    Message { url: String, label: &'static str, payload: serde_json::Value },
+    /// Answered once everything queued before it has been handled
+    Flush(oneshot::Sender<()>),
+}
+
+/// Bounded queue drained by a background task that POSTs JSON one message at a time
+///
+/// Messages to the same URL are spaced by `min_interval`; 429 Retry-After is honoured and
+/// network errors, timeouts and 5xx are retried with exponential backoff. Clones share the queue.
+#[derive(Clone)]
+pub struct QueuedSender {
+    name: &'static str,
+    queue: mpsc::Sender<Outgoing>,
+    dropped: Arc<AtomicU64>, // Messages lost to a full queue
+}
+
+impl QueuedSender {
+    /// Must be called inside the Tokio runtime: spawns the delivery task
+    pub fn new(name: &'static str, min_interval: Duration, max_attempts: u8) -> Self {
+        let (queue, rx) = mpsc::channel(QUEUE_CAPACITY);
+        let worker = SenderTask {
+            client: Client::builder()
+                .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            min_interval,
+            max_attempts,
+            last_send: HashMap::new(),
+        };
+        tokio::spawn(worker.run(rx));
+
+        Self { name, queue, dropped: Arc::new(AtomicU64::new(0)) }
+    }
+
+    /// Queue a POST of `payload` to `url`; returns without waiting for delivery
+    pub fn enqueue(&self, url: &str, label: &'static str, payload: serde_json::Value) {
+        let message = Outgoing::Message { url: url.to_string(), label, payload };
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.queue.try_send(message) {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            log::warn!("⚠️ {} queue full, dropped a {} message ({} dropped so far)", self.name, label, dropped);
+        }
+    }
+
+    /// Wait (up to FLUSH_TIMEOUT_SECS) until every queued message has been delivered or given up on
+    pub async fn flush(&self) {
+        let (done_tx, done_rx) = oneshot::channel();
+        if self.queue.send(Outgoing::Flush(done_tx)).await.is_err() {
+            return;
+        }
+        if tokio::time::timeout(Duration::from_secs(FLUSH_TIMEOUT_SECS), done_rx).await.is_err() {
+            log::warn!("⚠️ Gave up waiting for queued {} messages after {}s", self.name, FLUSH_TIMEOUT_SECS);
+        }
+
+        let dropped = self.dropped.load(Ordering::Relaxed);
+        if dropped > 0 {
+            log::warn!("⚠️ {} {} message(s) were dropped because the queue was full", dropped, self.name);
+        }
+    }
+}
+
+/// Background task behind a `QueuedSender`
+struct SenderTask {
+    client: Client,
+    min_interval: Duration,
+    max_attempts: u8,
+    last_send: HashMap<String, Instant>, // Per destination URL
+}
+
+impl SenderTask {
+    async fn run(mut self, mut rx: mpsc::Receiver<Outgoing>) {
+        while let Some(outgoing) = rx.recv().await {
+            match outgoing {
+                Outgoing::Message { url, label, payload } => {
+                    if let Err(e) = self.deliver(&url, label, &payload).await {
+                        log::error!("Failed to send notification: {}", e);
+                    }
+                }
+                Outgoing::Flush(done) => {
+                    let _ = done.send(());
+                }
+            }
+        }
+    }
+
+    /// POST to a webhook, spacing messages to the same URL by `min_interval` and honouring 429 Retry-After
+    /// Network errors, timeouts and 5xx are retried with exponential backoff up to `max_attempts`;
+    /// other 4xx fail immediately
+    async fn deliver(&mut self, url: &str, label: &str, payload: &serde_json::Value) -> Result<()> {
+        let mut rate_limited = 0;
+        let mut attempt = 1;
+
+        loop {
+            if let Some(last) = self.last_send.get(url) {
+                tokio::time::sleep_until(*last + self.min_interval).await;
+            }
+
+            let response = self.client.post(url).json(payload).send().await;
+            self.last_send.insert(url.to_string(), Instant::now());
+
+            let transient_error = match &response {
+                Err(e) => Some(e.to_string()),
+                Ok(r) if r.status().is_server_error() => Some(r.status().to_string()),
+                Ok(_) => None,
+            };
+            if let Some(error) = transient_error {
+                if attempt < self.max_attempts {
+                    let backoff = Duration::from_millis(RETRY_BASE_MS << (attempt - 1).min(5)); // Capped at 32s
+                    log::warn!("🔄 {} failed ({}), retrying in {:.1}s ({}/{})",
+                        label, error, backoff.as_secs_f64(), attempt, self.max_attempts);
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                    continue;
+                }
+            }
+            let response = response?;
+
+            if response.status() == StatusCode::TOO_MANY_REQUESTS && rate_limited < MAX_RATE_LIMIT_RETRIES {
+                let retry_after = retry_after_secs(&response);
+                if retry_after > MAX_RETRY_AFTER_SECS {
+                    anyhow::bail!("{} rate limited for {:.1}s, dropping message", label, retry_after);
+                }
+                rate_limited += 1;
+                log::warn!("⏳ {} rate limited, retrying in {:.1}s ({}/{})",
+                    label, retry_after, rate_limited, MAX_RATE_LIMIT_RETRIES);
+                tokio::time::sleep(Duration::from_secs_f64(retry_after)).await;
+                continue;
+            }
+
+            if !response.status().is_success() {
+                anyhow::bail!(
+                    "{} failed: {} - {}",
+                    label,
+                    response.status(),
+                    response.text().await?
+                );
+            }
+
+            return Ok(());
+        }
+    }
+}
+
+/// Seconds to wait from a 429's Retry-After header (1s if missing or unparseable)
+fn retry_after_secs(response: &reqwest::Response) -> f64 {
+    response.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .unwrap_or(1.0)
+}
+
+/// "12.50% (0.500000 / 4.000000 SOL)": how crowded the winning square was
+fn square_share(square_deployed: u64, total_deployed: u64) -> String {
+    let percent = if total_deployed == 0 {
+        0.0
+    } else {
+        square_deployed as f64 / total_deployed as f64 * 100.0
+    };
+    format!("{:.2}% ({:.6} / {:.6} SOL)", percent, square_deployed as f64 / 1e9, total_deployed as f64 / 1e9)
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+
+/// Custom errors returned by the Ore program
+/// Codes from regolith-labs/ore api/src/error.rs; anything else is kept as `Unknown`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OreError {
+    /// Deploy/claim amount below the program minimum
+    AmountTooSmall,
+    /// Signer is not the authority of the account being modified
+    NotAuthorized,
+    /// A custom code this bot doesn't know about
+    Unknown(u32),
+}
+
+impl OreError {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            0 => OreError::AmountTooSmall,
+            1 => OreError::NotAuthorized,
+            other => OreError::Unknown(other),
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            OreError::AmountTooSmall => 0,
+            OreError::NotAuthorized => 1,
+            OreError::Unknown(code) => *code,
+        }
+    }
+
+    /// Human-readable explanation for logs and Discord
+    pub fn description(&self) -> &'static str {
+        match self {
+            OreError::AmountTooSmall => "amount too small (bet per block below the program minimum)",
+            OreError::NotAuthorized => "not authorized (signer does not own the miner account)",
+            OreError::Unknown(_) => "unrecognized Ore program error",
+        }
+    }
+
+    /// Extract the code from an RPC/transaction error message,
+    /// e.g. "Error processing Instruction 2: custom program error: 0x1"
+    pub fn from_message(message: &str) -> Option<Self> {
+        const MARKER: &str = "custom program error: 0x";
+
+        let start = message.find(MARKER)? + MARKER.len();
+        let hex: String = message[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_hexdigit())
+            .collect();
+        u32::from_str_radix(&hex, 16).ok().map(Self::from_code)
+    }
+}
+
+impl fmt::Display for OreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ore program error 0x{:x}: {}", self.code(), self.description())
+    }
+}
+
+impl std::error::Error for OreError {}
@@ -3,7 +3,10 @@ use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
 };
-use crate::ore::pda::{get_automation_pda, get_board_pda, get_miner_pda, get_round_pda, get_treasury_pda, ore_program_id};
+use crate::ore::pda::{
+    get_associated_token_address, get_automation_pda, get_board_pda, get_miner_pda, get_round_pda, get_treasury_pda,
+    ore_program_id, ASSOCIATED_TOKEN_PROGRAM_ID, ORE_MINT_ADDRESS, TOKEN_PROGRAM_ID,
+};
 
 // System program ID constant
 pub const SYSTEM_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("11111111111111111111111111111111");
@@ -120,3 +123,71 @@ pub fn build_claim_sol_instruction(signer: Pubkey) -> Instruction {
         data: vec![CLAIM_SOL_DISCRIMINATOR],
     }
 }
+
+/// Claim ORE rewards into the signer's ORE token account
+///
+/// The token account must exist; prepend `build_create_ata_idempotent_instruction` if it doesn't.
+/// Account order follows `claim_ore` in regolith-labs/ore api/src/sdk.rs; the discriminator is
+/// `OreInstruction::ClaimORE` (api/src/instruction.rs), between ClaimSOL (3) and Deploy (6).
+pub fn build_claim_ore_instruction(signer: Pubkey) -> Instruction {
+    const CLAIM_ORE_DISCRIMINATOR: u8 = 4;
+
+    let miner_address = get_miner_pda(&signer).0;
+    let treasury_address = get_treasury_pda().0;
+    let treasury_tokens_address = get_associated_token_address(&treasury_address, &ORE_MINT_ADDRESS);
+    let recipient_address = get_associated_token_address(&signer, &ORE_MINT_ADDRESS);
+
+    Instruction {
+        program_id: ore_program_id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),                                   // signer
+            AccountMeta::new(miner_address, false),                           // miner
+            AccountMeta::new(ORE_MINT_ADDRESS, false),                        // mint
+            AccountMeta::new(recipient_address, false),                       // recipient token account
+            AccountMeta::new(treasury_address, false),                        // treasury
+            AccountMeta::new(treasury_tokens_address, false),                 // treasury token account
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),              // system_program
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),               // token_program
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),    // associated_token_program
+        ],
+        data: vec![CLAIM_ORE_DISCRIMINATOR],
+    }
+}
+
+/// Create `owner`'s associated token account for `mint` (no-op on chain if it already exists)
+pub fn build_create_ata_idempotent_instruction(payer: Pubkey, owner: Pubkey, mint: Pubkey) -> Instruction {
+    const CREATE_IDEMPOTENT_DISCRIMINATOR: u8 = 1;
+
+    let ata_address = get_associated_token_address(&owner, &mint);
+
+    Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),                         // funding account
+            AccountMeta::new(ata_address, false),                  // associated token account
+            AccountMeta::new_readonly(owner, false),               // wallet
+            AccountMeta::new_readonly(mint, false),                // mint
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),   // system_program
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),    // token_program
+        ],
+        data: vec![CREATE_IDEMPOTENT_DISCRIMINATOR],
+    }
+}
+
+/// System program Transfer of `lamports` from `from` to `to`
+pub fn build_transfer_instruction(from: Pubkey, to: Pubkey, lamports: u64) -> Instruction {
+    const TRANSFER_DISCRIMINATOR: u32 = 2;
+
+    let mut data = Vec::with_capacity(12);
+    data.extend_from_slice(&TRANSFER_DISCRIMINATOR.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(from, true), // funding account
+            AccountMeta::new(to, false),  // recipient
+        ],
+        data,
+    }
+}
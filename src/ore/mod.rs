@@ -1,3 +1,4 @@
+pub mod error;
 pub mod instruction;
 pub mod pda;
 pub mod state;
@@ -46,6 +47,29 @@ impl OreClient {
         }
     }
 
+    /// Get the Miner account as it stands for `round_id`
+    /// None if it doesn't exist or has moved on to another round (deployed no longer describes this one)
+    pub async fn get_miner_for_round(&self, authority: &Pubkey, round_id: u64) -> Result<Option<Miner>> {
+        Ok(self.get_miner(authority).await?.filter(|miner| miner.round_id == round_id))
+    }
+
+    /// Get the ORE token balance of `authority`'s associated token account
+    /// None if the token account doesn't exist yet
+    pub async fn get_ore_token_balance(&self, authority: &Pubkey) -> Result<Option<u64>> {
+        let token_address = pda::get_associated_token_address(authority, &pda::ORE_MINT_ADDRESS);
+        let account = self.solana.rpc
+            .get_account_with_commitment(&token_address, self.solana.rpc.commitment())
+            .await?
+            .value;
+
+        // SPL token account layout: mint (32) | owner (32) | amount (u64 LE) | ...
+        Ok(account.map(|account| {
+            account.data.get(64..72)
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                .unwrap_or(0)
+        }))
+    }
+
     /// Check if a round is active (within start and end slots)
     pub async fn is_round_active(&self, board: &Board) -> Result<bool> {
         let slot = self.solana.rpc.get_slot().await?;
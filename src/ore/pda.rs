@@ -7,6 +7,11 @@ use std::str::FromStr;
 
 pub const ORE_PROGRAM_ID: &str = "oreV3EG1i9BEgiAJ8b177Z2S2rMarzak4NMv1kULvWv";
 
+// ORE token mint and the SPL programs needed to hold it
+pub const ORE_MINT_ADDRESS: Pubkey = solana_sdk::pubkey!("oreoU2P8bN6jkk3jbaiVxYnG1dCXcYxwhwyK9jSybcp");
+pub const TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
 // PDA seeds
 pub const BOARD: &[u8] = b"board";
 pub const ROUND: &[u8] = b"round";
@@ -46,6 +51,14 @@ pub fn get_treasury_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[b"treasury"], &program_id)
 }
 
+/// Get the associated token account of `owner` for `mint`
+pub fn get_associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    ).0
+}
+
 /// Get the Ore program ID as a Pubkey
 pub fn ore_program_id() -> Pubkey {
     Pubkey::from_str(ORE_PROGRAM_ID).unwrap()
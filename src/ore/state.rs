@@ -57,24 +57,46 @@ pub struct Round {
     pub total_winnings: u64,
 }
 
+/// RNG value of a round's slot hash: the four little-endian u64 words XORed together
+/// All zeros (not sampled yet) and all ones (sampling failed) mean there is no RNG
+pub fn rng_from_hash(hash: &[u8; 32]) -> Option<u64> {
+    if *hash == [0; 32] || *hash == [u8::MAX; 32] {
+        return None;
+    }
+    Some(hash.chunks_exact(8)
+        .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+        .fold(0, |r, word| r ^ word))
+}
+
+/// Winning square (0-24) for a slot hash, or None if the hash carries no RNG
+pub fn winning_square_from_hash(hash: &[u8; 32]) -> Option<usize> {
+    rng_from_hash(hash).map(|rng| (rng % 25) as usize)
+}
+
 impl Round {
     /// Get RNG value from slot hash
     pub fn rng(&self) -> Option<u64> {
-        if self.slot_hash == [0; 32] || self.slot_hash == [u8::MAX; 32] {
-            return None;
-        }
-        let r1 = u64::from_le_bytes(self.slot_hash[0..8].try_into().unwrap());
-        let r2 = u64::from_le_bytes(self.slot_hash[8..16].try_into().unwrap());
-        let r3 = u64::from_le_bytes(self.slot_hash[16..24].try_into().unwrap());
-        let r4 = u64::from_le_bytes(self.slot_hash[24..32].try_into().unwrap());
-        let r = r1 ^ r2 ^ r3 ^ r4;
-        Some(r)
+        rng_from_hash(&self.slot_hash)
     }
 
     /// Get the winning square index (0-24)
     pub fn winning_square(&self, rng: u64) -> usize {
         (rng % 25) as usize
     }
+
+    /// Estimate the SOL a hypothetical bet on `square` would have returned if it won
+    ///
+    /// Used in dry-run mode where the bet never lands on-chain. Winners get their
+    /// stake back plus a share of `total_winnings` proportional to their stake on
+    /// the winning square (our stake is added to the square since it isn't on-chain).
+    pub fn estimate_sol_reward(&self, square: usize, bet: u64) -> u64 {
+        let square_total = self.deployed[square] + bet;
+        if square_total == 0 {
+            return 0;
+        }
+        let share = (self.total_winnings as u128 * bet as u128 / square_total as u128) as u64;
+        bet + share
+    }
 }
 
 #[repr(C)]
@@ -123,6 +145,18 @@ pub struct Miner {
     pub lifetime_rewards_ore: u64,
 }
 
+impl Miner {
+    /// Whether this miner has SOL on `square` in `round_id` (None if the account is on another round)
+    pub fn won_round(&self, round_id: u64, square: usize) -> Option<bool> {
+        (self.round_id == round_id).then(|| self.deployed[square] > 0)
+    }
+
+    /// Total SOL deployed across all squares in the miner's current round
+    pub fn total_deployed(&self) -> u64 {
+        self.deployed.iter().sum()
+    }
+}
+
 // Manual Pod/Zeroable implementation for Miner (contains Pubkey)
 unsafe impl Pod for Miner {}
 unsafe impl Zeroable for Miner {}
@@ -144,3 +178,68 @@ pub fn deserialize_account<T: Pod>(data: &[u8]) -> Result<&T, Error> {
             "Failed to deserialize account"
         ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Slot hash whose four little-endian words are `words`
+    fn hash_of(words: [u64; 4]) -> [u8; 32] {
+        let mut hash = [0u8; 32];
+        for (chunk, word) in hash.chunks_exact_mut(8).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        hash
+    }
+
+    #[test]
+    fn sentinel_hashes_have_no_rng() {
+        assert_eq!(rng_from_hash(&[0; 32]), None);
+        assert_eq!(rng_from_hash(&[u8::MAX; 32]), None);
+        assert_eq!(winning_square_from_hash(&[0; 32]), None);
+        assert_eq!(winning_square_from_hash(&[u8::MAX; 32]), None);
+    }
+
+    #[test]
+    fn rng_xors_the_four_words() {
+        assert_eq!(rng_from_hash(&hash_of([1, 26, 0, 0])), Some(27));
+        assert_eq!(rng_from_hash(&hash_of([u64::MAX, u64::MAX, 5, 0])), Some(5));
+        // Not a sentinel: the words cancel out, which is a valid RNG of 0
+        let ascending: [u8; 32] = std::array::from_fn(|i| i as u8);
+        assert_eq!(rng_from_hash(&ascending), Some(0));
+    }
+
+    #[test]
+    fn known_hashes_map_to_their_squares() {
+        assert_eq!(winning_square_from_hash(&hash_of([7, 0, 0, 0])), Some(7));
+        assert_eq!(winning_square_from_hash(&hash_of([0, 0, 0, 24])), Some(24));
+        assert_eq!(winning_square_from_hash(&hash_of([1, 26, 0, 0])), Some(2));
+        assert_eq!(winning_square_from_hash(&hash_of([0xABAB_ABAB_ABAB_ABAB, 0, 0, 0])), Some(8));
+        let ascending: [u8; 32] = std::array::from_fn(|i| i as u8);
+        assert_eq!(winning_square_from_hash(&ascending), Some(0));
+    }
+
+    #[test]
+    fn round_uses_its_slot_hash() {
+        let mut round = Round::zeroed();
+        assert_eq!(round.rng(), None);
+        round.slot_hash = hash_of([53, 0, 0, 0]);
+        let rng = round.rng().unwrap();
+        assert_eq!(round.winning_square(rng), 3);
+    }
+
+    #[test]
+    fn won_round_checks_round_and_square() {
+        let mut miner = Miner::zeroed();
+        miner.round_id = 42;
+        miner.deployed[3] = 1_000_000;
+        miner.deployed[17] = 1;
+
+        assert_eq!(miner.won_round(42, 3), Some(true));
+        assert_eq!(miner.won_round(42, 17), Some(true));
+        assert_eq!(miner.won_round(42, 4), Some(false));
+        // The account has moved on (or not yet reached) this round: no verdict
+        assert_eq!(miner.won_round(41, 3), None);
+        assert_eq!(miner.won_round(43, 3), None);
+    }
+}
@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Manual kill switch for new bets
+///
+/// Paused while the flag is set (SIGUSR1 toggles it, the status endpoint sets or clears it)
+/// or while `pause_file` exists. The main loop checks it between rounds, so a round in
+/// progress always finishes and the martingale cycle is left untouched.
+#[derive(Clone)]
+pub struct Pause {
+    paused: Arc<AtomicBool>,
+    pause_file: Option<PathBuf>,
+}
+
+impl Pause {
+    /// Install the SIGUSR1 handler (no-op on platforms without it)
+    pub fn listen(pause_file: Option<PathBuf>) -> Self {
+        let paused = Arc::new(AtomicBool::new(false));
+
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            match signal(SignalKind::user_defined1()) {
+                Ok(mut sigusr1) => {
+                    let paused = paused.clone();
+                    tokio::spawn(async move {
+                        while sigusr1.recv().await.is_some() {
+                            let now_paused = !paused.fetch_xor(true, Ordering::Relaxed);
+                            log::info!("📶 SIGUSR1 received, {} after the current round",
+                                if now_paused { "pausing" } else { "resuming" });
+                        }
+                    });
+                }
+                Err(e) => log::warn!("⚠️ Failed to install SIGUSR1 handler: {}", e),
+            }
+        }
+
+        Self { paused, pause_file }
+    }
+
+    /// Why betting is paused, or None if it isn't
+    pub fn reason(&self) -> Option<&'static str> {
+        if self.paused.load(Ordering::Relaxed) {
+            Some("SIGUSR1 or status endpoint")
+        } else if self.pause_file.as_ref().is_some_and(|path| path.exists()) {
+            Some("pause file")
+        } else {
+            None
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.reason().is_some()
+    }
+
+    /// Set the manual flag; returns false if it was already set
+    pub fn pause(&self) -> bool {
+        !self.paused.swap(true, Ordering::Relaxed)
+    }
+
+    /// Clear the manual flag; returns false if it wasn't set (a pause file still pauses)
+    pub fn resume(&self) -> bool {
+        self.paused.swap(false, Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Reload flag set by SIGHUP
+/// The main loop checks it between rounds, so a reload never lands mid-bet
+#[derive(Clone)]
+pub struct Reload {
+    requested: Arc<AtomicBool>,
+}
+
+impl Reload {
+    /// Install the SIGHUP handler (no-op on platforms without it)
+    pub fn listen() -> Self {
+        let requested = Arc::new(AtomicBool::new(false));
+
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            match signal(SignalKind::hangup()) {
+                Ok(mut sighup) => {
+                    let requested = requested.clone();
+                    tokio::spawn(async move {
+                        while sighup.recv().await.is_some() {
+                            log::info!("🔄 SIGHUP received, reloading config before the next round");
+                            requested.store(true, Ordering::Relaxed);
+                        }
+                    });
+                }
+                Err(e) => log::warn!("⚠️ Failed to install SIGHUP handler: {}", e),
+            }
+        }
+
+        Self { requested }
+    }
+
+    /// Whether a reload was requested since the last call
+    pub fn take(&self) -> bool {
+        self.requested.swap(false, Ordering::Relaxed)
+    }
+}
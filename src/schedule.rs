@@ -0,0 +1,210 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc, Weekday};
+
+const DAY_SECS: i64 = 86_400;
+const WEEK_SECS: i64 = 7 * DAY_SECS;
+const ALL_DAYS: u8 = 0b111_1111;
+
+/// When the bot is allowed to bet, as UTC ranges within the week
+///
+/// Each entry of `active_hours` is "HH:MM-HH:MM", optionally prefixed with days:
+/// "Mon-Fri 20:00-24:00", "Sat,Sun 00:00-24:00". A range whose end is before its start
+/// crosses midnight and runs into the next day. Ranges may overlap. No ranges = always on.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    intervals: Vec<(i64, i64)>, // [start, end) in seconds since Monday 00:00, end may pass the week end
+}
+
+impl Schedule {
+    pub fn parse(ranges: &[String]) -> Result<Self> {
+        let mut intervals = Vec::new();
+        for range in ranges {
+            let (days, start, end) = parse_range(range)
+                .with_context(|| format!("Invalid schedule range \"{}\" (expected e.g. \"20:00-24:00\" or \"Mon-Fri 00:00-06:00\")", range))?;
+            // start == end would be an empty range; read it as the whole day instead
+            let end = if end <= start { end + DAY_SECS } else { end };
+            for day in 0..7 {
+                if days & (1 << day) != 0 {
+                    let offset = day as i64 * DAY_SECS;
+                    intervals.push((offset + start, offset + end));
+                }
+            }
+        }
+        Ok(Self { intervals })
+    }
+
+    /// No ranges configured: bet around the clock
+    pub fn is_always_on(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        if self.is_always_on() {
+            return true;
+        }
+        let t = week_secs(now);
+        // An interval running past Sunday midnight also covers the start of the week
+        self.intervals.iter().any(|&(start, end)| (start..end).contains(&t) || (start..end).contains(&(t + WEEK_SECS)))
+    }
+
+    /// Next moment a window opens, or None if always on
+    /// Called while outside every window, so the nearest interval start is where betting resumes
+    pub fn next_start(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let t = week_secs(now);
+        self.intervals.iter()
+            .map(|&(start, _)| (start - t).rem_euclid(WEEK_SECS))
+            .min()
+            .map(|secs| now.with_nanosecond(0).unwrap_or(now) + Duration::seconds(secs))
+    }
+}
+
+/// Seconds since Monday 00:00 UTC
+fn week_secs(now: DateTime<Utc>) -> i64 {
+    now.weekday().num_days_from_monday() as i64 * DAY_SECS + now.num_seconds_from_midnight() as i64
+}
+
+/// "[days ]HH:MM-HH:MM" -> (day bitmask, Monday = bit 0; start secs; end secs)
+fn parse_range(range: &str) -> Result<(u8, i64, i64)> {
+    let range = range.trim();
+    let (days, hours) = match range.rsplit_once(' ') {
+        Some((days, hours)) => (parse_days(days.trim())?, hours),
+        None => (ALL_DAYS, range),
+    };
+    let (start, end) = hours.split_once('-').context("missing '-' between start and end")?;
+    let start = parse_time(start)?;
+    let end = parse_time(end)?;
+    if start == DAY_SECS {
+        anyhow::bail!("start can't be 24:00");
+    }
+    Ok((days, start, end))
+}
+
+/// "Mon", "Mon-Fri", "Sat,Sun", "Fri-Mon" (wraps over the weekend)
+fn parse_days(days: &str) -> Result<u8> {
+    let mut mask = 0u8;
+    for part in days.split(',') {
+        let part = part.trim();
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let from = parse_weekday(from)?;
+                let to = parse_weekday(to)?;
+                let mut day = from;
+                loop {
+                    mask |= 1 << day;
+                    if day == to {
+                        break;
+                    }
+                    day = (day + 1) % 7;
+                }
+            }
+            None => mask |= 1 << parse_weekday(part)?,
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_weekday(day: &str) -> Result<u8> {
+    let day: Weekday = day.trim().parse()
+        .map_err(|_| anyhow::anyhow!("unknown day \"{}\"", day))?;
+    Ok(day.num_days_from_monday() as u8)
+}
+
+/// "HH:MM" in 00:00..=24:00 -> seconds since midnight
+fn parse_time(time: &str) -> Result<i64> {
+    let (hours, minutes) = time.trim().split_once(':').context("time must be HH:MM")?;
+    let hours: i64 = hours.parse().with_context(|| format!("invalid hour \"{}\"", hours))?;
+    let minutes: i64 = minutes.parse().with_context(|| format!("invalid minute \"{}\"", minutes))?;
+    if !(0..60).contains(&minutes) || !(0..=24).contains(&hours) || (hours == 24 && minutes != 0) {
+        anyhow::bail!("time {} is out of range (00:00-24:00)", time.trim());
+    }
+    Ok(hours * 3600 + minutes * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(ranges: &[&str]) -> Schedule {
+        Schedule::parse(&ranges.iter().map(|r| r.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    /// 2025-01-06 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn malformed_ranges_are_rejected() {
+        for bad in ["", "20:00", "20:00-", "-06:00", "25:00-26:00", "20:60-21:00", "24:00-06:00", "24:30-01:00",
+            "aa:00-06:00", "20-22", "Funday 20:00-22:00", "Mon-Xyz 20:00-22:00", "Mon,,Tue 20:00-22:00"] {
+            assert!(Schedule::parse(&[bad.to_string()]).is_err(), "{:?} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn plain_range_every_day() {
+        let schedule = schedule(&["20:00-24:00"]);
+        assert!(!schedule.is_always_on());
+        assert!(schedule.is_active(at(8, 20, 0)));
+        assert!(schedule.is_active(at(8, 23, 59)));
+        assert!(!schedule.is_active(at(8, 19, 59)));
+        assert!(!schedule.is_active(at(9, 0, 0)));
+    }
+
+    #[test]
+    fn range_crossing_midnight() {
+        let schedule = schedule(&["Fri 22:00-02:00"]);
+        assert!(schedule.is_active(at(10, 23, 0)));  // Friday night
+        assert!(schedule.is_active(at(11, 1, 59)));  // Saturday early morning
+        assert!(!schedule.is_active(at(11, 2, 0)));
+        assert!(!schedule.is_active(at(10, 21, 59)));
+        assert!(!schedule.is_active(at(9, 23, 0)));  // Thursday night isn't covered
+    }
+
+    #[test]
+    fn sunday_night_wraps_into_monday() {
+        let schedule = schedule(&["Sun 23:00-01:00"]);
+        assert!(schedule.is_active(at(12, 23, 30)));
+        assert!(schedule.is_active(at(13, 0, 30)));  // Monday, the start of the next week
+        assert!(!schedule.is_active(at(13, 1, 0)));
+        assert!(!schedule.is_active(at(6, 23, 30))); // Monday night
+    }
+
+    #[test]
+    fn day_lists_and_wrapping_day_ranges() {
+        let weekend = schedule(&["Fri-Mon 00:00-24:00"]);
+        for (day, active) in [(6, true), (7, false), (9, false), (10, true), (11, true), (12, true)] {
+            assert_eq!(weekend.is_active(at(day, 12, 0)), active, "January {}", day);
+        }
+        let listed = schedule(&["Tue,Thu 08:00-09:00"]);
+        assert!(listed.is_active(at(7, 8, 30)));
+        assert!(!listed.is_active(at(8, 8, 30)));
+        assert!(listed.is_active(at(9, 8, 30)));
+    }
+
+    #[test]
+    fn next_start_outside_a_window() {
+        let schedule = schedule(&["Mon-Fri 20:00-24:00"]);
+        assert_eq!(schedule.next_start(at(8, 12, 0)), Some(at(8, 20, 0)));
+        // Saturday, after the last weekday window: next is Monday
+        assert_eq!(schedule.next_start(at(11, 12, 0)), Some(at(13, 20, 0)));
+    }
+
+    #[test]
+    fn next_start_inside_a_window() {
+        let schedule = schedule(&["20:00-24:00"]);
+        // Already open: the next opening is tomorrow's
+        assert_eq!(schedule.next_start(at(8, 21, 0)), Some(at(9, 20, 0)));
+        // Right at the start: that is the opening
+        assert_eq!(schedule.next_start(at(8, 20, 0)), Some(at(8, 20, 0)));
+    }
+
+    #[test]
+    fn no_ranges_is_always_on() {
+        let schedule = schedule(&[]);
+        assert!(schedule.is_always_on());
+        assert!(schedule.is_active(at(8, 3, 0)));
+        assert_eq!(schedule.next_start(at(8, 3, 0)), None);
+    }
+}
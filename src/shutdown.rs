@@ -0,0 +1,72 @@
+use tokio::sync::watch;
+use tokio::time::Duration;
+
+/// Exit code used when a second signal forces an immediate exit (128 + SIGINT)
+const FORCED_EXIT_CODE: i32 = 130;
+
+/// Shutdown flag flipped by SIGINT/SIGTERM
+/// The first signal requests a graceful stop; a second one exits immediately
+#[derive(Clone)]
+pub struct Shutdown {
+    requested: watch::Receiver<bool>,
+}
+
+impl Shutdown {
+    /// Install the signal handlers
+    pub fn listen() -> Self {
+        let (tx, requested) = watch::channel(false);
+
+        tokio::spawn(async move {
+            wait_for_signal().await;
+            log::warn!("🛑 Shutdown requested, finishing the current round... (signal again to force exit)");
+            let _ = tx.send(true);
+
+            wait_for_signal().await;
+            log::error!("🛑 Second signal received, exiting immediately");
+            crate::history::close_all();
+            std::process::exit(FORCED_EXIT_CODE);
+        });
+
+        Self { requested }
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.requested.borrow()
+    }
+
+    /// Sleep for `duration`, waking early on shutdown
+    /// Returns true if interrupted by a shutdown request
+    pub async fn sleep(&mut self, duration: Duration) -> bool {
+        tokio::select! {
+            biased;
+            _ = self.requested.wait_for(|requested| *requested) => true,
+            _ = tokio::time::sleep(duration) => false,
+        }
+    }
+}
+
+/// Resolve on the next SIGINT (Ctrl-C) or SIGTERM
+async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(e) => {
+                log::warn!("⚠️ Failed to install SIGTERM handler: {}", e);
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
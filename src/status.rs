@@ -0,0 +1,168 @@
+use anyhow::{Context, Result};
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::client::SolanaClient;
+use crate::mining::strategy::BettingStrategy;
+use crate::pause::Pause;
+use crate::subscription::{BoardSubscription, MinerSubscription, SubscriptionHealth};
+
+/// Everything the status endpoint reads from, or flips
+#[derive(Clone)]
+pub struct StatusContext {
+    pub martingale_state: Arc<Mutex<Box<dyn BettingStrategy + Send>>>,
+    pub solana_client: SolanaClient,
+    pub wallet: Pubkey,
+    pub subscription: MinerSubscription,
+    pub boards: BoardSubscription,
+    pub board_stale: Duration,
+    pub ws_stale: Duration,
+    pub pause: Pause,
+    pub auth_token: String,
+    pub started: Instant,
+}
+
+/// Bind `listen_addr` and serve `/status`, `/pause` and `/resume` in the background
+/// Binding happens here so a taken port fails startup instead of going unnoticed
+pub async fn start(listen_addr: SocketAddr, ctx: StatusContext) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(listen_addr).await
+        .with_context(|| format!("Failed to bind status endpoint on {}", listen_addr))?;
+
+    let app = Router::new()
+        .route("/status", get(status))
+        .route("/pause", post(pause))
+        .route("/resume", post(resume))
+        .with_state(ctx);
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            log::error!("❌ Status endpoint stopped: {}", e);
+        }
+    });
+
+    log::info!("🌐 Status endpoint listening on http://{}/status", listen_addr);
+    Ok(())
+}
+
+/// `Authorization: Bearer <token>`, compared without an early exit on the first wrong byte
+fn authorized(headers: &HeaderMap, token: &str) -> bool {
+    let Some(given) = headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    given.len() == token.len()
+        && given.bytes().zip(token.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn unauthorized() -> Response {
+    (StatusCode::UNAUTHORIZED, Json(json!({ "error": "missing or invalid bearer token" }))).into_response()
+}
+
+async fn status(State(ctx): State<StatusContext>, headers: HeaderMap) -> Response {
+    if !authorized(&headers, &ctx.auth_token) {
+        return unauthorized();
+    }
+
+    let (state, next_bet) = {
+        let strategy = ctx.martingale_state.lock().unwrap();
+        (strategy.state().clone(), strategy.next_bet())
+    };
+    // Live balance, or the last snapshot if RPC is down
+    let balance = match ctx.solana_client.get_balance(&ctx.wallet).await {
+        Ok(balance) => Some(balance),
+        Err(e) => {
+            log::warn!("⚠️ Status endpoint: failed to fetch balance: {}", e);
+            state.balance_lamports
+        }
+    };
+    let miner = ctx.subscription.get_miner().await;
+    let board = ctx.boards.latest(ctx.board_stale);
+    let ws_status = match ctx.subscription.health(ctx.ws_stale) {
+        SubscriptionHealth::Connected => "healthy",
+        SubscriptionHealth::Stale(_) => "stale",
+        SubscriptionHealth::Disconnected => "disconnected",
+    };
+
+    Json(json!({
+        "uptime_secs": ctx.started.elapsed().as_secs(),
+        "paused": ctx.pause.is_paused(),
+        "round": {
+            "current": state.current_round,
+            "board": board.map(|board| json!({
+                "round_id": board.round_id,
+                "start_slot": board.start_slot,
+                "end_slot": board.end_slot,
+            })),
+            "last_winning_square": state.last_winning_square,
+        },
+        "bet": {
+            "next_bet_per_block_sol": next_bet as f64 / 1e9,
+            "consecutive_losses": state.consecutive_losses,
+            "cycle_bet_sol": state.current_cycle_bet_lamports as f64 / 1e9,
+            "last_bet_time": state.last_bet_time,
+        },
+        "streaks": {
+            "win_streak": state.win_streak,
+            "longest_win_streak": state.max_win_streak_ever,
+            "longest_loss_streak": state.max_consecutive_losses_ever,
+        },
+        "profit": {
+            "wins": state.win_count,
+            "losses": state.loss_count,
+            "win_rate": state.win_rate(),
+            "net_profit_sol": state.net_profit_sol() as f64 / 1e9,
+            "fees_paid_sol": state.total_fees_lamports as f64 / 1e9,
+            "max_drawdown_sol": state.max_drawdown_lamports as f64 / 1e9,
+        },
+        "recent_rounds": {
+            "win_rate": state.rolling_win_rate().map(|(rate, _)| rate),
+            "net_sol": state.round_history.net(usize::MAX) as f64 / 1e9,
+            "rounds": state.round_history.iter().rev().map(|entry| json!({
+                "round_id": entry.round_id,
+                "winning_square": entry.winning_square,
+                "won": entry.won,
+                "net_sol": entry.net as f64 / 1e9,
+            })).collect::<Vec<_>>(),
+        },
+        "wallet": {
+            "address": ctx.wallet.to_string(),
+            "balance_sol": balance.map(|balance| balance as f64 / 1e9),
+            "unclaimed_sol": miner.map(|miner| miner.rewards_sol as f64 / 1e9),
+            "unclaimed_ore": miner.map(|miner| miner.rewards_ore as f64 / 1e11),
+        },
+        "websocket": {
+            "status": ws_status,
+            "reconnects": ctx.subscription.reconnect_count(),
+        },
+    })).into_response()
+}
+
+async fn pause(State(ctx): State<StatusContext>, headers: HeaderMap) -> Response {
+    if !authorized(&headers, &ctx.auth_token) {
+        return unauthorized();
+    }
+    if ctx.pause.pause() {
+        log::info!("⏸️ Pause requested via the status endpoint, takes effect after the current round");
+    }
+    Json(json!({ "paused": ctx.pause.is_paused() })).into_response()
+}
+
+async fn resume(State(ctx): State<StatusContext>, headers: HeaderMap) -> Response {
+    if !authorized(&headers, &ctx.auth_token) {
+        return unauthorized();
+    }
+    if ctx.pause.resume() {
+        log::info!("▶️ Resume requested via the status endpoint");
+    }
+    // Still true while a pause file exists
+    Json(json!({ "paused": ctx.pause.is_paused() })).into_response()
+}
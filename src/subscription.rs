@@ -2,33 +2,36 @@ use anyhow::Result;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use solana_sdk::pubkey::Pubkey;
-use std::sync::Arc;
-use tokio::sync::RwLock;
-use tokio::time::{sleep, Duration};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
-use crate::ore::state::Miner;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::TransactionError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio::time::{sleep, Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use crate::ore::pda::get_board_pda;
+use crate::ore::state::{deserialize_account, Board, Miner};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AccountSubscribeRequest {
+pub struct SubscribeRequest {
     jsonrpc: String,
     id: u64,
     method: String,
     params: Vec<serde_json::Value>,
 }
 
+/// Any `*Notification` message; `result` is decoded according to the subscription it belongs to
 #[derive(Debug, Clone, Deserialize)]
-pub struct AccountNotification {
-    #[allow(dead_code)]
-    pub jsonrpc: String,
+pub struct SubscriptionNotification {
     pub method: String,
-    pub params: AccountNotificationParams,
+    pub params: SubscriptionNotificationParams,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-pub struct AccountNotificationParams {
-    pub result: AccountNotificationResult,
-    #[allow(dead_code)]
+pub struct SubscriptionNotificationParams {
+    pub result: serde_json::Value,
     pub subscription: u64,
 }
 
@@ -61,79 +64,426 @@ pub struct AccountData {
     pub space: u64,
 }
 
-#[derive(Clone)]
-pub struct MinerSubscription {
-    pub miner_state: Arc<RwLock<Option<Miner>>>,
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlotInfo {
+    pub slot: u64,
+}
+
+/// Reply to a subscribe request: `{"jsonrpc":"2.0","result":<subscription id>,"id":<request id>}`
+#[derive(Debug, Clone, Deserialize)]
+struct SubscribeConfirmation {
+    id: u64,
+    result: u64,
+}
+
+/// Parse a subscribe confirmation into (request id, subscription id)
+/// None for anything else (notifications, errors, pongs)
+pub fn parse_subscription_confirmation(text: &str) -> Option<(u64, u64)> {
+    serde_json::from_str::<SubscribeConfirmation>(text)
+        .ok()
+        .map(|confirmation| (confirmation.id, confirmation.result))
+}
+
+/// Latest raw account data pushed over the WebSocket
+#[derive(Debug, Clone)]
+pub struct AccountUpdate {
+    pub data: Vec<u8>,     // Full account data (8-byte discriminator included)
+    pub received: Instant, // When it arrived
+}
+
+impl AccountUpdate {
+    /// Decode an ORE account (8-byte discriminator + `T`)
+    pub fn decode<T: bytemuck::Pod>(&self) -> Result<T> {
+        Ok(*deserialize_account::<T>(&self.data)?)
+    }
 }
 
-impl AccountNotification {
-    /// Parse the Miner account data from the notification
-    pub fn parse_miner(&self) -> Result<Miner> {
-        let data = self.params.result.value.data
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("No data in notification"))?;
+/// Connection state of the shared WebSocket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubscriptionHealth {
+    /// Connected and receiving messages
+    Connected,
+    /// Connected, but nothing (not even a pong) received for this long
+    Stale(Duration),
+    /// Not connected (between reconnect attempts)
+    Disconnected,
+}
+
+/// Liveness bookkeeping updated by the worker
+#[derive(Debug, Default)]
+struct ConnectionStats {
+    connected: bool,
+    last_update_at: Option<Instant>, // Last notification or pong
+    reconnects: u32,                 // Connections established after the first one
+}
 
-        let decoded = BASE64.decode(data)?;
+impl ConnectionStats {
+    fn touch(&mut self) {
+        self.last_update_at = Some(Instant::now());
+    }
+}
 
-        if decoded.len() < std::mem::size_of::<Miner>() {
-            return Err(anyhow::anyhow!("Invalid miner data length"));
+/// One stream multiplexed over the shared connection
+enum Feed {
+    Account {
+        address: Pubkey,
+        label: &'static str,
+        tx: watch::Sender<Option<AccountUpdate>>,
+    },
+    Slot {
+        tx: watch::Sender<Option<(u64, Instant)>>,
+    },
+}
+
+impl Feed {
+    fn label(&self) -> &'static str {
+        match self {
+            Feed::Account { label, .. } => label,
+            Feed::Slot { .. } => "slot",
         }
+    }
 
-        let miner = bytemuck::try_from_bytes::<Miner>(&decoded[..std::mem::size_of::<Miner>()])
-            .map_err(|e| anyhow::anyhow!("Failed to parse Miner: {}", e))?;
+    fn subscribe_request(&self, id: u64, commitment: CommitmentConfig) -> SubscribeRequest {
+        let (method, params) = match self {
+            Feed::Account { address, .. } => ("accountSubscribe", vec![
+                serde_json::json!(address.to_string()),
+                serde_json::json!({
+                    "encoding": "base64",
+                    "commitment": commitment.commitment
+                }),
+            ]),
+            Feed::Slot { .. } => ("slotSubscribe", vec![]),
+        };
+
+        SubscribeRequest {
+            jsonrpc: "2.0".to_string(),
+            id,
+            method: method.to_string(),
+            params,
+        }
+    }
 
-        Ok(*miner)
+    /// Publish a notification's result to this feed's channel
+    fn publish(&self, result: serde_json::Value) -> Result<()> {
+        match self {
+            Feed::Account { tx, .. } => {
+                let result: AccountNotificationResult = serde_json::from_value(result)?;
+                let data = result.value.data
+                    .first()
+                    .ok_or_else(|| anyhow::anyhow!("No data in notification"))?;
+                let update = AccountUpdate {
+                    data: BASE64.decode(data)?,
+                    received: Instant::now(),
+                };
+                tx.send_replace(Some(update));
+            }
+            Feed::Slot { tx } => {
+                let info: SlotInfo = serde_json::from_value(result)?;
+                tx.send_replace(Some((info.slot, Instant::now())));
+            }
+        }
+        Ok(())
     }
 }
 
-impl MinerSubscription {
-    pub async fn new(rpc_url: String, miner_address: Pubkey) -> Result<Self> {
-        let miner_state = Arc::new(RwLock::new(None));
-        let miner_state_clone = miner_state.clone();
+/// One WebSocket connection carrying every account/slot subscription
+///
+/// Register streams with the `subscribe_*` methods, then call `start`.
+/// Each stream is a `watch` channel; on reconnect everything is resubscribed.
+pub struct SubscriptionManager {
+    ws_url: String,
+    commitment: CommitmentConfig, // Commitment of account notifications
+    feeds: Vec<Feed>,
+    stats: Arc<Mutex<ConnectionStats>>,
+    worker: Option<AbortHandle>, // Background WebSocket worker (aborted on shutdown)
+}
 
-        // Spawn persistent WebSocket worker
-        tokio::spawn(async move {
-            wss_worker(rpc_url, miner_address, miner_state_clone).await;
-        });
+impl SubscriptionManager {
+    pub fn new(ws_url: String, commitment: CommitmentConfig) -> Self {
+        Self { ws_url, commitment, feeds: Vec::new(), stats: Arc::default(), worker: None }
+    }
 
-        Ok(Self { miner_state })
+    /// Register an account subscription (must be called before `start`)
+    pub fn subscribe_account(&mut self, address: Pubkey, label: &'static str) -> watch::Receiver<Option<AccountUpdate>> {
+        let (tx, rx) = watch::channel(None);
+        self.feeds.push(Feed::Account { address, label, tx });
+        rx
+    }
+
+    pub fn subscribe_miner(&mut self, miner_address: Pubkey) -> MinerSubscription {
+        MinerSubscription {
+            updates: self.subscribe_account(miner_address, "miner"),
+            stats: self.stats.clone(),
+        }
+    }
+
+    pub fn subscribe_board(&mut self) -> BoardSubscription {
+        BoardSubscription { updates: self.subscribe_account(get_board_pda().0, "board") }
+    }
+
+    pub fn subscribe_slots(&mut self) -> SlotSubscription {
+        let (tx, updates) = watch::channel(None);
+        self.feeds.push(Feed::Slot { tx });
+        SlotSubscription { updates }
+    }
+
+    /// Spawn the persistent WebSocket worker
+    pub fn start(&mut self) {
+        let ws_url = self.ws_url.clone();
+        let commitment = self.commitment;
+        let feeds = std::mem::take(&mut self.feeds);
+        let stats = self.stats.clone();
+        log::info!("📡 Starting WebSocket worker with {} subscription(s)", feeds.len());
+
+        self.worker = Some(tokio::spawn(async move {
+            wss_worker(ws_url, commitment, feeds, stats).await;
+        }).abort_handle());
+    }
+
+    /// Stop the WebSocket worker (and its keep-alive task)
+    pub fn shutdown(&self) {
+        if let Some(worker) = &self.worker {
+            worker.abort();
+        }
+        log::info!("📡 WebSocket subscription stopped");
+    }
+}
+
+/// Miner account stream
+#[derive(Clone)]
+pub struct MinerSubscription {
+    updates: watch::Receiver<Option<AccountUpdate>>,
+    stats: Arc<Mutex<ConnectionStats>>, // Shared connection liveness
+}
+
+impl MinerSubscription {
+    /// Health of the WebSocket behind this stream; stale once nothing arrived for `stale_after`
+    pub fn health(&self, stale_after: Duration) -> SubscriptionHealth {
+        let stats = self.stats.lock().unwrap();
+        if !stats.connected {
+            return SubscriptionHealth::Disconnected;
+        }
+        match stats.last_update_at.map(|at| at.elapsed()) {
+            Some(age) if age > stale_after => SubscriptionHealth::Stale(age),
+            _ => SubscriptionHealth::Connected,
+        }
+    }
+
+    /// Times the WebSocket has reconnected since startup
+    pub fn reconnect_count(&self) -> u32 {
+        self.stats.lock().unwrap().reconnects
     }
 
     /// Get current miner state (updated by WebSocket in background)
     pub async fn get_miner(&self) -> Option<Miner> {
-        self.miner_state.read().await.clone()
+        decode_latest(&self.updates)
     }
 
     /// Wait briefly for WebSocket update, with short timeout (ore-app pattern)
     pub async fn wait_for_wss_update(&self, baseline: u64, timeout: Duration) -> Option<Miner> {
-        let start = tokio::time::Instant::now();
+        let mut updates = self.updates.clone();
+        let waited = tokio::time::timeout(timeout, updates.wait_for(|update| {
+            update.as_ref()
+                .and_then(|update| update.decode::<Miner>().ok())
+                .is_some_and(|miner| miner.rewards_sol > baseline)
+        })).await;
+
+        match waited {
+            Ok(Ok(update)) => {
+                let miner = update.as_ref()?.decode::<Miner>().ok()?;
+                log::info!("📬 WebSocket update: rewards_sol = {:.6} SOL, rewards_ore = {:.6} ORE",
+                    miner.rewards_sol as f64 / 1e9,
+                    miner.rewards_ore as f64 / 1e11);
+                Some(miner)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Board account stream, so round detection doesn't need RPC polling
+#[derive(Clone)]
+pub struct BoardSubscription {
+    updates: watch::Receiver<Option<AccountUpdate>>,
+}
+
+impl BoardSubscription {
+    /// Latest board, unless nothing has arrived within `max_age`
+    pub fn latest(&self, max_age: Duration) -> Option<Board> {
+        let fresh = self.updates.borrow()
+            .as_ref()
+            .is_some_and(|update| update.received.elapsed() <= max_age);
+        if fresh { decode_latest(&self.updates) } else { None }
+    }
 
-        while start.elapsed() < timeout {
-            if let Some(miner) = self.get_miner().await {
-                if miner.rewards_sol > baseline {
-                    return Some(miner);
+    /// Wait until the stream reports a round other than `round_id`, or `timeout` elapses
+    /// Returns the new board if the round changed in time
+    pub async fn wait_for_new_round(&self, round_id: u64, timeout: Duration) -> Option<Board> {
+        let mut updates = self.updates.clone();
+        let waited = tokio::time::timeout(timeout, updates.wait_for(|update| {
+            update.as_ref()
+                .and_then(|update| update.decode::<Board>().ok())
+                .is_some_and(|board| board.round_id != round_id)
+        })).await;
+
+        match waited {
+            Ok(Ok(update)) => update.as_ref()?.decode::<Board>().ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Latest slot pushed by `slotSubscribe`, so round timing doesn't need RPC polling
+#[derive(Clone)]
+pub struct SlotSubscription {
+    updates: watch::Receiver<Option<(u64, Instant)>>, // (slot, when it was received)
+}
+
+impl SlotSubscription {
+    /// Latest slot seen and how long ago it arrived (None before the first notification)
+    pub async fn current_slot(&self) -> Option<(u64, Duration)> {
+        self.updates.borrow().map(|(slot, received)| (slot, received.elapsed()))
+    }
+}
+
+/// Decode the latest value of an account channel
+fn decode_latest<T: bytemuck::Pod>(updates: &watch::Receiver<Option<AccountUpdate>>) -> Option<T> {
+    updates.borrow().as_ref().and_then(|update| match update.decode::<T>() {
+        Ok(value) => Some(value),
+        Err(e) => {
+            log::warn!("⚠️ Failed to decode account update: {}", e);
+            None
+        }
+    })
+}
+
+/// `signatureNotification` result: `err` is null once the transaction landed successfully
+#[derive(Debug, Clone, Deserialize)]
+struct SignatureNotificationResult {
+    value: SignatureNotificationValue,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SignatureNotificationValue {
+    err: Option<serde_json::Value>,
+}
+
+/// Render a notification's `err` the way getSignatureStatuses errors read
+/// ("Error processing Instruction 1: custom program error: 0x1" rather than raw JSON),
+/// so error classification and Ore error decoding treat both paths alike
+fn transaction_error_message(err: serde_json::Value) -> String {
+    match serde_json::from_value::<TransactionError>(err.clone()) {
+        Ok(err) => err.to_string(),
+        Err(_) => err.to_string(),
+    }
+}
+
+/// Push-based transaction confirmation through `signatureSubscribe`
+///
+/// Each signature gets its own short-lived connection, separate from the shared
+/// `SubscriptionManager` socket, so a slow confirmation never holds up account updates.
+#[derive(Debug, Clone)]
+pub struct SignatureSubscription {
+    ws_url: String,
+}
+
+impl SignatureSubscription {
+    pub fn new(ws_url: String) -> Self {
+        Self { ws_url }
+    }
+
+    /// Subscribe to `signature` before it is sent, so a fast landing can't be missed
+    /// Returns once the node has acknowledged the subscription (or `timeout` passes)
+    pub async fn subscribe(&self, signature: &Signature, commitment: CommitmentConfig, timeout: Duration) -> Result<SignatureWaiter> {
+        let subscribed = tokio::time::timeout(timeout, async {
+            let (mut ws_stream, _) = connect_async(&self.ws_url).await?;
+            let request = SubscribeRequest {
+                jsonrpc: "2.0".to_string(),
+                id: 1,
+                method: "signatureSubscribe".to_string(),
+                params: vec![
+                    serde_json::json!(signature.to_string()),
+                    serde_json::json!({ "commitment": commitment.commitment }),
+                ],
+            };
+            ws_stream.send(Message::Text(serde_json::to_string(&request)?.into())).await?;
+
+            while let Some(msg) = ws_stream.next().await {
+                if let Message::Text(text) = msg? {
+                    if let Some((1, subscription_id)) = parse_subscription_confirmation(&text) {
+                        return Ok(SignatureWaiter { ws_stream, subscription_id });
+                    }
+                    log::debug!("WebSocket message: {}", text);
                 }
             }
-            tokio::time::sleep(Duration::from_millis(100)).await;
-        }
+            anyhow::bail!("WebSocket closed before signatureSubscribe was acknowledged")
+        }).await;
+
+        subscribed.unwrap_or_else(|_| anyhow::bail!("signatureSubscribe not acknowledged within {:?}", timeout))
+    }
+}
+
+/// Open `signatureSubscribe` for one transaction; the node unsubscribes after the first notification
+pub struct SignatureWaiter {
+    ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    subscription_id: u64,
+}
 
-        None
+impl SignatureWaiter {
+    /// Wait until `deadline` for the signature to reach the subscribed commitment
+    /// Ok(Ok(())) when it landed successfully, Ok(Err(message)) when it landed but failed,
+    /// Err on timeout or a dropped connection (the caller should fall back to polling)
+    pub async fn wait(mut self, deadline: Instant) -> Result<std::result::Result<(), String>> {
+        let outcome = tokio::time::timeout_at(deadline, async {
+            while let Some(msg) = self.ws_stream.next().await {
+                let text = match msg? {
+                    Message::Text(text) => text,
+                    Message::Close(_) => break,
+                    _ => continue,
+                };
+                let Ok(notification) = serde_json::from_str::<SubscriptionNotification>(&text) else {
+                    log::debug!("WebSocket message: {}", text);
+                    continue;
+                };
+                if notification.params.subscription != self.subscription_id {
+                    continue;
+                }
+                let result: SignatureNotificationResult = serde_json::from_value(notification.params.result)?;
+                return Ok(match result.value.err {
+                    None => Ok(()),
+                    Some(err) => Err(transaction_error_message(err)),
+                });
+            }
+            anyhow::bail!("WebSocket closed before the signature notification")
+        }).await;
+
+        let _ = self.ws_stream.close(None).await;
+        outcome.unwrap_or_else(|_| anyhow::bail!("No signature notification before the WebSocket timeout"))
+    }
+}
+
+/// Aborts the wrapped task when dropped
+struct AbortOnDrop(JoinHandle<()>);
+
+impl AbortOnDrop {
+    fn abort(&self) {
+        self.0.abort();
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
     }
 }
 
 /// WebSocket worker with automatic reconnection
-async fn wss_worker(
-    rpc_url: String,
-    miner_address: Pubkey,
-    miner_state: Arc<RwLock<Option<Miner>>>,
-) {
+/// Request ids are feed indices + 1; confirmations map the returned subscription ids back to feeds
+async fn wss_worker(ws_url: String, commitment: CommitmentConfig, feeds: Vec<Feed>, stats: Arc<Mutex<ConnectionStats>>) {
+    let mut connected_before = false;
     let mut retry_delay_ms = 1000u64;
     const MAX_RETRY_DELAY_MS: u64 = 60 * 1000;
 
-    let ws_url = rpc_url
-        .replace("https://", "wss://")
-        .replace("http://", "ws://");
-
     // Reconnection loop
     loop {
         log::info!("📡 Attempting WebSocket connection...");
@@ -142,6 +492,16 @@ async fn wss_worker(
             Ok((ws_stream, _)) => {
                 log::info!("📡 WebSocket connected successfully");
                 retry_delay_ms = 1000; // Reset delay on successful connection
+                {
+                    let mut stats = stats.lock().unwrap();
+                    stats.connected = true;
+                    stats.touch();
+                    if connected_before {
+                        stats.reconnects += 1;
+                        log::info!("📡 WebSocket reconnect #{}", stats.reconnects);
+                    }
+                }
+                connected_before = true;
 
                 let (write, mut read) = ws_stream.split();
 
@@ -149,7 +509,8 @@ async fn wss_worker(
                 let write_for_keepalive = Arc::new(tokio::sync::Mutex::new(write));
                 let write_clone = write_for_keepalive.clone();
 
-                let keepalive_task = tokio::spawn(async move {
+                // Guarded so the keep-alive dies with the worker if it is aborted
+                let keepalive_task = AbortOnDrop(tokio::spawn(async move {
                     let mut interval = tokio::time::interval(Duration::from_secs(30));
                     interval.tick().await; // Skip first immediate tick
 
@@ -162,62 +523,55 @@ async fn wss_worker(
                         }
                         log::debug!("📡 Sent keep-alive ping");
                     }
-                });
+                }));
 
                 let write = write_for_keepalive;
 
-                // Subscribe to miner account
-                let subscribe_request = AccountSubscribeRequest {
-                    jsonrpc: "2.0".to_string(),
-                    id: 1,
-                    method: "accountSubscribe".to_string(),
-                    params: vec![
-                        serde_json::json!(miner_address.to_string()),
-                        serde_json::json!({
-                            "encoding": "base64",
-                            "commitment": "confirmed"
-                        }),
-                    ],
-                };
-
-                if let Ok(subscribe_msg) = serde_json::to_string(&subscribe_request) {
-                    let mut w = write.lock().await;
-                    if let Err(e) = w.send(Message::Text(subscribe_msg.into())).await {
-                        log::error!("Failed to send subscription request: {}", e);
-                        drop(w);
-                        keepalive_task.abort();
-                        sleep(Duration::from_millis(retry_delay_ms)).await;
-                        retry_delay_ms = (retry_delay_ms * 2).min(MAX_RETRY_DELAY_MS);
-                        continue;
+                // (Re)subscribe every feed; subscription ids are only valid for this connection
+                let mut subscribed = true;
+                for (index, feed) in feeds.iter().enumerate() {
+                    let request = feed.subscribe_request(index as u64 + 1, commitment);
+                    let sent = match serde_json::to_string(&request) {
+                        Ok(msg) => write.lock().await.send(Message::Text(msg.into())).await
+                            .map_err(anyhow::Error::from),
+                        Err(e) => Err(e.into()),
+                    };
+                    if let Err(e) = sent {
+                        log::error!("Failed to send {} subscription request: {}", feed.label(), e);
+                        subscribed = false;
+                        break;
                     }
-                    drop(w);
-                    log::info!("📡 Subscribed to miner account: {}", miner_address);
-                } else {
+                }
+                if !subscribed {
                     keepalive_task.abort();
-                    log::error!("Failed to serialize subscription request");
+                    stats.lock().unwrap().connected = false;
                     sleep(Duration::from_millis(retry_delay_ms)).await;
                     retry_delay_ms = (retry_delay_ms * 2).min(MAX_RETRY_DELAY_MS);
                     continue;
                 }
 
+                let mut subscriptions: HashMap<u64, usize> = HashMap::new(); // subscription id -> feed index
+
                 // Message handling loop
                 while let Some(msg) = read.next().await {
                     match msg {
                         Ok(Message::Text(text)) => {
-                            if let Ok(notification) = serde_json::from_str::<AccountNotification>(&text) {
-                                if notification.method == "accountNotification" {
-                                    // Parse and update miner state
-                                    match notification.parse_miner() {
-                                        Ok(miner) => {
-                                            log::info!("📬 WebSocket update: rewards_sol = {:.6} SOL, rewards_ore = {:.6} ORE",
-                                                miner.rewards_sol as f64 / 1e9,
-                                                miner.rewards_ore as f64 / 1e11);
-                                            *miner_state.write().await = Some(miner);
-                                        }
-                                        Err(e) => {
-                                            log::warn!("⚠️ Failed to parse miner notification: {}", e);
+                            stats.lock().unwrap().touch();
+                            if let Some((request_id, subscription_id)) = parse_subscription_confirmation(&text) {
+                                let index = request_id.wrapping_sub(1) as usize;
+                                if let Some(feed) = feeds.get(index) {
+                                    log::info!("📡 Subscribed to {} (subscription {})", feed.label(), subscription_id);
+                                    subscriptions.insert(subscription_id, index);
+                                }
+                            } else if let Ok(notification) = serde_json::from_str::<SubscriptionNotification>(&text) {
+                                match subscriptions.get(&notification.params.subscription).map(|&i| &feeds[i]) {
+                                    Some(feed) => {
+                                        if let Err(e) = feed.publish(notification.params.result) {
+                                            log::warn!("⚠️ Failed to parse {} notification: {}", feed.label(), e);
                                         }
                                     }
+                                    None => log::debug!("WebSocket {} for unknown subscription {}",
+                                        notification.method, notification.params.subscription),
                                 }
                             } else {
                                 log::debug!("WebSocket message: {}", text);
@@ -226,6 +580,7 @@ async fn wss_worker(
                         Ok(Message::Ping(_)) | Ok(Message::Pong(_)) => {
                             // tungstenite handles ping/pong automatically
                             log::debug!("📡 Ping/Pong (auto-handled)");
+                            stats.lock().unwrap().touch();
                         }
                         Ok(Message::Close(_)) => {
                             log::warn!("WebSocket closed by server");
@@ -243,6 +598,7 @@ async fn wss_worker(
 
                 // Connection lost, abort keep-alive task
                 keepalive_task.abort();
+                stats.lock().unwrap().connected = false;
             }
             Err(e) => {
                 log::error!("Failed to connect WebSocket: {}. Retrying in {}ms...", e, retry_delay_ms);
@@ -255,3 +611,30 @@ async fn wss_worker(
         log::warn!("Attempting WebSocket reconnection...");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_confirmation() {
+        assert_eq!(parse_subscription_confirmation(r#"{"jsonrpc":"2.0","result":23784,"id":2}"#), Some((2, 23784)));
+    }
+
+    #[test]
+    fn notifications_and_errors_are_not_confirmations() {
+        let notification = r#"{"jsonrpc":"2.0","method":"slotNotification","params":{"result":{"parent":1,"root":0,"slot":2},"subscription":0}}"#;
+        assert_eq!(parse_subscription_confirmation(notification), None);
+        let error = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params"},"id":3}"#;
+        assert_eq!(parse_subscription_confirmation(error), None);
+    }
+
+    #[test]
+    fn non_numeric_results_are_ignored() {
+        // Unsubscribe replies carry a bool
+        assert_eq!(parse_subscription_confirmation(r#"{"jsonrpc":"2.0","result":true,"id":4}"#), None);
+        assert_eq!(parse_subscription_confirmation(r#"{"jsonrpc":"2.0","result":"23784","id":5}"#), None);
+        assert_eq!(parse_subscription_confirmation(r#"{"jsonrpc":"2.0","result":-1,"id":6}"#), None);
+        assert_eq!(parse_subscription_confirmation("pong"), None);
+    }
+}
@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use serde_json::json;
+use std::time::Duration;
+use crate::config::TelegramConfig;
+use crate::notifier::{Channel, Notifier, QueuedSender};
+
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+const MIN_INTERVAL_MS: u64 = 1000; // Telegram allows about one message per second per chat
+const MAX_ATTEMPTS: u8 = 3;
+const MAX_MESSAGE_CHARS: usize = 4096; // sendMessage text limit
+
+/// Telegram Bot API backend: embeds are rendered as HTML text and sent with `sendMessage`
+pub struct TelegramNotifier {
+    send_message_url: String,
+    chat_id: String,
+    sender: QueuedSender,
+}
+
+impl TelegramNotifier {
+    pub fn new(config: &TelegramConfig) -> Self {
+        Self {
+            send_message_url: format!("{}/bot{}/sendMessage", TELEGRAM_API_URL, config.bot_token.trim()),
+            chat_id: config.chat_id.trim().to_string(),
+            sender: QueuedSender::new("Telegram", Duration::from_millis(MIN_INTERVAL_MS), MAX_ATTEMPTS),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn send(&self, _channel: Channel, message: &serde_json::Value) {
+        let payload = json!({
+            "chat_id": self.chat_id,
+            "text": render_html(message),
+            "parse_mode": "HTML",
+            "disable_web_page_preview": true,
+        });
+        self.sender.enqueue(&self.send_message_url, "Telegram sendMessage", payload);
+    }
+
+    async fn flush(&self) {
+        self.sender.flush().await;
+    }
+}
+
+/// Render an embed-shaped message as Telegram HTML: bold title, description, one line per field
+pub fn render_html(message: &serde_json::Value) -> String {
+    let Some(embed) = message["embeds"].get(0) else {
+        return String::new();
+    };
+
+    let mut text = format!("<b>{}</b>", escape_html(embed["title"].as_str().unwrap_or_default()));
+    if let Some(description) = embed["description"].as_str() {
+        text.push_str(&format!("\n{}", escape_html(description)));
+    }
+    for field in embed["fields"].as_array().into_iter().flatten() {
+        let name = escape_html(field["name"].as_str().unwrap_or_default());
+        let value = field["value"].as_str().unwrap_or_default();
+
+        if let Some(code) = value.strip_prefix("```").and_then(|v| v.strip_suffix("```")) {
+            text.push_str(&format!("\n<b>{}</b>\n<pre>{}</pre>", name, escape_html(code.trim())));
+        } else {
+            text.push_str(&format!("\n<b>{}:</b> {}", name, render_links(value)));
+        }
+    }
+
+    if text.chars().count() > MAX_MESSAGE_CHARS {
+        // Cutting may split a tag; Telegram rejects malformed HTML, so fall back to plain text
+        return message_plain(embed).chars().take(MAX_MESSAGE_CHARS).collect();
+    }
+    text
+}
+
+/// Unformatted version used when the HTML would be too long
+fn message_plain(embed: &serde_json::Value) -> String {
+    let mut text = embed["title"].as_str().unwrap_or_default().to_string();
+    for field in embed["fields"].as_array().into_iter().flatten() {
+        text.push_str(&format!("\n{}: {}",
+            field["name"].as_str().unwrap_or_default(),
+            field["value"].as_str().unwrap_or_default()));
+    }
+    escape_html(&text)
+}
+
+/// Turn markdown links `[text](url)` into `<a href>` tags, escaping everything else
+fn render_links(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find('[') {
+        let link = rest[start..].find("](").and_then(|mid| {
+            let close = rest[start + mid..].find(')')?;
+            Some((start + mid, start + mid + close))
+        });
+        let Some((mid, close)) = link else {
+            break;
+        };
+
+        out.push_str(&escape_html(&rest[..start]));
+        out.push_str(&format!("<a href=\"{}\">{}</a>",
+            escape_html(&rest[mid + 2..close]),
+            escape_html(&rest[start + 1..mid])));
+        rest = &rest[close + 1..];
+    }
+
+    out.push_str(&escape_html(rest));
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}